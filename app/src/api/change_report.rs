@@ -0,0 +1,163 @@
+use crate::models::{ChangeReport, ChangeReportSummary};
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use actix_web::{web, HttpResponse};
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Number of change reports listed on the scraper page
+pub const RECENT_REPORTS: i64 = 10;
+
+/// Get the counts of the most recent change reports, newest first
+#[server(GetChangeReports)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info"))]
+pub async fn get_change_reports() -> Result<Vec<ChangeReportSummary>, ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let reports = sqlx::query!(
+        r#"
+        SELECT
+            sr.id,
+            sr.previous_run_id as "previous_run_id!",
+            sr.completed_at,
+            COUNT(mc.id) FILTER (WHERE mc.change_type = 'added') as "added!",
+            COUNT(mc.id) FILTER (WHERE mc.change_type = 'removed') as "removed!",
+            COUNT(mc.id) FILTER (WHERE mc.change_type = 'changed') as "changed!"
+        FROM scraping_run sr
+        LEFT JOIN module_change mc ON mc.scraping_run_id = sr.id
+        WHERE sr.status = 'completed' AND sr.previous_run_id IS NOT NULL
+        GROUP BY sr.id
+        ORDER BY sr.id DESC
+        LIMIT $1
+        "#,
+        RECENT_REPORTS
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| ChangeReportSummary {
+        run_id: row.id,
+        previous_run_id: row.previous_run_id,
+        completed_at: row.completed_at,
+        added: row.added,
+        removed: row.removed,
+        changed: row.changed,
+    })
+    .collect();
+
+    Ok(reports)
+}
+
+/// Get the full change report of a run
+#[server(GetChangeReport)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", fields(run_id = run_id)))]
+pub async fn get_change_report(run_id: i32) -> Result<ChangeReport, ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+
+    load_change_report(&pool, run_id)
+        .await?
+        .ok_or_else(|| ServerFnError::new("Scraping run not found"))
+}
+
+/// Load the stored change report of a run
+///
+/// Returns `None` if the run does not exist.
+#[cfg(feature = "ssr")]
+pub async fn load_change_report(pool: &PgPool, run_id: i32) -> Result<Option<ChangeReport>, sqlx::Error> {
+    use crate::models::{ChangeReportEntry, FieldChangeInfo};
+    use std::collections::HashMap;
+
+    let Some(run) = sqlx::query!(
+        "SELECT previous_run_id, completed_at FROM scraping_run WHERE id = $1",
+        run_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let mut fields: HashMap<i32, Vec<FieldChangeInfo>> = HashMap::new();
+    for row in sqlx::query!(
+        r#"
+        SELECT f.module_change_id, f.field, f.old_value, f.new_value
+        FROM module_change_field f
+        JOIN module_change mc ON mc.id = f.module_change_id
+        WHERE mc.scraping_run_id = $1
+        ORDER BY f.id
+        "#,
+        run_id
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        fields.entry(row.module_change_id).or_default().push(FieldChangeInfo {
+            field: row.field,
+            old_value: row.old_value,
+            new_value: row.new_value,
+        });
+    }
+
+    let entries = sqlx::query!(
+        r#"
+        SELECT id, module_id, module_version, title, change_type::text as "change_type!"
+        FROM module_change
+        WHERE scraping_run_id = $1
+        ORDER BY module_id, module_version
+        "#,
+        run_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| ChangeReportEntry {
+        module_id: row.module_id,
+        module_version: row.module_version,
+        title: row.title,
+        change_type: row.change_type,
+        changes: fields.remove(&row.id).unwrap_or_default(),
+    })
+    .collect();
+
+    Ok(Some(ChangeReport {
+        run_id,
+        previous_run_id: run.previous_run_id,
+        completed_at: run.completed_at,
+        entries,
+    }))
+}
+
+/// Change report of a run as JSON
+#[cfg(feature = "ssr")]
+pub async fn change_report_json(path: web::Path<i32>, pool: web::Data<PgPool>) -> HttpResponse {
+    match load_change_report(&pool, path.into_inner()).await {
+        Ok(Some(report)) => HttpResponse::Ok().json(report),
+        Ok(None) => HttpResponse::NotFound().body("Scraping run not found"),
+        Err(e) => {
+            tracing::error!("Failed to load change report: {}", e);
+            HttpResponse::InternalServerError().body("Failed to load change report")
+        }
+    }
+}
+
+/// Change report of a run as a Markdown document
+#[cfg(feature = "ssr")]
+pub async fn change_report_markdown(path: web::Path<i32>, pool: web::Data<PgPool>) -> HttpResponse {
+    match load_change_report(&pool, path.into_inner()).await {
+        Ok(Some(report)) => HttpResponse::Ok()
+            .content_type("text/markdown; charset=utf-8")
+            .body(report.to_markdown()),
+        Ok(None) => HttpResponse::NotFound().body("Scraping run not found"),
+        Err(e) => {
+            tracing::error!("Failed to load change report: {}", e);
+            HttpResponse::InternalServerError().body("Failed to load change report")
+        }
+    }
+}
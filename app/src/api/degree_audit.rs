@@ -0,0 +1,144 @@
+use crate::models::{DegreeAudit, StupoOption};
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+#[cfg(feature = "ssr")]
+use crate::models::{AreaAudit, AuditModule};
+
+/// Get all StuPOs with their study program, for the audit selection
+#[server(GetStupoOptions)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info"))]
+pub async fn get_stupo_options() -> Result<Vec<StupoOption>, ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let stupos = sqlx::query!(
+        r#"
+        SELECT st.id, st.name, sp.name as program_name
+        FROM stupo st
+        JOIN study_program sp ON st.study_program_id = sp.id
+        ORDER BY sp.name, st.name
+        "#
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| StupoOption {
+        id: row.id,
+        program_name: row.program_name,
+        name: row.name,
+    })
+    .collect();
+
+    Ok(stupos)
+}
+
+/// Audit a set of modules against the catalog areas of a StuPO
+///
+/// A module listed in several areas is counted toward each of them and
+/// reported separately, since only the student can decide where it is booked.
+#[server(GetDegreeAudit)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip(keys), fields(key_count = keys.len())))]
+pub async fn get_degree_audit(stupo_id: i32, keys: Vec<(i32, i32)>) -> Result<DegreeAudit, ServerFnError> {
+    use leptos_actix::extract;
+    use std::collections::BTreeMap;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let (ids, versions): (Vec<i32>, Vec<i32>) = keys.into_iter().unzip();
+
+    let areas = sqlx::query!(
+        r#"
+        SELECT id, name, required_credits
+        FROM catalog_area
+        WHERE stupo_id = $1
+        ORDER BY name
+        "#,
+        stupo_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // One row per module and area it is listed in (or a single row without area)
+    let rows = sqlx::query!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        ),
+        selected AS (
+            SELECT * FROM UNNEST($2::int[], $3::int[]) AS s(id, version)
+        )
+        SELECT m.id, m.version, m.title, m.credits, ca.id as "area_id?", ca.name as "area_name?"
+        FROM selected s
+        JOIN latest_modules lm ON lm.id = s.id AND lm.version = s.version
+        JOIN module m ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+        LEFT JOIN (
+            module_catalog_area mca
+            JOIN catalog_area ca ON ca.id = mca.catalog_area_id AND ca.stupo_id = $1
+        ) ON mca.module_id = m.id AND mca.module_version = m.version AND mca.module_scraping_run_id = m.scraping_run_id
+        ORDER BY m.title, ca.name
+        "#,
+        stupo_id,
+        &ids,
+        &versions
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut modules: BTreeMap<(i32, i32), AuditModule> = BTreeMap::new();
+    let mut area_modules: BTreeMap<i32, Vec<(i32, i32)>> = BTreeMap::new();
+    for row in rows {
+        let key = (row.id, row.version);
+        let module = modules.entry(key).or_insert_with(|| AuditModule {
+            id: row.id,
+            version: row.version,
+            title: row.title.clone(),
+            credits: row.credits,
+            areas: Vec::new(),
+        });
+        if let (Some(area_id), Some(area_name)) = (row.area_id, row.area_name) {
+            module.areas.push(area_name);
+            area_modules.entry(area_id).or_default().push(key);
+        }
+    }
+
+    let areas = areas
+        .into_iter()
+        .map(|area| {
+            let area_modules: Vec<AuditModule> = area_modules
+                .get(&area.id)
+                .into_iter()
+                .flatten()
+                .filter_map(|key| modules.get(key).cloned())
+                .collect();
+            let earned_credits = area_modules.iter().map(|m| m.credits).sum();
+            AreaAudit {
+                id: area.id,
+                name: area.name,
+                required_credits: area.required_credits,
+                earned_credits,
+                missing_credits: area.required_credits.map(|required| (required - earned_credits).max(0)),
+                modules: area_modules,
+            }
+        })
+        .collect();
+
+    let shared_modules = modules.values().filter(|m| m.areas.len() > 1).cloned().collect();
+    let outside_modules = modules.values().filter(|m| m.areas.is_empty()).cloned().collect();
+    let total_credits = modules.values().map(|m| m.credits).sum();
+
+    Ok(DegreeAudit {
+        stupo_id,
+        areas,
+        shared_modules,
+        outside_modules,
+        total_credits,
+    })
+}
@@ -0,0 +1,159 @@
+use crate::models::{FilterFacets, SearchFilters};
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use crate::models::OrganizationUnit;
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Get the number of matching modules per filter option under the active filters
+#[server(GetFilterFacets)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all))]
+pub async fn get_filter_facets(
+    #[server(default)] filters: SearchFilters,
+) -> Result<FilterFacets, ServerFnError> {
+    use leptos_actix::extract;
+
+    super::modules::check_module_keys(&filters).map_err(ServerFnError::new)?;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>()
+        .await
+        ?;
+
+    Ok(load_filter_facets(&pool, &filters).await?)
+}
+
+/// Count matching modules per value of `key`
+///
+/// `joins` may add rows per module, modules are counted once per value.
+/// Relies on the module alias `m` like the search filters.
+#[cfg(feature = "ssr")]
+async fn facet_counts<K>(
+    pool: &PgPool,
+    filters: &SearchFilters,
+    key: &str,
+    joins: &str,
+) -> Result<std::collections::HashMap<K, i64>, sqlx::Error>
+where
+    K: for<'r> sqlx::Decode<'r, sqlx::Postgres> + sqlx::Type<sqlx::Postgres> + Eq + std::hash::Hash + Send + Unpin,
+{
+    let query_str = format!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        )
+        SELECT {key} AS value, COUNT(DISTINCT (m.id, m.version)) AS count
+        FROM module m
+        INNER JOIN latest_modules lm ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+        {joins}
+        WHERE {where_clause}
+        GROUP BY 1
+        "#,
+        where_clause = super::modules::filter_where_clause(filters)
+    );
+
+    let (ids, versions) = super::modules::module_key_binds(filters);
+    let counts = sqlx::query_as::<_, (K, i64)>(&query_str)
+        .bind(ids)
+        .bind(versions)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(counts)
+}
+
+/// Count the matching modules for every option of every multi-select filter
+#[cfg(feature = "ssr")]
+pub async fn load_filter_facets(pool: &PgPool, filters: &SearchFilters) -> Result<FilterFacets, sqlx::Error> {
+    const COMPONENTS: &str = "JOIN module_component mc ON mc.module_id = m.id AND mc.module_version = m.version";
+    const EXAMS: &str = "JOIN exam e ON e.module_id = m.id AND e.module_version = m.version AND e.module_scraping_run_id = m.scraping_run_id";
+
+    // Languages are matched within comma-separated lists like the language filter does,
+    // with regex characters in the language escaped
+    const LANGUAGES: &str = r#"
+        JOIN (
+            SELECT DISTINCT language, regexp_replace(language, '([.^$*+?()[\]{}|\\])', '\\\1', 'g') AS pattern
+            FROM module_component WHERE language IS NOT NULL AND language != ''
+        ) lang
+            ON EXISTS (SELECT 1 FROM module_component mc WHERE mc.module_id = m.id AND mc.module_version = m.version AND mc.language ~ ('(^|, )' || lang.pattern || '(, |$)'))
+    "#;
+
+    let without_programs = SearchFilters { study_program_ids: None, ..filters.clone() };
+    let without_rotations = SearchFilters { semester_rotations: None, ..filters.clone() };
+    let without_categories = SearchFilters { exam_categories: None, ..filters.clone() };
+    let without_types = SearchFilters { component_types: None, ..filters.clone() };
+    let without_languages = SearchFilters { component_languages: None, ..filters.clone() };
+    // Excluded exam forms count too, picking one replaces its exclusion
+    let without_exam_types = SearchFilters {
+        exam_types: None,
+        excluded_exam_types: None,
+        ..filters.clone()
+    };
+    let without_graded = SearchFilters { graded: None, ..filters.clone() };
+    let without_unit = |unit: OrganizationUnit| {
+        let mut without = filters.clone();
+        *unit.selected_mut(&mut without) = None;
+        without
+    };
+    let organization_keys = OrganizationUnit::ALL.map(|unit| format!("m.{}", unit.module_column()));
+    let [without_faculties, without_institutes, without_fachgebiete, without_persons, without_boards] =
+        OrganizationUnit::ALL.map(without_unit);
+
+    let (
+        study_programs,
+        semester_rotations,
+        exam_categories,
+        component_types,
+        component_languages,
+        exam_types,
+        graded,
+        faculties,
+        institutes,
+        fachgebiete,
+        responsible_persons,
+        examination_boards,
+    ) = futures::try_join!(
+        facet_counts(
+            pool,
+            &without_programs,
+            "st.study_program_id",
+            "JOIN module_catalog_usage mcu ON mcu.module_id = m.id AND mcu.module_version = m.version JOIN stupo st ON mcu.stupo_id = st.id",
+        ),
+        facet_counts(pool, &without_rotations, "mc.rotation::text", COMPONENTS),
+        facet_counts(
+            pool,
+            &without_categories,
+            "ec.category::text",
+            "JOIN exam e ON e.module_id = m.id AND e.module_version = m.version JOIN exam_component ec ON ec.exam_id = e.id",
+        ),
+        facet_counts(pool, &without_types, "mc.component_type::text", COMPONENTS),
+        facet_counts(pool, &without_languages, "lang.language", LANGUAGES),
+        facet_counts(pool, &without_exam_types, "e.exam_type", EXAMS),
+        facet_counts::<bool>(pool, &without_graded, "e.graded", EXAMS),
+        facet_counts(pool, &without_faculties, &organization_keys[0], ""),
+        facet_counts(pool, &without_institutes, &organization_keys[1], ""),
+        facet_counts(pool, &without_fachgebiete, &organization_keys[2], ""),
+        facet_counts(pool, &without_persons, &organization_keys[3], ""),
+        facet_counts(pool, &without_boards, &organization_keys[4], ""),
+    )?;
+
+    Ok(FilterFacets {
+        study_programs,
+        semester_rotations,
+        exam_categories,
+        component_types,
+        component_languages,
+        exam_types,
+        graded: graded.get(&true).copied().unwrap_or(0),
+        ungraded: graded.get(&false).copied().unwrap_or(0),
+        faculties,
+        institutes,
+        fachgebiete,
+        responsible_persons,
+        examination_boards,
+    })
+}
@@ -0,0 +1,129 @@
+#[cfg(feature = "ssr")]
+use crate::feed::{collect_feed_entries, render_atom_feed, FEED_RUNS};
+
+#[cfg(feature = "ssr")]
+use crate::models::ChangeReport;
+
+#[cfg(feature = "ssr")]
+use crate::notifications::public_base_url;
+
+#[cfg(feature = "ssr")]
+use actix_web::{web, HttpRequest, HttpResponse};
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Load the change reports of the most recent completed runs, newest first
+#[cfg(feature = "ssr")]
+async fn load_recent_reports(pool: &PgPool) -> Result<Vec<ChangeReport>, sqlx::Error> {
+    use super::change_report::load_change_report;
+
+    let run_ids = sqlx::query!(
+        r#"
+        SELECT id
+        FROM scraping_run
+        WHERE status = 'completed' AND previous_run_id IS NOT NULL
+        ORDER BY id DESC
+        LIMIT $1
+        "#,
+        FEED_RUNS
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut reports = Vec::new();
+    for run in run_ids {
+        if let Some(report) = load_change_report(pool, run.id).await? {
+            reports.push(report);
+        }
+    }
+
+    Ok(reports)
+}
+
+#[cfg(feature = "ssr")]
+fn atom_response(body: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/atom+xml; charset=utf-8")
+        .body(body)
+}
+
+/// Atom feed of module versions added or changed in recent runs
+#[cfg(feature = "ssr")]
+pub async fn changes_feed(req: HttpRequest, pool: web::Data<PgPool>) -> HttpResponse {
+    match load_recent_reports(&pool).await {
+        Ok(reports) => {
+            let entries = collect_feed_entries(reports, |_| true);
+            atom_response(render_atom_feed(
+                "TU Berlin modules: new and changed",
+                &public_base_url(),
+                req.path(),
+                &entries,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Failed to load change feed: {}", e);
+            HttpResponse::InternalServerError().body("Failed to load change feed")
+        }
+    }
+}
+
+/// Atom feed of module versions added or changed in recent runs that are
+/// used by a study program
+#[cfg(feature = "ssr")]
+pub async fn program_feed(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    use std::collections::HashSet;
+
+    let program_id = path.into_inner();
+
+    let result: Result<Option<String>, sqlx::Error> = async {
+        let Some(program) = sqlx::query!("SELECT name FROM study_program WHERE id = $1", program_id)
+            .fetch_optional(&**pool)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // Module versions of any snapshot, so changed catalog usages still match
+        let program_modules: HashSet<(i32, i32)> = sqlx::query!(
+            r#"
+            SELECT DISTINCT mcu.module_id, mcu.module_version
+            FROM module_catalog_usage mcu
+            JOIN stupo st ON mcu.stupo_id = st.id
+            WHERE st.study_program_id = $1
+            "#,
+            program_id
+        )
+        .fetch_all(&**pool)
+        .await?
+        .into_iter()
+        .map(|r| (r.module_id, r.module_version))
+        .collect();
+
+        let reports = load_recent_reports(&pool).await?;
+        let entries = collect_feed_entries(reports, |entry| {
+            program_modules.contains(&(entry.module_id, entry.module_version))
+        });
+
+        Ok(Some(render_atom_feed(
+            &format!("TU Berlin modules: new and changed in {}", program.name),
+            &public_base_url(),
+            req.path(),
+            &entries,
+        )))
+    }
+    .await;
+
+    match result {
+        Ok(Some(feed)) => atom_response(feed),
+        Ok(None) => HttpResponse::NotFound().body("Study program not found"),
+        Err(e) => {
+            tracing::error!("Failed to load program feed: {}", e);
+            HttpResponse::InternalServerError().body("Failed to load program feed")
+        }
+    }
+}
@@ -0,0 +1,874 @@
+//! GraphQL endpoint over the module catalog at `/graphql`
+//!
+//! Nested fields are resolved on demand from the latest snapshot of each
+//! module version, so clients only pay for what they select. Lookups of
+//! nested fields are batched across all modules of a response, and queries
+//! are limited in depth and complexity. `GET /graphql` serves GraphiQL for
+//! exploring the schema.
+
+use super::modules::{check_module_keys, count_modules, search_module_keys};
+use super::rest::{load_scraping_runs, ScrapingRunInfo};
+use crate::models::{ComponentInfo, ContactInfo, ExamComponentInfo, ExamInfo, PrerequisiteInfo, SearchFilters, SortOrder, WorkloadInfo};
+use actix_web::{web, HttpResponse};
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{ComplexObject, Context, EmptyMutation, EmptySubscription, InputObject, Object, Result, Schema, SimpleObject};
+use db::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Maximum nesting depth of a query, stops endless module → StuPO → module cycles
+const MAX_QUERY_DEPTH: usize = 10;
+
+/// Maximum complexity of a query, each field counts once per expected item
+const MAX_QUERY_COMPLEXITY: usize = 20_000;
+
+/// Expected length of lists without paging when estimating query complexity
+const LIST_COMPLEXITY: usize = 10;
+
+pub type CatalogSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn build_schema(pool: PgPool) -> CatalogSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(DataLoader::new(CatalogLoader { pool: pool.clone() }, tokio::spawn))
+        .data(pool)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Execute a GraphQL request
+pub async fn graphql(schema: web::Data<CatalogSchema>, request: web::Json<async_graphql::Request>) -> HttpResponse {
+    HttpResponse::Ok().json(schema.execute(request.into_inner()).await)
+}
+
+/// GraphiQL explorer for the endpoint
+pub async fn graphiql() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+/// Module version key
+#[derive(InputObject)]
+pub struct ModuleKey {
+    pub id: i32,
+    pub version: i32,
+}
+
+/// Search filters, the same as in the web interface
+///
+/// Starred modules and collections only exist in the browser, pass their keys
+/// as `moduleKeys`.
+#[derive(InputObject, Default)]
+pub struct ModuleFilter {
+    /// Case-insensitive substring of the module title, may contain filters
+    /// like `lp:>=6 lang:en -"Projekt"` as in the search box. A query that
+    /// doesn't parse is matched as plain title text.
+    pub search_query: Option<String>,
+    pub min_credits: Option<i32>,
+    pub max_credits: Option<i32>,
+    /// Semester rotations of any component, e.g. `WiSe/SoSe`
+    pub semester_rotations: Option<Vec<String>>,
+    /// Exam categories, e.g. `written`
+    pub exam_categories: Option<Vec<String>>,
+    pub study_program_ids: Option<Vec<i32>>,
+    /// Parts of study program names, case-insensitive, e.g. `Informatik`
+    pub study_program_names: Option<Vec<String>>,
+    /// Component types of any component, e.g. `VL`
+    pub component_types: Option<Vec<String>>,
+    /// Languages of any component, e.g. `Deutsch`
+    pub component_languages: Option<Vec<String>>,
+    pub faculty_ids: Option<Vec<i32>>,
+    pub institute_ids: Option<Vec<i32>>,
+    pub fachgebiet_ids: Option<Vec<i32>>,
+    pub responsible_person_ids: Option<Vec<i32>>,
+    pub examination_board_ids: Option<Vec<i32>>,
+    /// `true` for modules with a graded exam, `false` for an ungraded one
+    pub graded: Option<bool>,
+    /// Exam forms of any exam, e.g. `Portfolioprüfung`
+    pub exam_types: Option<Vec<String>>,
+    /// Exam forms no exam may have
+    pub excluded_exam_types: Option<Vec<String>>,
+    pub min_workload_hours: Option<i32>,
+    pub max_workload_hours: Option<i32>,
+    /// Only modules without a cap on attendees
+    #[graphql(default)]
+    pub no_attendance_limit: bool,
+    /// Modules with at least this many places or without a cap
+    pub min_attendee_places: Option<i32>,
+    /// Only these module versions, at most 2000
+    pub module_keys: Option<Vec<ModuleKey>>,
+    /// Keep only the newest still valid version per module number
+    #[graphql(default)]
+    pub newest_version_only: bool,
+    /// Include module versions missing from the latest full scraping run
+    #[graphql(default)]
+    pub include_discontinued: bool,
+    /// Result order, by relevance while searching and by title otherwise
+    #[graphql(default)]
+    pub sort: SortOrder,
+}
+
+impl From<ModuleFilter> for SearchFilters {
+    fn from(filter: ModuleFilter) -> Self {
+        SearchFilters {
+            search_query: filter.search_query,
+            min_credits: filter.min_credits,
+            max_credits: filter.max_credits,
+            semester_rotations: filter.semester_rotations,
+            exam_categories: filter.exam_categories,
+            study_program_ids: filter.study_program_ids,
+            study_program_names: filter.study_program_names,
+            component_types: filter.component_types,
+            component_languages: filter.component_languages,
+            faculty_ids: filter.faculty_ids,
+            institute_ids: filter.institute_ids,
+            fachgebiet_ids: filter.fachgebiet_ids,
+            responsible_person_ids: filter.responsible_person_ids,
+            examination_board_ids: filter.examination_board_ids,
+            graded: filter.graded,
+            exam_types: filter.exam_types,
+            excluded_exam_types: filter.excluded_exam_types,
+            min_workload_hours: filter.min_workload_hours,
+            max_workload_hours: filter.max_workload_hours,
+            no_attendance_limit: filter.no_attendance_limit,
+            min_attendee_places: filter.min_attendee_places,
+            module_keys: filter
+                .module_keys
+                .map(|keys| keys.into_iter().map(|k| (k.id, k.version)).collect()),
+            newest_version_only: filter.newest_version_only,
+            include_discontinued: filter.include_discontinued,
+            sort: filter.sort,
+            ..Default::default()
+        }
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Search module versions, in the order picked by the filter
+    #[graphql(complexity = "page_size.clamp(1, 500) as usize * child_complexity")]
+    async fn modules(
+        &self,
+        filter: Option<ModuleFilter>,
+        #[graphql(default = 0, validator(minimum = 0))] page: i64,
+        #[graphql(default = 50, validator(minimum = 1, maximum = 500))] page_size: i64,
+    ) -> Result<ModulePage> {
+        let filters = filter.map(SearchFilters::from).unwrap_or_default();
+        check_module_keys(&filters)?;
+        Ok(ModulePage { page, page_size, filters })
+    }
+
+    /// A module version, `None` if it does not exist
+    async fn module(&self, ctx: &Context<'_>, id: i32, version: i32) -> Result<Option<Module>> {
+        let pool = ctx.data::<PgPool>()?;
+        Ok(load_modules(pool, &[(id, version)]).await?.pop())
+    }
+
+    /// All study programs, ordered by name
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn study_programs(&self, ctx: &Context<'_>) -> Result<Vec<StudyProgram>> {
+        let pool = ctx.data::<PgPool>()?;
+        let programs = sqlx::query_as!(StudyProgram, "SELECT id, name, link FROM study_program ORDER BY name")
+            .fetch_all(pool)
+            .await?;
+        Ok(programs)
+    }
+
+    async fn study_program(&self, ctx: &Context<'_>, id: i32) -> Result<Option<StudyProgram>> {
+        let pool = ctx.data::<PgPool>()?;
+        let program = sqlx::query_as!(StudyProgram, "SELECT id, name, link FROM study_program WHERE id = $1", id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(program)
+    }
+
+    /// Scraping runs, newest first
+    async fn scraping_runs(&self, ctx: &Context<'_>) -> Result<Vec<ScrapingRunInfo>> {
+        let pool = ctx.data::<PgPool>()?;
+        Ok(load_scraping_runs(pool).await?)
+    }
+}
+
+/// One page of search results
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct ModulePage {
+    pub page: i64,
+    pub page_size: i64,
+    #[graphql(skip)]
+    filters: SearchFilters,
+}
+
+#[ComplexObject]
+impl ModulePage {
+    /// Number of module versions matching the filters across all pages
+    async fn total(&self, ctx: &Context<'_>) -> Result<i64> {
+        let pool = ctx.data::<PgPool>()?;
+        Ok(count_modules(pool, &self.filters, None).await?)
+    }
+
+    async fn items(&self, ctx: &Context<'_>) -> Result<Vec<Module>> {
+        let pool = ctx.data::<PgPool>()?;
+        let keys = search_module_keys(pool, &self.filters, self.page, self.page_size).await?;
+        Ok(load_modules(pool, &keys).await?)
+    }
+}
+
+/// A module version as of its latest snapshot
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Module {
+    pub id: i32,
+    pub version: i32,
+    pub title: String,
+    pub credits: i32,
+    pub languages: Vec<String>,
+    /// First semester, e.g. "WiSe 2023"
+    pub valid_since: Option<String>,
+    /// Last semester, e.g. "SoSe 2026"
+    pub valid_until: Option<String>,
+    pub faculty: Option<String>,
+    pub institute: Option<String>,
+    pub fachgebiet: Option<String>,
+    pub responsible_person: Option<String>,
+    pub examination_board: Option<String>,
+    pub learning_result: Option<String>,
+    pub content: Option<String>,
+    pub teaching_information: Option<String>,
+    pub requirements: Option<String>,
+    pub additional_info: Option<String>,
+    pub registration: Option<String>,
+    pub max_attendees: Option<i32>,
+    pub duration: Option<String>,
+    pub moses_link: String,
+    /// Run that first no longer listed this module version
+    pub discontinued_in_run_id: Option<i32>,
+    /// Run of the snapshot the fields were loaded from
+    pub scraping_run_id: i32,
+}
+
+fn semester_label(semester: Option<String>, year: Option<i32>) -> Option<String> {
+    match (semester, year) {
+        (Some(semester), Some(year)) => Some(format!("{} {}", semester, year)),
+        _ => None,
+    }
+}
+
+/// Load modules from their latest snapshots, in the order of the keys
+///
+/// Keys that do not exist are skipped.
+async fn load_modules(pool: &PgPool, keys: &[(i32, i32)]) -> Result<Vec<Module>, sqlx::Error> {
+    let (ids, versions): (Vec<i32>, Vec<i32>) = keys.iter().copied().unzip();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT DISTINCT ON (k.position)
+            m.id, m.version, m.scraping_run_id, m.title, m.credits, m.languages,
+            m.valid_since_semester::text as valid_since_semester,
+            m.valid_since_year,
+            m.valid_until_semester::text as valid_until_semester,
+            m.valid_until_year,
+            f.name as "faculty_name?",
+            i.name as "institute_name?",
+            fg.name as "fachgebiet_name?",
+            rp.name as "responsible_person_name?",
+            eb.name as "examination_board_name?",
+            m.learning_result,
+            m.content,
+            m.teaching_information,
+            m.requirements,
+            m.additional_info,
+            m.registration,
+            m.max_attendees,
+            m.duration,
+            m.moses_link,
+            m.discontinued_in_run_id
+        FROM UNNEST($1::int[], $2::int[]) WITH ORDINALITY AS k(id, version, position)
+        JOIN module m ON m.id = k.id AND m.version = k.version
+        LEFT JOIN faculty f ON m.faculty_id = f.id
+        LEFT JOIN institute i ON m.institute_id = i.id
+        LEFT JOIN fachgebiet fg ON m.fg_id = fg.id
+        LEFT JOIN responsible_person rp ON m.responsible_id = rp.id
+        LEFT JOIN examination_board eb ON m.examination_board_id = eb.id
+        ORDER BY k.position, m.scraping_run_id DESC
+        "#,
+        &ids,
+        &versions
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Module {
+            id: row.id,
+            version: row.version,
+            title: row.title,
+            credits: row.credits,
+            languages: row.languages,
+            valid_since: semester_label(row.valid_since_semester, row.valid_since_year),
+            valid_until: semester_label(row.valid_until_semester, row.valid_until_year),
+            faculty: row.faculty_name,
+            institute: row.institute_name,
+            fachgebiet: row.fachgebiet_name,
+            responsible_person: row.responsible_person_name,
+            examination_board: row.examination_board_name,
+            learning_result: row.learning_result,
+            content: row.content,
+            teaching_information: row.teaching_information,
+            requirements: row.requirements,
+            additional_info: row.additional_info,
+            registration: row.registration,
+            max_attendees: row.max_attendees,
+            duration: row.duration,
+            moses_link: row.moses_link,
+            discontinued_in_run_id: row.discontinued_in_run_id,
+            scraping_run_id: row.scraping_run_id,
+        })
+        .collect())
+}
+
+#[ComplexObject]
+impl Module {
+    async fn contact(&self, ctx: &Context<'_>) -> Result<Option<ContactInfo>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        Ok(loader.load_one(ContactKey(self.id, self.version, self.scraping_run_id)).await?)
+    }
+
+    async fn components(&self, ctx: &Context<'_>) -> Result<Vec<ComponentInfo>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        let components = loader.load_one(ComponentsKey(self.id, self.version, self.scraping_run_id)).await?;
+        Ok(components.unwrap_or_default())
+    }
+
+    /// Exams with their components
+    async fn exams(&self, ctx: &Context<'_>) -> Result<Vec<ExamInfo>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        let exams = loader.load_one(ExamsKey(self.id, self.version, self.scraping_run_id)).await?;
+        Ok(exams.unwrap_or_default())
+    }
+
+    async fn workload(&self, ctx: &Context<'_>) -> Result<Vec<WorkloadInfo>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        let workload = loader.load_one(WorkloadKey(self.id, self.version, self.scraping_run_id)).await?;
+        Ok(workload.unwrap_or_default())
+    }
+
+    /// StuPOs listing this module version and the semesters it is listed in
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn catalog_usages(&self, ctx: &Context<'_>) -> Result<Vec<CatalogUsage>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        let usages = loader.load_one(CatalogUsagesKey(self.id, self.version, self.scraping_run_id)).await?;
+        Ok(usages.unwrap_or_default())
+    }
+
+    /// Modules this one requires or recommends
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn prerequisites(&self, ctx: &Context<'_>) -> Result<Vec<PrerequisiteInfo>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        let prerequisites = loader.load_one(PrerequisitesKey(self.id, self.version, self.scraping_run_id)).await?;
+        Ok(prerequisites.unwrap_or_default())
+    }
+
+    /// Modules that require or recommend any version of this one
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn required_by(&self, ctx: &Context<'_>) -> Result<Vec<PrerequisiteInfo>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        Ok(loader.load_one(RequiredByKey(self.id)).await?.unwrap_or_default())
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct StudyProgram {
+    pub id: i32,
+    pub name: String,
+    pub link: String,
+}
+
+#[ComplexObject]
+impl StudyProgram {
+    /// Study and examination regulations of this program
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn stupos(&self, ctx: &Context<'_>) -> Result<Vec<Stupo>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        Ok(loader.load_one(StuposKey(self.id)).await?.unwrap_or_default())
+    }
+}
+
+/// Study and examination regulations (StuPO) of a study program
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct Stupo {
+    pub id: i32,
+    #[graphql(skip)]
+    pub study_program_id: i32,
+    pub name: String,
+    pub link: String,
+}
+
+#[ComplexObject]
+impl Stupo {
+    async fn study_program(&self, ctx: &Context<'_>) -> Result<Option<StudyProgram>> {
+        let loader = ctx.data::<DataLoader<CatalogLoader>>()?;
+        Ok(loader.load_one(StudyProgramKey(self.study_program_id)).await?)
+    }
+
+    /// Module versions listed in this StuPO with the semesters they are listed
+    /// in, one page at a time
+    #[graphql(complexity = "page_size.clamp(1, 500) as usize * child_complexity")]
+    async fn modules(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 0, validator(minimum = 0))] page: i64,
+        #[graphql(default = 100, validator(minimum = 1, maximum = 500))] page_size: i64,
+    ) -> Result<Vec<CatalogEntry>> {
+        let pool = ctx.data::<PgPool>()?;
+        let rows = sqlx::query!(
+            r#"
+            WITH latest_modules AS (
+                SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+                FROM module
+                ORDER BY id, version, scraping_run_id DESC
+            )
+            SELECT mcu.module_id, mcu.module_version, mcu.first_usage, mcu.last_usage
+            FROM module_catalog_usage mcu
+            JOIN latest_modules lm ON mcu.module_id = lm.id AND mcu.module_version = lm.version AND mcu.module_scraping_run_id = lm.scraping_run_id
+            WHERE mcu.stupo_id = $1
+            ORDER BY mcu.module_id, mcu.module_version
+            LIMIT $2 OFFSET $3
+            "#,
+            self.id,
+            page_size,
+            // Pages past the end are empty, whatever their offset
+            page.saturating_mul(page_size)
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let keys: Vec<(i32, i32)> = rows.iter().map(|row| (row.module_id, row.module_version)).collect();
+        let mut modules = load_modules(pool, &keys).await?.into_iter().peekable();
+
+        let mut entries = Vec::new();
+        for row in rows {
+            if let Some(module) = modules.next_if(|m| m.id == row.module_id && m.version == row.module_version) {
+                entries.push(CatalogEntry {
+                    module,
+                    first_usage: row.first_usage,
+                    last_usage: row.last_usage,
+                });
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// A StuPO listing a module, with the listing window
+#[derive(SimpleObject, Clone)]
+pub struct CatalogUsage {
+    pub stupo: Stupo,
+    /// First semester the module is listed in
+    pub first_usage: String,
+    /// Last semester the module is listed in
+    pub last_usage: String,
+}
+
+/// A module listed in a StuPO, with the listing window
+#[derive(SimpleObject)]
+pub struct CatalogEntry {
+    pub module: Module,
+    /// First semester the module is listed in
+    pub first_usage: String,
+    /// Last semester the module is listed in
+    pub last_usage: String,
+}
+
+/// Batches lookups of nested fields into one query per field and response
+///
+/// Nothing is cached, every request reads the current data.
+pub struct CatalogLoader {
+    pool: PgPool,
+}
+
+/// Contact of a module snapshot, `(id, version, scraping_run_id)`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContactKey(i32, i32, i32);
+
+/// Components of a module snapshot
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComponentsKey(i32, i32, i32);
+
+/// Exams of a module snapshot
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExamsKey(i32, i32, i32);
+
+/// Workload distribution of a module snapshot
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WorkloadKey(i32, i32, i32);
+
+/// StuPOs listing a module snapshot
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CatalogUsagesKey(i32, i32, i32);
+
+/// Prerequisites of a module snapshot
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrerequisitesKey(i32, i32, i32);
+
+/// Modules requiring any version of a module id
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RequiredByKey(i32);
+
+/// A study program by id
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StudyProgramKey(i32);
+
+/// StuPOs of a study program id
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StuposKey(i32);
+
+type LoadResult<K, V> = Result<HashMap<K, V>, Arc<sqlx::Error>>;
+
+/// Split snapshot keys into id, version and run arrays for `UNNEST`
+fn snapshot_binds(keys: impl Iterator<Item = (i32, i32, i32)>) -> (Vec<i32>, Vec<i32>, Vec<i32>) {
+    let mut binds = (Vec::new(), Vec::new(), Vec::new());
+    for (id, version, run_id) in keys {
+        binds.0.push(id);
+        binds.1.push(version);
+        binds.2.push(run_id);
+    }
+    binds
+}
+
+impl Loader<ContactKey> for CatalogLoader {
+    type Value = ContactInfo;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[ContactKey]) -> LoadResult<ContactKey, ContactInfo> {
+        let (ids, versions, runs) = snapshot_binds(keys.iter().map(|k| (k.0, k.1, k.2)));
+        let rows = sqlx::query!(
+            r#"
+            SELECT module_id, module_version, module_scraping_run_id, secretariat, contact_person, email, website
+            FROM contact
+            WHERE (module_id, module_version, module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+            "#,
+            &ids,
+            &versions,
+            &runs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let key = ContactKey(row.module_id, row.module_version, row.module_scraping_run_id);
+                let contact = ContactInfo {
+                    secretariat: row.secretariat,
+                    contact_person: row.contact_person,
+                    email: row.email,
+                    website: row.website,
+                };
+                (key, contact)
+            })
+            .collect())
+    }
+}
+
+impl Loader<ComponentsKey> for CatalogLoader {
+    type Value = Vec<ComponentInfo>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[ComponentsKey]) -> LoadResult<ComponentsKey, Vec<ComponentInfo>> {
+        let (ids, versions, runs) = snapshot_binds(keys.iter().map(|k| (k.0, k.1, k.2)));
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                module_id, module_version, module_scraping_run_id,
+                component_type::text as "component_type!",
+                module_name as name,
+                number,
+                rotation::text as "rotation!",
+                sws,
+                language
+            FROM module_component
+            WHERE (module_id, module_version, module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+            ORDER BY component_type, number
+            "#,
+            &ids,
+            &versions,
+            &runs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut components: HashMap<ComponentsKey, Vec<ComponentInfo>> = HashMap::new();
+        for row in rows {
+            let key = ComponentsKey(row.module_id, row.module_version, row.module_scraping_run_id);
+            components.entry(key).or_default().push(ComponentInfo {
+                component_type: row.component_type,
+                name: row.name,
+                number: row.number,
+                rotation: row.rotation,
+                sws: row.sws,
+                language: row.language,
+            });
+        }
+        Ok(components)
+    }
+}
+
+impl Loader<ExamsKey> for CatalogLoader {
+    type Value = Vec<ExamInfo>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[ExamsKey]) -> LoadResult<ExamsKey, Vec<ExamInfo>> {
+        let (ids, versions, runs) = snapshot_binds(keys.iter().map(|k| (k.0, k.1, k.2)));
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                e.module_id, e.module_version, e.module_scraping_run_id,
+                e.id, e.graded, e.exam_type, e.description,
+                ec.name as "component_name?",
+                ec.points as "points?",
+                ec.category::text as "category?",
+                ec.scope
+            FROM exam e
+            LEFT JOIN exam_component ec ON ec.exam_id = e.id
+            WHERE (e.module_id, e.module_version, e.module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+            ORDER BY e.id, ec.id
+            "#,
+            &ids,
+            &versions,
+            &runs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut exams: HashMap<ExamsKey, Vec<ExamInfo>> = HashMap::new();
+        for row in rows {
+            let module_exams = exams
+                .entry(ExamsKey(row.module_id, row.module_version, row.module_scraping_run_id))
+                .or_default();
+            if module_exams.last().is_none_or(|exam| exam.id != row.id) {
+                module_exams.push(ExamInfo {
+                    id: row.id,
+                    graded: row.graded,
+                    exam_type: row.exam_type,
+                    description: row.description,
+                    components: Vec::new(),
+                });
+            }
+            if let (Some(name), Some(points), Some(category)) = (row.component_name, row.points, row.category) {
+                if let Some(exam) = module_exams.last_mut() {
+                    exam.components.push(ExamComponentInfo {
+                        name,
+                        points,
+                        category,
+                        scope: row.scope,
+                    });
+                }
+            }
+        }
+        Ok(exams)
+    }
+}
+
+impl Loader<WorkloadKey> for CatalogLoader {
+    type Value = Vec<WorkloadInfo>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[WorkloadKey]) -> LoadResult<WorkloadKey, Vec<WorkloadInfo>> {
+        let (ids, versions, runs) = snapshot_binds(keys.iter().map(|k| (k.0, k.1, k.2)));
+        let rows = sqlx::query!(
+            r#"
+            SELECT module_id, module_version, module_scraping_run_id, description, total_hours as hours
+            FROM module_workload_distribution
+            WHERE (module_id, module_version, module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+            ORDER BY id
+            "#,
+            &ids,
+            &versions,
+            &runs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut workload: HashMap<WorkloadKey, Vec<WorkloadInfo>> = HashMap::new();
+        for row in rows {
+            let key = WorkloadKey(row.module_id, row.module_version, row.module_scraping_run_id);
+            workload.entry(key).or_default().push(WorkloadInfo {
+                description: row.description,
+                hours: row.hours,
+            });
+        }
+        Ok(workload)
+    }
+}
+
+impl Loader<CatalogUsagesKey> for CatalogLoader {
+    type Value = Vec<CatalogUsage>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[CatalogUsagesKey]) -> LoadResult<CatalogUsagesKey, Vec<CatalogUsage>> {
+        let (ids, versions, runs) = snapshot_binds(keys.iter().map(|k| (k.0, k.1, k.2)));
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                mcu.module_id, mcu.module_version, mcu.module_scraping_run_id,
+                st.id, st.study_program_id, st.name, st.link, mcu.first_usage, mcu.last_usage
+            FROM module_catalog_usage mcu
+            JOIN stupo st ON mcu.stupo_id = st.id
+            WHERE (mcu.module_id, mcu.module_version, mcu.module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+            ORDER BY st.name
+            "#,
+            &ids,
+            &versions,
+            &runs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut usages: HashMap<CatalogUsagesKey, Vec<CatalogUsage>> = HashMap::new();
+        for row in rows {
+            let key = CatalogUsagesKey(row.module_id, row.module_version, row.module_scraping_run_id);
+            usages.entry(key).or_default().push(CatalogUsage {
+                stupo: Stupo {
+                    id: row.id,
+                    study_program_id: row.study_program_id,
+                    name: row.name,
+                    link: row.link,
+                },
+                first_usage: row.first_usage,
+                last_usage: row.last_usage,
+            });
+        }
+        Ok(usages)
+    }
+}
+
+impl Loader<PrerequisitesKey> for CatalogLoader {
+    type Value = Vec<PrerequisiteInfo>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[PrerequisitesKey]) -> LoadResult<PrerequisitesKey, Vec<PrerequisiteInfo>> {
+        let (ids, versions, runs) = snapshot_binds(keys.iter().map(|k| (k.0, k.1, k.2)));
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                mp.module_id, mp.module_version, mp.module_scraping_run_id,
+                mp.required_module_id as "id!",
+                mp.required_module_version as "version!",
+                COALESCE(rm.title, mp.matched_text) as "title!",
+                mp.kind::text as "kind!"
+            FROM module_prerequisite mp
+            LEFT JOIN LATERAL (
+                SELECT title
+                FROM module
+                WHERE id = mp.required_module_id AND version = mp.required_module_version
+                ORDER BY scraping_run_id DESC
+                LIMIT 1
+            ) rm ON true
+            WHERE (mp.module_id, mp.module_version, mp.module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+            ORDER BY mp.kind, COALESCE(rm.title, mp.matched_text)
+            "#,
+            &ids,
+            &versions,
+            &runs
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut prerequisites: HashMap<PrerequisitesKey, Vec<PrerequisiteInfo>> = HashMap::new();
+        for row in rows {
+            let key = PrerequisitesKey(row.module_id, row.module_version, row.module_scraping_run_id);
+            prerequisites.entry(key).or_default().push(PrerequisiteInfo {
+                id: row.id,
+                version: row.version,
+                title: row.title,
+                kind: row.kind,
+            });
+        }
+        Ok(prerequisites)
+    }
+}
+
+impl Loader<RequiredByKey> for CatalogLoader {
+    type Value = Vec<PrerequisiteInfo>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[RequiredByKey]) -> LoadResult<RequiredByKey, Vec<PrerequisiteInfo>> {
+        let ids: Vec<i32> = keys.iter().map(|k| k.0).collect();
+        let rows = sqlx::query!(
+            r#"
+            WITH latest_modules AS (
+                SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+                FROM module
+                ORDER BY id, version, scraping_run_id DESC
+            )
+            SELECT DISTINCT mp.required_module_id, m.id, m.version, m.title, mp.kind::text as "kind!"
+            FROM module_prerequisite mp
+            JOIN latest_modules lm ON mp.module_id = lm.id AND mp.module_version = lm.version AND mp.module_scraping_run_id = lm.scraping_run_id
+            JOIN module m ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+            WHERE mp.required_module_id = ANY($1)
+            ORDER BY m.title, m.id, m.version
+            "#,
+            &ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut dependents: HashMap<RequiredByKey, Vec<PrerequisiteInfo>> = HashMap::new();
+        for row in rows {
+            dependents.entry(RequiredByKey(row.required_module_id)).or_default().push(PrerequisiteInfo {
+                id: row.id,
+                version: row.version,
+                title: row.title,
+                kind: row.kind,
+            });
+        }
+        Ok(dependents)
+    }
+}
+
+impl Loader<StudyProgramKey> for CatalogLoader {
+    type Value = StudyProgram;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[StudyProgramKey]) -> LoadResult<StudyProgramKey, StudyProgram> {
+        let ids: Vec<i32> = keys.iter().map(|k| k.0).collect();
+        let programs = sqlx::query_as!(
+            StudyProgram,
+            "SELECT id, name, link FROM study_program WHERE id = ANY($1)",
+            &ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(programs.into_iter().map(|p| (StudyProgramKey(p.id), p)).collect())
+    }
+}
+
+impl Loader<StuposKey> for CatalogLoader {
+    type Value = Vec<Stupo>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[StuposKey]) -> LoadResult<StuposKey, Vec<Stupo>> {
+        let ids: Vec<i32> = keys.iter().map(|k| k.0).collect();
+        let rows = sqlx::query_as!(
+            Stupo,
+            "SELECT id, study_program_id, name, link FROM stupo WHERE study_program_id = ANY($1) ORDER BY name",
+            &ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut stupos: HashMap<StuposKey, Vec<Stupo>> = HashMap::new();
+        for stupo in rows {
+            stupos.entry(StuposKey(stupo.study_program_id)).or_default().push(stupo);
+        }
+        Ok(stupos)
+    }
+}
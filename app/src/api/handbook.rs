@@ -0,0 +1,158 @@
+//! Generated module handbooks for a search, a collection or a catalog area
+
+use super::module_detail::load_module_detail;
+use super::modules::{check_module_keys, count_modules, search_module_keys};
+use crate::handbook::{render_handbook_html, render_handbook_pdf, Handbook, HandbookFormat};
+use crate::models::SearchFilters;
+use actix_web::{web, HttpResponse};
+use db::PgPool;
+use serde::Deserialize;
+
+/// Larger handbooks take too long to render in a single request
+pub const MAX_HANDBOOK_MODULES: i64 = 300;
+
+#[derive(Debug, Deserialize)]
+pub struct HandbookQuery {
+    /// Document title, derived from the source if missing
+    pub title: Option<String>,
+    /// `SearchFilters` as JSON
+    pub filters: Option<String>,
+    /// Catalog area whose modules are included, instead of `filters`
+    pub area: Option<i32>,
+}
+
+/// Module keys and the default title of a catalog area, ordered by title
+async fn area_modules(pool: &PgPool, area_id: i32) -> Result<Option<(String, Vec<(i32, i32)>)>, sqlx::Error> {
+    let Some(area) = sqlx::query!(
+        r#"
+        SELECT ca.name, st.name as stupo_name, sp.name as program_name
+        FROM catalog_area ca
+        JOIN stupo st ON ca.stupo_id = st.id
+        JOIN study_program sp ON st.study_program_id = sp.id
+        WHERE ca.id = $1
+        "#,
+        area_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let keys = sqlx::query!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        )
+        SELECT m.id, m.version
+        FROM module_catalog_area mca
+        JOIN latest_modules lm ON mca.module_id = lm.id AND mca.module_version = lm.version AND mca.module_scraping_run_id = lm.scraping_run_id
+        JOIN module m ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+        WHERE mca.catalog_area_id = $1
+        ORDER BY m.title, m.id, m.version
+        "#,
+        area_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.version))
+    .collect();
+
+    let title = format!("{} – {}: {}", area.program_name, area.stupo_name, area.name);
+    Ok(Some((title, keys)))
+}
+
+fn internal_error(e: sqlx::Error) -> HttpResponse {
+    tracing::error!("Failed to generate handbook: {}", e);
+    HttpResponse::InternalServerError().body("Failed to generate handbook")
+}
+
+/// Module handbook as printable HTML or PDF, e.g. `/api/handbook.pdf?area=12`
+pub async fn module_handbook(
+    path: web::Path<String>,
+    query: web::Query<HandbookQuery>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    let Some(format) = HandbookFormat::from_extension(&path) else {
+        return HttpResponse::NotFound().body("Unknown handbook format");
+    };
+
+    let (default_title, keys) = if let Some(area_id) = query.area {
+        match area_modules(&pool, area_id).await {
+            Ok(Some(area)) => area,
+            Ok(None) => return HttpResponse::NotFound().body("Catalog area not found"),
+            Err(e) => return internal_error(e),
+        }
+    } else {
+        let filters = match &query.filters {
+            Some(json) => match serde_json::from_str::<SearchFilters>(json) {
+                Ok(filters) => filters,
+                Err(_) => return HttpResponse::BadRequest().body("Invalid filters"),
+            },
+            None => return HttpResponse::BadRequest().body("Pass filters or a catalog area"),
+        };
+        if let Err(message) = check_module_keys(&filters) {
+            return HttpResponse::BadRequest().body(message);
+        }
+        let total = match count_modules(&pool, &filters, None).await {
+            Ok(total) => total,
+            Err(e) => return internal_error(e),
+        };
+        if total > MAX_HANDBOOK_MODULES {
+            return HttpResponse::BadRequest().body(format!(
+                "{} modules match, a handbook can contain at most {}. Narrow down the filters.",
+                total, MAX_HANDBOOK_MODULES
+            ));
+        }
+        match search_module_keys(&pool, &filters, 0, total.max(1)).await {
+            Ok(keys) => ("Module handbook".to_string(), keys),
+            Err(e) => return internal_error(e),
+        }
+    };
+
+    if keys.len() as i64 > MAX_HANDBOOK_MODULES {
+        return HttpResponse::BadRequest().body(format!(
+            "This catalog area lists {} modules, a handbook can contain at most {}.",
+            keys.len(),
+            MAX_HANDBOOK_MODULES
+        ));
+    }
+
+    let mut modules = Vec::with_capacity(keys.len());
+    for (id, version) in keys {
+        match load_module_detail(&pool, id, version).await {
+            Ok(Some(module)) => modules.push(module),
+            Ok(None) => {}
+            Err(e) => return internal_error(e),
+        }
+    }
+
+    let handbook = Handbook {
+        title: query
+            .title
+            .clone()
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or(default_title),
+        generated_on: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        modules,
+    };
+
+    match format {
+        HandbookFormat::Html => HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(render_handbook_html(&handbook)),
+        HandbookFormat::Pdf => match render_handbook_pdf(&handbook) {
+            Ok(pdf) => HttpResponse::Ok()
+                .content_type("application/pdf")
+                .insert_header(("Content-Disposition", "inline; filename=\"module-handbook.pdf\""))
+                .body(pdf),
+            Err(e) => {
+                tracing::error!("Failed to render handbook PDF: {}", e);
+                HttpResponse::InternalServerError().body("Failed to generate handbook")
+            }
+        },
+    }
+}
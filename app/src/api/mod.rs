@@ -1,7 +1,34 @@
+pub mod change_report;
+pub mod degree_audit;
+pub mod facets;
+pub mod feeds;
+#[cfg(feature = "ssr")]
+pub mod graphql;
+#[cfg(feature = "ssr")]
+pub mod handbook;
 pub mod module_detail;
+#[cfg(feature = "ssr")]
+pub mod module_export;
+pub mod module_graph;
+pub mod module_history;
+pub mod module_lineage;
 pub mod modules;
+pub mod notifications;
+#[cfg(feature = "ssr")]
+pub mod rest;
 pub mod scraper;
+pub mod shared_lists;
+pub mod sync;
 
+pub use change_report::*;
+pub use degree_audit::*;
+pub use facets::*;
 pub use module_detail::*;
+pub use module_graph::*;
+pub use module_history::*;
+pub use module_lineage::*;
 pub use modules::*;
+pub use notifications::*;
 pub use scraper::*;
+pub use shared_lists::*;
+pub use sync::*;
@@ -9,16 +9,52 @@ use db::PgPool;
 #[cfg_attr(feature = "ssr", tracing::instrument(level = "info", fields(module_id = id, module_version = version)))]
 pub async fn get_module_detail(id: i32, version: i32) -> Result<ModuleDetail, ServerFnError> {
     use leptos_actix::extract;
-    use sqlx::query;
 
     #[cfg(feature = "ssr")]
     tracing::info!("get_module_detail called for module_id={}, version={}", id, version);
 
     let pool = extract::<actix_web::web::Data<PgPool>>().await?;
-    let pool: &PgPool = &*pool;
+
+    load_module_detail(&pool, id, version)
+        .await?
+        .ok_or_else(|| ServerFnError::new("Module not found"))
+}
+
+/// Get the details of several module versions, e.g. for comparing them
+///
+/// Keys that do not exist (any more) are skipped, the order of the input is preserved.
+#[server(GetModuleDetails)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip(keys), fields(key_count = keys.len())))]
+pub async fn get_module_details(keys: Vec<(i32, i32)>) -> Result<Vec<ModuleDetail>, ServerFnError> {
+    use leptos_actix::extract;
+
+    if keys.len() > crate::compare::MAX_COMPARE {
+        return Err(ServerFnError::new(format!(
+            "At most {} modules can be compared",
+            crate::compare::MAX_COMPARE
+        )));
+    }
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+
+    let mut modules = Vec::with_capacity(keys.len());
+    for (id, version) in keys {
+        if let Some(module) = load_module_detail(&pool, id, version).await? {
+            modules.push(module);
+        }
+    }
+    Ok(modules)
+}
+
+/// Load the details of a module version from its latest snapshot
+///
+/// Returns `None` if the module version does not exist.
+#[cfg(feature = "ssr")]
+pub async fn load_module_detail(pool: &PgPool, id: i32, version: i32) -> Result<Option<ModuleDetail>, sqlx::Error> {
+    use sqlx::query;
 
     // Get the latest scraping_run_id for this module
-    let latest_run_id = query!(
+    let Some(latest) = query!(
         r#"
         SELECT scraping_run_id
         FROM module
@@ -29,9 +65,12 @@ pub async fn get_module_detail(id: i32, version: i32) -> Result<ModuleDetail, Se
         id,
         version
     )
-    .fetch_one(pool)
+    .fetch_optional(pool)
     .await?
-    .scraping_run_id;
+    else {
+        return Ok(None);
+    };
+    let latest_run_id = latest.scraping_run_id;
 
     // Fetch main module data with joined organizations
     let module_row = query!(
@@ -55,7 +94,8 @@ pub async fn get_module_detail(id: i32, version: i32) -> Result<ModuleDetail, Se
             m.registration,
             m.max_attendees,
             m.duration,
-            m.moses_link
+            m.moses_link,
+            m.discontinued_in_run_id
         FROM module m
         LEFT JOIN faculty f ON m.faculty_id = f.id
         LEFT JOIN institute i ON m.institute_id = i.id
@@ -237,7 +277,69 @@ pub async fn get_module_detail(id: i32, version: i32) -> Result<ModuleDetail, Se
     })
     .collect();
 
-    Ok(ModuleDetail {
+    // Fetch modules this one requires
+    let prerequisites = query!(
+        r#"
+        SELECT
+            mp.required_module_id as "id!",
+            mp.required_module_version as "version!",
+            COALESCE(rm.title, mp.matched_text) as "title!",
+            mp.kind::text as "kind!"
+        FROM module_prerequisite mp
+        LEFT JOIN LATERAL (
+            SELECT title
+            FROM module
+            WHERE id = mp.required_module_id AND version = mp.required_module_version
+            ORDER BY scraping_run_id DESC
+            LIMIT 1
+        ) rm ON true
+        WHERE mp.module_id = $1 AND mp.module_version = $2 AND mp.module_scraping_run_id = $3
+        ORDER BY mp.kind, COALESCE(rm.title, mp.matched_text)
+        "#,
+        id,
+        version,
+        latest_run_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|p| PrerequisiteInfo {
+        id: p.id,
+        version: p.version,
+        title: p.title,
+        kind: p.kind,
+    })
+    .collect();
+
+    // Fetch modules that require this one (from their latest snapshots)
+    let required_by = query!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        )
+        SELECT DISTINCT m.id, m.version, m.title, mp.kind::text as "kind!"
+        FROM module_prerequisite mp
+        JOIN latest_modules lm ON mp.module_id = lm.id AND mp.module_version = lm.version AND mp.module_scraping_run_id = lm.scraping_run_id
+        JOIN module m ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+        WHERE mp.required_module_id = $1
+        ORDER BY m.title, m.id, m.version
+        "#,
+        id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|p| PrerequisiteInfo {
+        id: p.id,
+        version: p.version,
+        title: p.title,
+        kind: p.kind,
+    })
+    .collect();
+
+    Ok(Some(ModuleDetail {
         id: module_row.id,
         version: module_row.version,
         title: module_row.title,
@@ -263,6 +365,9 @@ pub async fn get_module_detail(id: i32, version: i32) -> Result<ModuleDetail, Se
         exams,
         workload,
         study_programs,
+        prerequisites,
+        required_by,
         moses_link: module_row.moses_link,
-    })
+        discontinued_in_run_id: module_row.discontinued_in_run_id,
+    }))
 }
@@ -0,0 +1,191 @@
+//! Download of all modules matching the search filters as CSV, JSON or Excel
+//!
+//! CSV and JSON are streamed while the modules are loaded, Excel files can
+//! only be written once all rows are known and are capped for that reason.
+
+use super::modules::{check_module_keys, completed_snapshot, load_module_summaries, search_module_keys_after};
+use crate::export::{cell_text, parse_export_columns, ExportColumn, ExportColumns, ExportFormat};
+use crate::models::{ModuleSummary, SearchFilters};
+use actix_web::web::Bytes;
+use actix_web::{web, HttpResponse};
+use db::PgPool;
+use futures::stream::{Stream, StreamExt, TryStreamExt};
+use serde::Deserialize;
+use serde_json::Value;
+use std::pin::Pin;
+
+/// Modules looked up per search query while exporting
+const EXPORT_BATCH: i64 = 200;
+
+/// Most modules in one Excel export, those are held in memory until written
+const MAX_XLSX_MODULES: usize = 5_000;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// Comma-separated column keys, the default columns if missing
+    pub columns: Option<String>,
+    /// `SearchFilters` as JSON, all modules if missing
+    pub filters: Option<String>,
+}
+
+type ModuleStream = Pin<Box<dyn Stream<Item = Result<ModuleSummary, sqlx::Error>>>>;
+
+/// All modules matching the filters, in search order
+fn matching_modules(pool: PgPool, filters: SearchFilters) -> ModuleStream {
+    Box::pin(async_stream::try_stream! {
+        let snapshot = completed_snapshot(&pool).await?;
+        let mut cursor = None;
+        loop {
+            let (keys, next_cursor) = search_module_keys_after(&pool, &filters, snapshot, cursor.as_ref(), EXPORT_BATCH).await?;
+            for module in load_module_summaries(&pool, &keys, snapshot).await? {
+                yield module;
+            }
+            match next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+    })
+}
+
+fn csv_line(fields: impl IntoIterator<Item = String>) -> Bytes {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    // Writing into a Vec cannot fail
+    let _ = writer.write_record(fields.into_iter());
+    Bytes::from(writer.into_inner().unwrap_or_default())
+}
+
+fn json_object(columns: &[ExportColumn], module: &ModuleSummary) -> String {
+    let fields: Vec<String> = columns
+        .iter()
+        .map(|column| format!("{}:{}", Value::from(column.key()), column.value(module)))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn stream_error(e: sqlx::Error) -> actix_web::Error {
+    tracing::error!("Failed to export modules: {}", e);
+    actix_web::error::ErrorInternalServerError("Failed to export modules")
+}
+
+fn xlsx_workbook(columns: &[ExportColumn], modules: &[ModuleSummary]) -> Result<Vec<u8>, rust_xlsxwriter::XlsxError> {
+    use rust_xlsxwriter::{Format, Workbook};
+
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Modules")?;
+    let bold = Format::new().set_bold();
+
+    for (col, column) in columns.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, column.label(), &bold)?;
+    }
+    for (row, module) in modules.iter().enumerate() {
+        let row = row as u32 + 1;
+        for (col, column) in columns.iter().enumerate() {
+            match column.value(module) {
+                Value::Number(number) => sheet.write_number(row, col as u16, number.as_f64().unwrap_or_default())?,
+                value => sheet.write_string(row, col as u16, cell_text(&value))?,
+            };
+        }
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofit();
+
+    workbook.save_to_buffer()
+}
+
+/// Export all modules matching the filters, e.g. `/api/modules/export.csv?columns=id,title`
+pub async fn export_modules(
+    path: web::Path<String>,
+    query: web::Query<ExportQuery>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    let Some(format) = ExportFormat::from_extension(&path) else {
+        return HttpResponse::NotFound().body("Unknown export format");
+    };
+    let columns = match &query.columns {
+        Some(param) => match parse_export_columns(param) {
+            Ok(columns) => columns,
+            Err(message) => return HttpResponse::BadRequest().body(message),
+        },
+        None => ExportColumns::default().columns,
+    };
+    let filters = match &query.filters {
+        Some(json) => match serde_json::from_str::<SearchFilters>(json) {
+            Ok(filters) => filters,
+            Err(_) => return HttpResponse::BadRequest().body("Invalid filters"),
+        },
+        None => SearchFilters::default(),
+    };
+    if let Err(message) = check_module_keys(&filters) {
+        return HttpResponse::BadRequest().body(message);
+    }
+
+    let modules = matching_modules(pool.get_ref().clone(), filters);
+    let disposition = format!("attachment; filename=\"modules.{}\"", format.extension());
+
+    match format {
+        ExportFormat::Csv => {
+            let header = csv_line(columns.iter().map(|c| c.label().to_string()));
+            let rows = modules.map(move |module| {
+                module
+                    .map(|module| csv_line(columns.iter().map(|c| cell_text(&c.value(&module)))))
+                    .map_err(stream_error)
+            });
+            HttpResponse::Ok()
+                .content_type("text/csv; charset=utf-8")
+                .insert_header(("Content-Disposition", disposition))
+                .streaming(futures::stream::once(async { Ok::<_, actix_web::Error>(header) }).chain(rows))
+        }
+        ExportFormat::Json => {
+            let body: Pin<Box<dyn Stream<Item = Result<Bytes, actix_web::Error>>>> =
+                Box::pin(async_stream::stream! {
+                    let mut modules = modules;
+                    let mut separator = "[";
+                    while let Some(module) = modules.next().await {
+                        match module {
+                            Ok(module) => {
+                                yield Ok(Bytes::from(format!("{}{}", separator, json_object(&columns, &module))));
+                                separator = ",";
+                            }
+                            Err(e) => {
+                                yield Err(stream_error(e));
+                                return;
+                            }
+                        }
+                    }
+                    // An empty export still has to be a valid array
+                    yield Ok(Bytes::from(if separator == "[" { "[]" } else { "]" }));
+                });
+            HttpResponse::Ok()
+                .content_type("application/json")
+                .insert_header(("Content-Disposition", disposition))
+                .streaming(body)
+        }
+        ExportFormat::Xlsx => {
+            let modules = match modules.take(MAX_XLSX_MODULES + 1).try_collect::<Vec<_>>().await {
+                Ok(modules) if modules.len() > MAX_XLSX_MODULES => {
+                    return HttpResponse::BadRequest().body(format!(
+                        "Excel exports are limited to {} modules, narrow the filters or export CSV",
+                        MAX_XLSX_MODULES
+                    ));
+                }
+                Ok(modules) => modules,
+                Err(e) => {
+                    tracing::error!("Failed to export modules: {}", e);
+                    return HttpResponse::InternalServerError().body("Failed to export modules");
+                }
+            };
+            match xlsx_workbook(&columns, &modules) {
+                Ok(workbook) => HttpResponse::Ok()
+                    .content_type("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+                    .insert_header(("Content-Disposition", disposition))
+                    .body(workbook),
+                Err(e) => {
+                    tracing::error!("Failed to write Excel export: {}", e);
+                    HttpResponse::InternalServerError().body("Failed to export modules")
+                }
+            }
+        }
+    }
+}
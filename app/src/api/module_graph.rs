@@ -0,0 +1,128 @@
+use crate::models::ModuleGraph;
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use actix_web::{web, HttpResponse};
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Get the prerequisite graph around a module
+#[server(GetModuleGraph)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", fields(module_id = id, module_version = version)))]
+pub async fn get_module_graph(id: i32, version: i32) -> Result<ModuleGraph, ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+
+    load_module_graph(&pool, id, version)
+        .await?
+        .ok_or_else(|| ServerFnError::new("Module not found"))
+}
+
+/// Load all prerequisite relations and lay out the graph around a module
+///
+/// Returns `None` if the module version does not exist.
+#[cfg(feature = "ssr")]
+pub async fn load_module_graph(
+    pool: &PgPool,
+    id: i32,
+    version: i32,
+) -> Result<Option<ModuleGraph>, sqlx::Error> {
+    use crate::graph::{build_module_graph, NodeLabel, PrerequisiteEdge};
+    use std::collections::HashMap;
+
+    let labels: HashMap<(i32, i32), NodeLabel> = sqlx::query!(
+        r#"
+        SELECT DISTINCT ON (id, version) id, version, title, credits
+        FROM module
+        ORDER BY id, version, scraping_run_id DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| {
+        (
+            (row.id, row.version),
+            NodeLabel {
+                title: row.title,
+                credits: row.credits,
+            },
+        )
+    })
+    .collect();
+
+    if !labels.contains_key(&(id, version)) {
+        return Ok(None);
+    }
+
+    let relations: Vec<PrerequisiteEdge> = sqlx::query!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        )
+        SELECT
+            mp.module_id,
+            mp.module_version,
+            mp.required_module_id,
+            mp.required_module_version,
+            mp.kind::text as "kind!"
+        FROM module_prerequisite mp
+        JOIN latest_modules lm ON mp.module_id = lm.id AND mp.module_version = lm.version AND mp.module_scraping_run_id = lm.scraping_run_id
+        "#
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| PrerequisiteEdge {
+        module_id: row.module_id,
+        module_version: row.module_version,
+        required_id: row.required_module_id,
+        required_version: row.required_module_version,
+        kind: row.kind,
+    })
+    .collect();
+
+    Ok(Some(build_module_graph(id, version, &relations, &labels)))
+}
+
+/// Prerequisite graph as JSON, including the computed layout
+#[cfg(feature = "ssr")]
+pub async fn module_graph_json(
+    path: web::Path<(i32, i32)>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    let (id, version) = path.into_inner();
+
+    match load_module_graph(&pool, id, version).await {
+        Ok(Some(graph)) => HttpResponse::Ok().json(graph),
+        Ok(None) => HttpResponse::NotFound().body("Module not found"),
+        Err(e) => {
+            tracing::error!("Failed to load module graph: {}", e);
+            HttpResponse::InternalServerError().body("Failed to load module graph")
+        }
+    }
+}
+
+/// Prerequisite graph rendered as a standalone SVG image
+#[cfg(feature = "ssr")]
+pub async fn module_graph_svg(
+    path: web::Path<(i32, i32)>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    let (id, version) = path.into_inner();
+
+    match load_module_graph(&pool, id, version).await {
+        Ok(Some(graph)) => HttpResponse::Ok()
+            .content_type("image/svg+xml")
+            .body(crate::graph::render_graph_svg(&graph)),
+        Ok(None) => HttpResponse::NotFound().body("Module not found"),
+        Err(e) => {
+            tracing::error!("Failed to load module graph: {}", e);
+            HttpResponse::InternalServerError().body("Failed to load module graph")
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::models::ModuleHistoryEntry;
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Get the runs in which a module version first appeared or changed, newest first
+#[server(GetModuleHistory)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", fields(module_id = id, module_version = version)))]
+pub async fn get_module_history(id: i32, version: i32) -> Result<Vec<ModuleHistoryEntry>, ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+
+    Ok(load_module_history(&pool, id, version).await?)
+}
+
+#[cfg(feature = "ssr")]
+async fn load_module_history(pool: &PgPool, id: i32, version: i32) -> Result<Vec<ModuleHistoryEntry>, db::DbError> {
+    use crate::models::FieldChangeInfo;
+    use std::collections::HashMap;
+
+    let snapshots = db::load_module_snapshots(pool, id, version).await?;
+
+    let run_ids: Vec<i32> = snapshots.iter().map(|s| s.scraping_run_id).collect();
+    let started: HashMap<i32, chrono::DateTime<chrono::Utc>> = sqlx::query!(
+        "SELECT id, started_at FROM scraping_run WHERE id = ANY($1)",
+        &run_ids
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.started_at))
+    .collect();
+
+    let mut entries = Vec::new();
+    let mut previous: Option<&db::ModuleSnapshot> = None;
+
+    for snapshot in &snapshots {
+        let changes: Vec<FieldChangeInfo> = match previous {
+            Some(old) => db::diff_snapshots(old, snapshot).into_iter().map(Into::into).collect(),
+            None => Vec::new(),
+        };
+
+        // Runs that re-scraped the module without changes are left out
+        if previous.is_none() || !changes.is_empty() {
+            entries.push(ModuleHistoryEntry {
+                scraping_run_id: snapshot.scraping_run_id,
+                started_at: started.get(&snapshot.scraping_run_id).copied().unwrap_or_default(),
+                first_seen: previous.is_none(),
+                changes,
+            });
+        }
+        previous = Some(snapshot);
+    }
+
+    entries.reverse();
+    Ok(entries)
+}
@@ -0,0 +1,83 @@
+use crate::models::ModuleLineage;
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Get all versions of a module number and the changes between consecutive versions
+#[server(GetModuleLineage)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", fields(module_id = id)))]
+pub async fn get_module_lineage(id: i32) -> Result<ModuleLineage, ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+
+    Ok(load_module_lineage(&pool, id).await?)
+}
+
+#[cfg(feature = "ssr")]
+async fn load_module_lineage(pool: &PgPool, id: i32) -> Result<ModuleLineage, db::DbError> {
+    use crate::models::{ModuleVersionInfo, VersionDiff};
+    use crate::planner::Term;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT DISTINCT ON (version)
+            version, title,
+            valid_since_semester::text as valid_since_semester,
+            valid_since_year,
+            valid_until_semester::text as valid_until_semester,
+            valid_until_year
+        FROM module
+        WHERE id = $1
+        ORDER BY version, scraping_run_id DESC
+        "#,
+        id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let current = Term::current();
+    let mut versions: Vec<ModuleVersionInfo> = rows
+        .into_iter()
+        .map(|row| {
+            let until = row
+                .valid_until_semester
+                .as_deref()
+                .zip(row.valid_until_year)
+                .and_then(|(semester, year)| Term::from_db(semester, year));
+            // Same format as the validity period on the detail page
+            let format_term = |semester: Option<String>, year: Option<i32>| {
+                semester.zip(year).map(|(semester, year)| format!("{} {}", semester, year))
+            };
+            ModuleVersionInfo {
+                version: row.version,
+                title: row.title,
+                valid_since: format_term(row.valid_since_semester, row.valid_since_year),
+                valid_until: format_term(row.valid_until_semester, row.valid_until_year),
+                expired: until.is_some_and(|term| term < current),
+                newest_valid: false,
+            }
+        })
+        .collect();
+
+    let newest = versions
+        .iter()
+        .rposition(|v| !v.expired)
+        .or(versions.len().checked_sub(1));
+    if let Some(index) = newest {
+        versions[index].newest_valid = true;
+    }
+
+    let snapshots = db::load_version_snapshots(pool, id).await?;
+    let diffs = snapshots
+        .windows(2)
+        .map(|pair| VersionDiff {
+            from_version: pair[0].version,
+            to_version: pair[1].version,
+            changes: db::diff_snapshots(&pair[0], &pair[1]).into_iter().map(Into::into).collect(),
+        })
+        .collect();
+
+    Ok(ModuleLineage { id, versions, diffs })
+}
@@ -1,4 +1,4 @@
-use crate::models::{FilterOptions, ModuleSummary, SearchFilters};
+use crate::models::{FilterOptions, ModuleSearchPage, ModuleSummary, SearchFilters};
 use leptos::prelude::*;
 use chrono::{DateTime, Utc};
 
@@ -6,7 +6,9 @@ use chrono::{DateTime, Utc};
 use db::PgPool;
 
 #[cfg(feature = "ssr")]
-use crate::models::ComponentInfo;
+use crate::models::{ComponentInfo, OrganizationOption, OrganizationUnit, SortOrder};
+#[cfg(feature = "ssr")]
+use crate::query_syntax::{parse_query, ParsedQuery};
 
 /// Get the timestamp of the latest completed scraping run
 #[server(GetLatestScrapingRun)]
@@ -35,116 +37,42 @@ pub async fn get_latest_scraping_run() -> Result<Option<DateTime<Utc>>, ServerFn
 }
 
 /// Get total count of modules matching filters
+///
+/// Pass the `snapshot` of the first search page so the count matches the
+/// results, `None` counts the latest data.
 #[server(GetModuleCount)]
 #[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all, fields(filter_count = filters.search_query.is_some())))]
 pub async fn get_module_count(
     #[server(default)] filters: SearchFilters,
+    #[server(default)] snapshot: Option<i32>,
 ) -> Result<i64, ServerFnError> {
     use leptos_actix::extract;
-    use sqlx::Row;
 
     #[cfg(feature = "ssr")]
     tracing::info!("get_module_count called");
 
+    check_module_keys(&filters).map_err(ServerFnError::new)?;
+
     let pool = extract::<actix_web::web::Data<PgPool>>()
         .await
         ?;
-    let pool: &PgPool = &*pool;
-
-    // Build dynamic WHERE clauses
-    let mut where_clauses = vec!["1=1".to_string()];
-
-    if let Some(ref query) = filters.search_query {
-        if !query.is_empty() {
-            where_clauses.push(format!(
-                "m.title ILIKE '%{}%'",
-                query.replace('\'', "''")
-            ));
-        }
-    }
-
-    if let Some(min) = filters.min_credits {
-        where_clauses.push(format!("m.credits >= {}", min));
-    }
-    if let Some(max) = filters.max_credits {
-        where_clauses.push(format!("m.credits <= {}", max));
-    }
-
-    if let Some(ref rotations) = filters.semester_rotations {
-        if !rotations.is_empty() {
-            let rotation_strs: Vec<String> = rotations
-                .iter()
-                .map(|r| format!("'{}'", r.replace('\'', "''")))
-                .collect();
-            where_clauses.push(format!(
-                "EXISTS (SELECT 1 FROM module_component mc WHERE mc.module_id = m.id AND mc.module_version = m.version AND mc.rotation::text IN ({}))",
-                rotation_strs.join(", ")
-            ));
-        }
-    }
 
-    if let Some(ref categories) = filters.exam_categories {
-        if !categories.is_empty() {
-            let category_strs: Vec<String> = categories
-                .iter()
-                .map(|c| format!("'{}'", c.replace('\'', "''")))
-                .collect();
-            where_clauses.push(format!(
-                "EXISTS (SELECT 1 FROM exam e JOIN exam_component ec ON e.id = ec.exam_id WHERE e.module_id = m.id AND e.module_version = m.version AND ec.category::text IN ({}))",
-                category_strs.join(", ")
-            ));
-        }
-    }
-
-    if let Some(ref ids) = filters.study_program_ids {
-        if !ids.is_empty() {
-            let id_strs: Vec<String> = ids
-                .iter()
-                .map(|id| id.to_string())
-                .collect();
-            where_clauses.push(format!(
-                "EXISTS (SELECT 1 FROM module_catalog_usage mcu JOIN stupo st ON mcu.stupo_id = st.id WHERE mcu.module_id = m.id AND mcu.module_version = m.version AND st.study_program_id IN ({}))",
-                id_strs.join(", ")
-            ));
-        }
-    }
-
-    if let Some(ref types) = filters.component_types {
-        if !types.is_empty() {
-            let type_strs: Vec<String> = types
-                .iter()
-                .map(|t| format!("'{}'", t.replace('\'', "''")))
-                .collect();
-            where_clauses.push(format!(
-                "EXISTS (SELECT 1 FROM module_component mc WHERE mc.module_id = m.id AND mc.module_version = m.version AND mc.component_type::text IN ({}))",
-                type_strs.join(", ")
-            ));
-        }
-    }
-
-    if let Some(ref langs) = filters.component_languages {
-        if !langs.is_empty() {
-            let lang_conditions: Vec<String> = langs
-                .iter()
-                .map(|l| {
-                    let escaped = l.replace('\'', "''");
-                    format!("mc.language ~ '(^|, ){}(, |$)'", escaped)
-                })
-                .collect();
-            where_clauses.push(format!(
-                "EXISTS (SELECT 1 FROM module_component mc WHERE mc.module_id = m.id AND mc.module_version = m.version AND ({}))",
-                lang_conditions.join(" OR ")
-            ));
-        }
-    }
+    Ok(count_modules(&pool, &filters, snapshot).await?)
+}
 
-    let where_clause = where_clauses.join(" AND ");
+/// Count the module versions matching the filters
+///
+/// Scraping runs after `snapshot` are ignored, `None` counts all of them.
+#[cfg(feature = "ssr")]
+pub async fn count_modules(pool: &PgPool, filters: &SearchFilters, snapshot: Option<i32>) -> Result<i64, sqlx::Error> {
+    use sqlx::Row;
 
     let query_str = format!(
         r#"
         WITH latest_modules AS (
             SELECT DISTINCT ON (id, version) id, version, scraping_run_id
             FROM module
+            {}
             ORDER BY id, version, scraping_run_id DESC
         )
         SELECT COUNT(DISTINCT (m.id, m.version))
@@ -152,10 +80,14 @@ pub async fn get_module_count(
         INNER JOIN latest_modules lm ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
         WHERE {}
         "#,
-        where_clause
+        snapshot_clause(snapshot),
+        filter_where_clause(filters)
     );
 
+    let (ids, versions) = module_key_binds(filters);
     let row = sqlx::query(&query_str)
+        .bind(ids)
+        .bind(versions)
         .fetch_one(pool)
         .await
         ?;
@@ -170,12 +102,18 @@ pub async fn get_module_count(
 #[cfg_attr(feature = "ssr", tracing::instrument(level = "info"))]
 pub async fn get_filter_options() -> Result<FilterOptions, ServerFnError> {
     use leptos_actix::extract;
-    use sqlx::query;
 
     let pool = extract::<actix_web::web::Data<PgPool>>()
         .await
         ?;
-    let pool: &PgPool = &*pool;
+
+    Ok(load_filter_options(&pool).await?)
+}
+
+/// Load the values offered by the search filters from the latest snapshots
+#[cfg(feature = "ssr")]
+pub async fn load_filter_options(pool: &PgPool) -> Result<FilterOptions, sqlx::Error> {
+    use sqlx::query;
 
     // Get all study programs
     let study_programs = query!(
@@ -281,6 +219,34 @@ pub async fn get_filter_options() -> Result<FilterOptions, ServerFnError> {
     .map(|row| row.language)
     .collect();
 
+    // Get distinct exam forms (from latest runs only)
+    let exam_types = query!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        )
+        SELECT DISTINCT e.exam_type
+        FROM exam e
+        JOIN latest_modules lm ON e.module_id = lm.id AND e.module_version = lm.version AND e.module_scraping_run_id = lm.scraping_run_id
+        WHERE e.exam_type != ''
+        ORDER BY e.exam_type
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    ?
+    .into_iter()
+    .map(|row| row.exam_type)
+    .collect();
+
+    let faculties = load_organization_options(pool, OrganizationUnit::Faculty).await?;
+    let institutes = load_organization_options(pool, OrganizationUnit::Institute).await?;
+    let fachgebiete = load_organization_options(pool, OrganizationUnit::Fachgebiet).await?;
+    let responsible_persons = load_organization_options(pool, OrganizationUnit::ResponsiblePerson).await?;
+    let examination_boards = load_organization_options(pool, OrganizationUnit::ExaminationBoard).await?;
+
     // Get credit range (from latest runs only)
     let credit_range = query!(
         r#"
@@ -298,46 +264,397 @@ pub async fn get_filter_options() -> Result<FilterOptions, ServerFnError> {
     .await
     ?;
 
+    // Get total workload range (from latest runs only)
+    let workload_range = query!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        ),
+        workloads AS (
+            SELECT SUM(w.total_hours) as total
+            FROM module_workload_distribution w
+            JOIN latest_modules lm ON w.module_id = lm.id AND w.module_version = lm.version AND w.module_scraping_run_id = lm.scraping_run_id
+            GROUP BY lm.id, lm.version
+        )
+        SELECT COALESCE(FLOOR(MIN(total)), 0)::int as "min!", COALESCE(CEIL(MAX(total)), 0)::int as "max!"
+        FROM workloads
+        "#
+    )
+    .fetch_one(pool)
+    .await
+    ?;
+
+    // Get attendee limit range (from latest runs only)
+    let attendee_limit_range = query!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        )
+        SELECT COALESCE(MIN(m.max_attendees), 0) as "min!", COALESCE(MAX(m.max_attendees), 0) as "max!"
+        FROM module m
+        JOIN latest_modules lm ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+        "#
+    )
+    .fetch_one(pool)
+    .await
+    ?;
+
     Ok(FilterOptions {
         study_programs,
         exam_categories,
         semester_rotations,
         component_types,
         component_languages,
+        faculties,
+        institutes,
+        fachgebiete,
+        responsible_persons,
+        examination_boards,
+        exam_types,
         credit_range: (credit_range.min, credit_range.max),
+        workload_range: (workload_range.min, workload_range.max),
+        attendee_limit_range: (attendee_limit_range.min, attendee_limit_range.max),
     })
 }
 
-/// Search modules with filters and pagination
+/// Organizational units referenced by the latest module snapshots, ordered by name
+#[cfg(feature = "ssr")]
+async fn load_organization_options(pool: &PgPool, unit: OrganizationUnit) -> Result<Vec<OrganizationOption>, sqlx::Error> {
+    let query_str = format!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        )
+        SELECT DISTINCT o.id, o.name
+        FROM module m
+        JOIN latest_modules lm ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+        JOIN {table} o ON m.{column} = o.id
+        WHERE o.name != ''
+        ORDER BY o.name
+        "#,
+        table = unit.table(),
+        column = unit.module_column()
+    );
+
+    let options = sqlx::query_as::<_, (i32, String)>(&query_str)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(id, name)| OrganizationOption { id, name })
+        .collect();
+
+    Ok(options)
+}
+
+/// Search modules with filters, continuing after `cursor`
+///
+/// Pass `None` for the first page and the returned `next_cursor` for every
+/// following page. Scraping runs completed after the first page and runs still
+/// in progress are ignored, so results do not shift while scrolling. The
+/// returned `snapshot` gives the same view to `get_module_count`. At most 500
+/// modules are returned per page.
 #[server(SearchModulesPaginated)]
-#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip(filters), fields(page = page, page_size = page_size)))]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip(filters, cursor), fields(page_size = page_size)))]
 pub async fn search_modules_paginated(
     #[server(default)] filters: SearchFilters,
-    page: i64,
+    cursor: Option<String>,
     page_size: i64,
-) -> Result<Vec<ModuleSummary>, ServerFnError> {
+) -> Result<ModuleSearchPage, ServerFnError> {
     use leptos_actix::extract;
-    use sqlx::{query, Row};
 
     #[cfg(feature = "ssr")]
-    tracing::info!("search_modules_paginated called with page={}, page_size={}", page, page_size);
+    tracing::info!("search_modules_paginated called with page_size={}", page_size);
+
+    check_module_keys(&filters).map_err(ServerFnError::new)?;
+    let page_size = page_size.clamp(1, super::rest::MAX_PAGE_SIZE);
 
     let pool = extract::<actix_web::web::Data<PgPool>>()
         .await
         ?;
-    let pool: &PgPool = &*pool;
 
+    let cursor = match cursor {
+        Some(cursor) => Some(SearchCursor::decode(&cursor, &filters).ok_or_else(|| ServerFnError::new("Invalid cursor"))?),
+        None => None,
+    };
+
+    let snapshot = match &cursor {
+        Some(cursor) => cursor.snapshot,
+        None => completed_snapshot(&pool).await?,
+    };
+
+    let (keys, next_cursor) = search_module_keys_after(&pool, &filters, snapshot, cursor.as_ref(), page_size).await?;
+    let modules = load_module_summaries(&pool, &keys, snapshot).await?;
+
+    Ok(ModuleSearchPage {
+        modules,
+        next_cursor: next_cursor.map(|cursor| cursor.encode()),
+        snapshot,
+    })
+}
+
+/// Position in a search, handed to clients as an opaque string
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchCursor {
+    /// Newest completed scraping run of the search, later runs are ignored
+    pub snapshot: Option<i32>,
+    pub sort: SortOrder,
+    /// Sort key values of the last module returned, ending with its id and version
+    pub after: Vec<serde_json::Value>,
+}
+
+#[cfg(feature = "ssr")]
+impl SearchCursor {
+    pub fn encode(&self) -> String {
+        use base64::Engine;
+
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// Decode a cursor, `None` if it is malformed or belongs to another order
+    pub fn decode(cursor: &str, filters: &SearchFilters) -> Option<Self> {
+        use base64::Engine;
+
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        let cursor: SearchCursor = serde_json::from_slice(&bytes).ok()?;
+        let keys = sort_keys(filters);
+        // Every value has to match the type of its key, id and version are numbers
+        let numeric = keys.iter().map(|key| key.ends_with("::bigint")).chain([true, true]);
+        let valid = cursor.sort == filters.sort
+            && cursor.after.len() == keys.len() + 2
+            && cursor
+                .after
+                .iter()
+                .zip(numeric)
+                .all(|(value, numeric)| if numeric { value.is_i64() } else { value.is_string() });
+        valid.then_some(cursor)
+    }
+}
+
+/// Newest completed scraping run, `None` before the first run completed
+#[cfg(feature = "ssr")]
+pub async fn completed_snapshot(pool: &PgPool) -> Result<Option<i32>, sqlx::Error> {
+    sqlx::query_scalar!("SELECT MAX(id) FROM scraping_run WHERE status = 'completed'")
+        .fetch_one(pool)
+        .await
+}
+
+/// Condition on `module` that ignores scraping runs after the snapshot
+#[cfg(feature = "ssr")]
+fn snapshot_clause(snapshot: Option<i32>) -> String {
+    match snapshot {
+        Some(snapshot) => format!("WHERE scraping_run_id <= {}", snapshot),
+        None => String::new(),
+    }
+}
+
+/// Find the (id, version) keys of the modules following `cursor` and the cursor
+/// after them, `None` once all matching modules were returned
+///
+/// Scraping runs after `snapshot` are ignored, `None` searches all of them.
+#[cfg(feature = "ssr")]
+pub async fn search_module_keys_after(
+    pool: &PgPool,
+    filters: &SearchFilters,
+    snapshot: Option<i32>,
+    cursor: Option<&SearchCursor>,
+    page_size: i64,
+) -> Result<(Vec<(i32, i32)>, Option<SearchCursor>), sqlx::Error> {
+    use serde_json::Value;
+    use sqlx::Row;
+
+    let keys = sort_keys(filters);
+    let key_count = keys.len();
+    let columns: String = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| format!("{} AS sort_key_{}, ", key, i))
+        .collect();
+    let key_names: Vec<String> = (0..key_count)
+        .map(|i| format!("sort_key_{}", i))
+        .chain(["id".to_string(), "version".to_string()])
+        .collect();
+
+    // Row comparison works because every key is ascending and never NULL
+    let after_clause = match cursor {
+        Some(cursor) => {
+            let values: Vec<String> = cursor
+                .after
+                .iter()
+                .map(|value| match value {
+                    Value::String(text) => format!("'{}'", text.replace('\'', "''")),
+                    number => format!("{}::bigint", number),
+                })
+                .collect();
+            format!("WHERE ({}) > ({})", key_names.join(", "), values.join(", "))
+        }
+        None => String::new(),
+    };
+
+    // One extra row tells whether another page follows
+    let query_str = format!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            {snapshot_clause}
+            ORDER BY id, version, scraping_run_id DESC
+        ),
+        matching AS (
+            SELECT {columns}m.id, m.version
+            FROM module m
+            INNER JOIN latest_modules lm ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+            LEFT JOIN faculty f ON m.faculty_id = f.id
+            WHERE {where_clause}
+        )
+        SELECT * FROM matching
+        {after_clause}
+        ORDER BY {order}
+        LIMIT {limit}
+        "#,
+        snapshot_clause = snapshot_clause(snapshot),
+        where_clause = filter_where_clause(filters),
+        order = key_names.join(", "),
+        limit = page_size + 1
+    );
+
+    let (ids, versions) = module_key_binds(filters);
+    let mut rows = sqlx::query(&query_str)
+        .bind(ids)
+        .bind(versions)
+        .fetch_all(pool)
+        .await
+        ?;
+
+    let has_more = rows.len() as i64 > page_size;
+    rows.truncate(page_size.max(0) as usize);
+
+    let next_cursor = match rows.last() {
+        Some(last) if has_more => {
+            let mut after = Vec::with_capacity(key_count + 2);
+            for i in 0..key_count {
+                let value = match last.try_get::<i64, _>(i) {
+                    Ok(number) => Value::from(number),
+                    Err(_) => Value::from(last.try_get::<String, _>(i)?),
+                };
+                after.push(value);
+            }
+            after.push(Value::from(last.try_get::<i32, _>("id")?));
+            after.push(Value::from(last.try_get::<i32, _>("version")?));
+            Some(SearchCursor {
+                snapshot,
+                sort: filters.sort,
+                after,
+            })
+        }
+        _ => None,
+    };
+
+    let keys = rows
+        .into_iter()
+        .map(|row| (row.try_get("id").unwrap_or(0), row.try_get("version").unwrap_or(0)))
+        .collect();
+
+    Ok((keys, next_cursor))
+}
+
+/// Load one page of module summaries matching the filters, in the requested order
+#[cfg(feature = "ssr")]
+pub async fn search_modules(
+    pool: &PgPool,
+    filters: &SearchFilters,
+    page: i64,
+    page_size: i64,
+) -> Result<Vec<ModuleSummary>, sqlx::Error> {
+    let keys = search_module_keys(pool, filters, page, page_size).await?;
+    load_module_summaries(pool, &keys, None).await
+}
+
+/// Find the (id, version) keys of one page of modules matching the filters, in the requested order
+#[cfg(feature = "ssr")]
+pub async fn search_module_keys(
+    pool: &PgPool,
+    filters: &SearchFilters,
+    page: i64,
+    page_size: i64,
+) -> Result<Vec<(i32, i32)>, sqlx::Error> {
+    use sqlx::Row;
+
+    let where_clause = filter_where_clause(filters);
+    // Pages past the end are empty, also when the offset does not fit
+    let offset = page.saturating_mul(page_size);
+
+    // Main query with CTE to get latest modules
+    let query_str = format!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        )
+        SELECT m.id, m.version
+        FROM module m
+        INNER JOIN latest_modules lm ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+        LEFT JOIN faculty f ON m.faculty_id = f.id
+        WHERE {}
+        ORDER BY {}
+        LIMIT {} OFFSET {}
+        "#,
+        where_clause,
+        order_clause(filters),
+        page_size,
+        offset
+    );
+
+    let (ids, versions) = module_key_binds(filters);
+    let rows = sqlx::query(&query_str)
+        .bind(ids)
+        .bind(versions)
+        .fetch_all(pool)
+        .await
+        ?;
+
+    let keys = rows
+        .into_iter()
+        .map(|row| (row.try_get("id").unwrap_or(0), row.try_get("version").unwrap_or(0)))
+        .collect();
+
+    Ok(keys)
+}
+
+/// Build the WHERE clause for the search filters
+///
+/// Relies on the module alias `m` and the `latest_modules` CTE of the surrounding query.
+/// The module keys are bound as `$1` and `$2`, see [`module_key_binds`].
+#[cfg(feature = "ssr")]
+pub(crate) fn filter_where_clause(filters: &SearchFilters) -> String {
     // Build dynamic WHERE clauses
     let mut where_clauses = vec!["1=1".to_string()];
 
-    // Search query filter
+    // Search query filter, filters typed into the search box apply on top of the others
     if let Some(ref query) = filters.search_query {
-        if !query.is_empty() {
+        let parsed = parse_query(query).unwrap_or_else(|_| ParsedQuery::plain(query));
+        for term in &parsed.terms {
             where_clauses.push(format!(
-                "m.title ILIKE '%{}%'",
-                query.replace('\'', "''")
+                "m.title {} '%{}%'",
+                if term.negated { "NOT ILIKE" } else { "ILIKE" },
+                like_escape(&term.text).replace('\'', "''")
             ));
         }
+        if parsed.filters != SearchFilters::default() {
+            // Discontinued modules are already handled by the outer filters
+            let query_filters = SearchFilters {
+                include_discontinued: true,
+                ..parsed.filters
+            };
+            where_clauses.push(format!("({})", filter_where_clause(&query_filters)));
+        }
     }
 
     // Credit filters
@@ -390,6 +707,20 @@ pub async fn search_modules_paginated(
         }
     }
 
+    // Study program name filter
+    if let Some(ref names) = filters.study_program_names {
+        if !names.is_empty() {
+            let name_strs: Vec<String> = names
+                .iter()
+                .map(|n| format!("sp.name ILIKE '%{}%'", like_escape(n).replace('\'', "''")))
+                .collect();
+            where_clauses.push(format!(
+                "EXISTS (SELECT 1 FROM module_catalog_usage mcu JOIN stupo st ON mcu.stupo_id = st.id JOIN study_program sp ON st.study_program_id = sp.id WHERE mcu.module_id = m.id AND mcu.module_version = m.version AND ({}))",
+                name_strs.join(" OR ")
+            ));
+        }
+    }
+
     // Component type filter
     if let Some(ref types) = filters.component_types {
         if !types.is_empty() {
@@ -404,13 +735,30 @@ pub async fn search_modules_paginated(
         }
     }
 
+    // Faculty, institute, Fachgebiet, responsible person and examination board filters
+    for unit in OrganizationUnit::ALL {
+        if let Some(ids) = unit.selected(filters) {
+            if !ids.is_empty() {
+                let id_strs: Vec<String> = ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect();
+                where_clauses.push(format!(
+                    "m.{} IN ({})",
+                    unit.module_column(),
+                    id_strs.join(", ")
+                ));
+            }
+        }
+    }
+
     // Component language filter
     if let Some(ref langs) = filters.component_languages {
         if !langs.is_empty() {
             let lang_conditions: Vec<String> = langs
                 .iter()
                 .map(|l| {
-                    let escaped = l.replace('\'', "''");
+                    let escaped = regex_escape(l).replace('\'', "''");
                     format!("mc.language ~ '(^|, ){}(, |$)'", escaped)
                 })
                 .collect();
@@ -421,164 +769,421 @@ pub async fn search_modules_paginated(
         }
     }
 
-    let where_clause = where_clauses.join(" AND ");
-    let offset = page * page_size;
+    // Graded or ungraded exam filter
+    if let Some(graded) = filters.graded {
+        where_clauses.push(format!(
+            "EXISTS (SELECT 1 FROM exam e WHERE e.module_id = m.id AND e.module_version = m.version AND e.module_scraping_run_id = m.scraping_run_id AND e.graded = {})",
+            graded
+        ));
+    }
 
-    // Main query with CTE to get latest modules
-    let query_str = format!(
+    // Exam form filters
+    if let Some(ref types) = filters.exam_types {
+        if !types.is_empty() {
+            let type_strs: Vec<String> = types
+                .iter()
+                .map(|t| format!("'{}'", t.replace('\'', "''")))
+                .collect();
+            where_clauses.push(format!(
+                "EXISTS (SELECT 1 FROM exam e WHERE e.module_id = m.id AND e.module_version = m.version AND e.module_scraping_run_id = m.scraping_run_id AND e.exam_type IN ({}))",
+                type_strs.join(", ")
+            ));
+        }
+    }
+    if let Some(ref types) = filters.excluded_exam_types {
+        if !types.is_empty() {
+            let type_strs: Vec<String> = types
+                .iter()
+                .map(|t| format!("'{}'", t.replace('\'', "''")))
+                .collect();
+            where_clauses.push(format!(
+                "NOT EXISTS (SELECT 1 FROM exam e WHERE e.module_id = m.id AND e.module_version = m.version AND e.module_scraping_run_id = m.scraping_run_id AND e.exam_type IN ({}))",
+                type_strs.join(", ")
+            ));
+        }
+    }
+
+    // Total workload filters, the sum is NULL without workload information
+    let total_workload = "(SELECT SUM(w.total_hours) FROM module_workload_distribution w WHERE w.module_id = m.id AND w.module_version = m.version AND w.module_scraping_run_id = m.scraping_run_id)";
+    if let Some(min) = filters.min_workload_hours {
+        where_clauses.push(format!("{} >= {}", total_workload, min));
+    }
+    if let Some(max) = filters.max_workload_hours {
+        where_clauses.push(format!("{} <= {}", total_workload, max));
+    }
+
+    // Attendance limit filters
+    if filters.no_attendance_limit {
+        where_clauses.push("m.max_attendees IS NULL".to_string());
+    }
+    if let Some(places) = filters.min_attendee_places {
+        where_clauses.push(format!("(m.max_attendees IS NULL OR m.max_attendees >= {})", places));
+    }
+
+    // Newest valid version filter
+    if filters.newest_version_only {
+        where_clauses.push(newest_version_clause());
+    }
+
+    // Discontinued modules are hidden unless requested
+    if !filters.include_discontinued {
+        where_clauses.push("m.discontinued_in_run_id IS NULL".to_string());
+    }
+
+    // Explicit module list (starred modules, a collection or a shared list)
+    if let Some(ref keys) = filters.module_keys {
+        where_clauses.push(module_keys_clause(keys));
+    }
+
+    where_clauses.join(" AND ")
+}
+
+/// Sort key expressions for the requested order, all ascending and never NULL
+///
+/// The module number and version follow as the last keys. They are unique among
+/// the latest snapshots, so the order is total and pages never overlap or skip
+/// modules. Numbers are cast to bigint so cursors only hold integers and text.
+/// Relies on the aliases `m`, `lm` and `f` of the surrounding query.
+#[cfg(feature = "ssr")]
+fn sort_keys(filters: &SearchFilters) -> Vec<String> {
+    let parsed = filters
+        .search_query
+        .as_deref()
+        .map(|query| parse_query(query).unwrap_or_else(|_| ParsedQuery::plain(query)));
+    let query = parsed
+        .as_ref()
+        .and_then(ParsedQuery::title_text)
+        .map(str::trim)
+        .filter(|query| !query.is_empty())
+        .map(|query| query.replace('\'', "''"));
+
+    let title = "m.title".to_string();
+    match (filters.sort, query) {
+        // Exact title first, then titles starting with the query, then titles
+        // with a word starting with it, then by where the match is
+        (SortOrder::Relevance, Some(query)) => vec![
+            format!(
+                r#"(CASE
+                    WHEN lower(m.title) = lower('{q}') THEN 0
+                    WHEN m.title ILIKE '{pattern}%' THEN 1
+                    WHEN m.title ILIKE '% {pattern}%' THEN 2
+                    ELSE 3
+                END)::bigint"#,
+                q = query,
+                pattern = like_escape(&query)
+            ),
+            format!("strpos(lower(m.title), lower('{}'))::bigint", query),
+            title,
+        ],
+        (SortOrder::Relevance, None) | (SortOrder::Title, _) => vec![title],
+        (SortOrder::CreditsAsc, _) => vec!["m.credits::bigint".to_string(), title],
+        (SortOrder::CreditsDesc, _) => vec!["(-m.credits)::bigint".to_string(), title],
+        (SortOrder::ModuleNumber, _) => vec![],
+        (SortOrder::Faculty, _) => vec!["f.name".to_string(), title],
+        (SortOrder::TotalSws, _) => vec![
+            r#"(
+                SELECT COALESCE(SUM(mc.sws), 0)
+                FROM module_component mc
+                WHERE mc.module_id = m.id AND mc.module_version = m.version AND mc.module_scraping_run_id = m.scraping_run_id
+            )::bigint"#
+                .to_string(),
+            title,
+        ],
+        // Newest first, versions without a change report count from the run
+        // they first appeared in
+        (SortOrder::RecentlyChanged, _) => vec![
+            r#"(-COALESCE(
+                (SELECT MAX(mch.scraping_run_id) FROM module_change mch WHERE mch.module_id = m.id AND mch.module_version = m.version AND mch.change_type <> 'removed' AND mch.scraping_run_id <= lm.scraping_run_id),
+                (SELECT MIN(fm.scraping_run_id) FROM module fm WHERE fm.id = m.id AND fm.version = m.version)
+            ))::bigint"#
+                .to_string(),
+            title,
+        ],
+    }
+}
+
+/// Build the ORDER BY clause for the requested sort order
+#[cfg(feature = "ssr")]
+fn order_clause(filters: &SearchFilters) -> String {
+    let mut keys = sort_keys(filters);
+    keys.push("m.id".to_string());
+    keys.push("m.version".to_string());
+    keys.join(", ")
+}
+
+/// Keep only the newest version per module number that is still valid
+///
+/// A version counts as valid if its validity window has not ended before the
+/// current semester. Module numbers whose versions have all expired keep their
+/// newest version. Relies on the `latest_modules` CTE of the surrounding query.
+#[cfg(feature = "ssr")]
+fn newest_version_clause() -> String {
+    use crate::planner::{Season, Term};
+
+    let current = Term::current();
+    let season = match current.season {
+        Season::SoSe => "SoSe",
+        Season::WiSe => "WiSe",
+    };
+
+    format!(
+        r#"(m.id, m.version) IN (
+            SELECT DISTINCT ON (v.id) v.id, v.version
+            FROM module v
+            INNER JOIN latest_modules lv ON v.id = lv.id AND v.version = lv.version AND v.scraping_run_id = lv.scraping_run_id
+            ORDER BY v.id,
+                (v.valid_until_year IS NULL OR (v.valid_until_year, COALESCE(v.valid_until_semester, 'WiSe')) >= ({}, '{}'::semester)) DESC,
+                v.version DESC
+        )"#,
+        current.year, season
+    )
+}
+
+/// Restrict a search to the explicit (id, version) keys bound as `$1` and `$2`
+#[cfg(feature = "ssr")]
+fn module_keys_clause(keys: &[(i32, i32)]) -> String {
+    if keys.is_empty() {
+        return "FALSE".to_string();
+    }
+    "(m.id, m.version) IN (SELECT * FROM UNNEST($1::int[], $2::int[]))".to_string()
+}
+
+/// Module ids and versions to bind as `$1` and `$2` of every query using [`filter_where_clause`]
+#[cfg(feature = "ssr")]
+pub(crate) fn module_key_binds(filters: &SearchFilters) -> (Vec<i32>, Vec<i32>) {
+    filters.module_keys.iter().flatten().copied().unzip()
+}
+
+/// Reject filters with more explicit module keys than a search takes
+#[cfg(feature = "ssr")]
+pub(crate) fn check_module_keys(filters: &SearchFilters) -> Result<(), String> {
+    match &filters.module_keys {
+        Some(keys) if keys.len() > MAX_MODULE_KEYS => Err(format!(
+            "At most {} module keys can be searched",
+            MAX_MODULE_KEYS
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Escape the wildcards of a LIKE pattern, Postgres uses `\` as escape character by default
+#[cfg(feature = "ssr")]
+fn like_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\%_".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape text for use inside a Postgres regular expression
+#[cfg(feature = "ssr")]
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.^$*+?()[]{}|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Maximum number of module versions looked up by key, as many as can be starred and synced
+pub const MAX_MODULE_KEYS: usize = super::sync::MAX_SYNCED_MODULES;
+
+/// Get module summaries for an explicit list of (id, version) keys
+///
+/// Keys that do not exist are skipped, the order of the input is preserved.
+#[server(GetModulesByKeys)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip(keys), fields(key_count = keys.len())))]
+pub async fn get_modules_by_keys(keys: Vec<(i32, i32)>) -> Result<Vec<ModuleSummary>, ServerFnError> {
+    use leptos_actix::extract;
+
+    if keys.len() > MAX_MODULE_KEYS {
+        return Err(ServerFnError::new(format!(
+            "At most {} modules can be loaded at once",
+            MAX_MODULE_KEYS
+        )));
+    }
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+
+    Ok(load_module_summaries(&pool, &keys, None).await?)
+}
+
+/// Load the summary of a module version from its latest snapshot
+///
+/// Returns `None` if the module version does not exist.
+#[cfg(feature = "ssr")]
+pub async fn load_module_summary(
+    pool: &PgPool,
+    id: i32,
+    version: i32,
+) -> Result<Option<ModuleSummary>, sqlx::Error> {
+    Ok(load_module_summaries(pool, &[(id, version)], None).await?.pop())
+}
+
+/// Load the summaries of module versions from their latest snapshots
+///
+/// Keys that do not exist are skipped, the order of the input is preserved.
+/// Takes a fixed number of queries however many keys are given. Scraping runs
+/// after `snapshot` are ignored, `None` uses the latest snapshot of each key.
+#[cfg(feature = "ssr")]
+pub async fn load_module_summaries(
+    pool: &PgPool,
+    keys: &[(i32, i32)],
+    snapshot: Option<i32>,
+) -> Result<Vec<ModuleSummary>, sqlx::Error> {
+    use std::collections::HashMap;
+    use sqlx::query;
+
+    let (ids, versions): (Vec<i32>, Vec<i32>) = keys.iter().copied().unzip();
+
+    // Latest snapshot of every key, in the order of the keys
+    let module_rows = query!(
         r#"
-        WITH latest_modules AS (
-            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
-            FROM module
-            ORDER BY id, version, scraping_run_id DESC
-        )
-        SELECT DISTINCT
-            m.id,
-            m.version,
-            m.title,
-            m.credits,
-            m.languages,
-            f.name as faculty_name
-        FROM module m
-        INNER JOIN latest_modules lm ON m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+        SELECT DISTINCT ON (k.position)
+            m.id, m.version, m.scraping_run_id, m.title, m.credits, m.languages, m.discontinued_in_run_id,
+            f.name as "faculty_name?"
+        FROM UNNEST($1::int[], $2::int[]) WITH ORDINALITY AS k(id, version, position)
+        JOIN module m ON m.id = k.id AND m.version = k.version
         LEFT JOIN faculty f ON m.faculty_id = f.id
-        WHERE {}
-        ORDER BY m.title
-        LIMIT {} OFFSET {}
+        WHERE $3::int IS NULL OR m.scraping_run_id <= $3
+        ORDER BY k.position, m.scraping_run_id DESC
         "#,
-        where_clause, page_size, offset
-    );
-
-    let rows = sqlx::query(&query_str)
-        .fetch_all(pool)
-        .await
-        ?;
-
-    let mut modules_data = Vec::new();
+        &ids,
+        &versions,
+        snapshot
+    )
+    .fetch_all(pool)
+    .await?;
 
-    for row in rows {
-        let id: i32 = row.try_get("id").unwrap_or(0);
-        let version: i32 = row.try_get("version").unwrap_or(0);
+    let mut snapshot_ids = Vec::with_capacity(module_rows.len());
+    let mut snapshot_versions = Vec::with_capacity(module_rows.len());
+    let mut snapshot_runs = Vec::with_capacity(module_rows.len());
+    for row in &module_rows {
+        snapshot_ids.push(row.id);
+        snapshot_versions.push(row.version);
+        snapshot_runs.push(row.scraping_run_id);
+    }
 
-        // Get the latest scraping_run_id for this module
-        let latest_run_id = query!(
-            r#"
-            SELECT scraping_run_id
-            FROM module
-            WHERE id = $1 AND version = $2
-            ORDER BY scraping_run_id DESC
-            LIMIT 1
-            "#,
-            id,
-            version
-        )
-        .fetch_one(pool)
-        .await?
-        .scraping_run_id;
-
-        // Get semester rotations
-        let rotations = query!(
-            r#"
-            SELECT DISTINCT rotation::text as "rotation!"
-            FROM module_component
-            WHERE module_id = $1 AND module_version = $2 AND module_scraping_run_id = $3
-            "#,
-            id,
-            version,
-            latest_run_id
-        )
-        .fetch_all(pool)
-        .await
-        ?
-        .into_iter()
-        .map(|r| r.rotation)
-        .collect();
+    // Get semester rotations
+    let mut rotations: HashMap<(i32, i32, i32), Vec<String>> = HashMap::new();
+    for row in query!(
+        r#"
+        SELECT DISTINCT module_id, module_version, module_scraping_run_id, rotation::text as "rotation!"
+        FROM module_component
+        WHERE (module_id, module_version, module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+        "#,
+        &snapshot_ids,
+        &snapshot_versions,
+        &snapshot_runs
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        let key = (row.module_id, row.module_version, row.module_scraping_run_id);
+        rotations.entry(key).or_default().push(row.rotation);
+    }
 
-        // Get exam categories
-        let categories = query!(
-            r#"
-            SELECT DISTINCT ec.category::text as "category!"
-            FROM exam e
-            JOIN exam_component ec ON e.id = ec.exam_id
-            WHERE e.module_id = $1 AND e.module_version = $2 AND e.module_scraping_run_id = $3
-            "#,
-            id,
-            version,
-            latest_run_id
-        )
-        .fetch_all(pool)
-        .await
-        ?
-        .into_iter()
-        .map(|r| r.category)
-        .collect();
+    // Get exam categories
+    let mut categories: HashMap<(i32, i32, i32), Vec<String>> = HashMap::new();
+    for row in query!(
+        r#"
+        SELECT DISTINCT e.module_id, e.module_version, e.module_scraping_run_id, ec.category::text as "category!"
+        FROM exam e
+        JOIN exam_component ec ON e.id = ec.exam_id
+        WHERE (e.module_id, e.module_version, e.module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+        "#,
+        &snapshot_ids,
+        &snapshot_versions,
+        &snapshot_runs
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        let key = (row.module_id, row.module_version, row.module_scraping_run_id);
+        categories.entry(key).or_default().push(row.category);
+    }
 
-        // Get study programs
-        let programs = query!(
-            r#"
-            SELECT DISTINCT sp.id, sp.name
-            FROM module_catalog_usage mcu
-            JOIN stupo st ON mcu.stupo_id = st.id
-            JOIN study_program sp ON st.study_program_id = sp.id
-            WHERE mcu.module_id = $1 AND mcu.module_version = $2 AND mcu.module_scraping_run_id = $3
-            "#,
-            id,
-            version,
-            latest_run_id
-        )
-        .fetch_all(pool)
-        .await
-        ?;
+    // Get study programs
+    let mut programs: HashMap<(i32, i32, i32), Vec<(i32, String)>> = HashMap::new();
+    for row in query!(
+        r#"
+        SELECT DISTINCT mcu.module_id, mcu.module_version, mcu.module_scraping_run_id, sp.id, sp.name
+        FROM module_catalog_usage mcu
+        JOIN stupo st ON mcu.stupo_id = st.id
+        JOIN study_program sp ON st.study_program_id = sp.id
+        WHERE (mcu.module_id, mcu.module_version, mcu.module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+        "#,
+        &snapshot_ids,
+        &snapshot_versions,
+        &snapshot_runs
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        let key = (row.module_id, row.module_version, row.module_scraping_run_id);
+        programs.entry(key).or_default().push((row.id, row.name));
+    }
 
-        let study_program_names: Vec<String> = programs.iter().map(|p| p.name.clone()).collect();
-        let study_program_ids: Vec<i32> = programs.iter().map(|p| p.id).collect();
-
-        // Get module components
-        let components = query!(
-            r#"
-            SELECT
-                component_type::text as "component_type!",
-                module_name,
-                number,
-                rotation::text as "rotation!",
-                sws,
-                language
-            FROM module_component
-            WHERE module_id = $1 AND module_version = $2 AND module_scraping_run_id = $3
-            ORDER BY component_type, number
-            "#,
-            id,
-            version,
-            latest_run_id
-        )
-        .fetch_all(pool)
-        .await
-        ?
-        .into_iter()
-        .map(|c| ComponentInfo {
+    // Get module components
+    let mut components: HashMap<(i32, i32, i32), Vec<ComponentInfo>> = HashMap::new();
+    for c in query!(
+        r#"
+        SELECT
+            module_id, module_version, module_scraping_run_id,
+            component_type::text as "component_type!",
+            module_name,
+            number,
+            rotation::text as "rotation!",
+            sws,
+            language
+        FROM module_component
+        WHERE (module_id, module_version, module_scraping_run_id) IN (SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[]))
+        ORDER BY component_type, number
+        "#,
+        &snapshot_ids,
+        &snapshot_versions,
+        &snapshot_runs
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        let key = (c.module_id, c.module_version, c.module_scraping_run_id);
+        components.entry(key).or_default().push(ComponentInfo {
             component_type: c.component_type,
             name: c.module_name,
             number: c.number,
             rotation: c.rotation,
             sws: c.sws,
             language: c.language,
-        })
-        .collect();
-
-        modules_data.push(ModuleSummary {
-            id,
-            version,
-            title: row.try_get("title").unwrap_or_default(),
-            credits: row.try_get("credits").unwrap_or(0),
-            languages: row.try_get("languages").unwrap_or_default(),
-            semester_rotations: rotations,
-            exam_categories: categories,
-            study_programs: study_program_names,
-            study_program_ids,
-            faculty_name: row.try_get("faculty_name").unwrap_or_default(),
-            components,
         });
     }
 
-    Ok(modules_data)
+    Ok(module_rows
+        .into_iter()
+        .map(|row| {
+            let key = (row.id, row.version, row.scraping_run_id);
+            let programs = programs.get(&key).cloned().unwrap_or_default();
+            ModuleSummary {
+                id: row.id,
+                version: row.version,
+                title: row.title,
+                credits: row.credits,
+                languages: row.languages,
+                semester_rotations: rotations.get(&key).cloned().unwrap_or_default(),
+                exam_categories: categories.get(&key).cloned().unwrap_or_default(),
+                study_programs: programs.iter().map(|(_, name)| name.clone()).collect(),
+                study_program_ids: programs.iter().map(|(id, _)| *id).collect(),
+                faculty_name: row.faculty_name.unwrap_or_default(),
+                components: components.get(&key).cloned().unwrap_or_default(),
+                discontinued_in_run_id: row.discontinued_in_run_id,
+            }
+        })
+        .collect())
 }
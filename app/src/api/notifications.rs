@@ -0,0 +1,207 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use actix_web::{http::header, web, HttpResponse};
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Maximum number of module versions a subscription can follow
+pub const MAX_FOLLOWED_MODULES: usize = 200;
+
+/// Minutes before another confirmation email is sent to the same address
+pub const CONFIRMATION_INTERVAL_MINUTES: i32 = 15;
+
+/// Ask to subscribe an email address to changes of the given module versions
+///
+/// Nothing changes until the address is confirmed. Confirming creates the
+/// subscription or replaces the modules followed by an existing one.
+#[server(SubscribeNotifications)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all, fields(module_count = modules.len())))]
+pub async fn subscribe_notifications(email: String, modules: Vec<(i32, i32)>) -> Result<(), ServerFnError> {
+    use crate::notifications::{send_confirmation, MailConfig};
+    use leptos_actix::extract;
+
+    let config = MailConfig::from_env()
+        .map_err(ServerFnError::new)?
+        .ok_or_else(|| ServerFnError::new("Email notifications are not enabled on this server"))?;
+
+    let email = email.trim().to_string();
+    if email.parse::<lettre::Address>().is_err() {
+        return Err(ServerFnError::new("Invalid email address"));
+    }
+    if modules.is_empty() {
+        return Err(ServerFnError::new("Star at least one module to follow"));
+    }
+    if modules.len() > MAX_FOLLOWED_MODULES {
+        return Err(ServerFnError::new(format!(
+            "At most {} modules can be followed",
+            MAX_FOLLOWED_MODULES
+        )));
+    }
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let (ids, versions): (Vec<i32>, Vec<i32>) = modules.iter().copied().unzip();
+
+    // A newer request replaces the open one, but not more often than every few minutes
+    let token = sqlx::query_scalar!(
+        r#"
+        INSERT INTO notification_subscription_request AS r (email, module_ids, module_versions)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (email) DO UPDATE
+        SET token = DEFAULT,
+            module_ids = EXCLUDED.module_ids,
+            module_versions = EXCLUDED.module_versions,
+            created_at = NOW()
+        WHERE r.created_at < NOW() - make_interval(mins => $4)
+        RETURNING token
+        "#,
+        email,
+        &ids,
+        &versions,
+        CONFIRMATION_INTERVAL_MINUTES
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| {
+        ServerFnError::new(format!(
+            "A confirmation email was sent to this address recently, try again in {} minutes",
+            CONFIRMATION_INTERVAL_MINUTES
+        ))
+    })?;
+
+    if let Err(e) = send_confirmation(&config, &email, &token, modules.len()).await {
+        tracing::error!("Failed to send confirmation email: {}", e);
+        // Nothing was sent, so the address may try again right away
+        sqlx::query!("DELETE FROM notification_subscription_request WHERE token = $1", token)
+            .execute(pool)
+            .await?;
+        return Err(ServerFnError::new("Failed to send the confirmation email"));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+fn redirect_to_page(query: String) -> HttpResponse {
+    HttpResponse::SeeOther()
+        .insert_header((header::LOCATION, format!("/notifications?{}", query)))
+        .finish()
+}
+
+#[cfg(feature = "ssr")]
+fn redirect_with_status(status: &str) -> HttpResponse {
+    redirect_to_page(format!("status={}", status))
+}
+
+/// Confirmation link from the subscription email, opens a page with a button
+/// that confirms
+///
+/// Only the POST of that button changes anything, so mail scanners following
+/// the link do not confirm on the user's behalf.
+#[cfg(feature = "ssr")]
+pub async fn confirm_link(path: web::Path<String>) -> HttpResponse {
+    redirect_to_page(format!("confirm={}", crate::planner::percent_encode(&path.into_inner())))
+}
+
+/// Unsubscribe link from notification emails, opens a page with a button that
+/// unsubscribes
+#[cfg(feature = "ssr")]
+pub async fn unsubscribe_link(path: web::Path<String>) -> HttpResponse {
+    redirect_to_page(format!("unsubscribe={}", crate::planner::percent_encode(&path.into_inner())))
+}
+
+/// Turn an open request into a subscription, `false` if the token is unknown
+///
+/// An existing subscription of the address keeps its token and digest state
+/// and only gets the requested modules. New subscriptions only cover runs
+/// completed after confirming.
+#[cfg(feature = "ssr")]
+async fn confirm_request(pool: &PgPool, token: &str) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let Some(request) = sqlx::query!(
+        r#"
+        DELETE FROM notification_subscription_request
+        WHERE token = $1
+        RETURNING email, module_ids, module_versions
+        "#,
+        token
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(false);
+    };
+
+    let subscription_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO notification_subscription (email, confirmed_at, last_notified_run_id)
+        VALUES ($1, NOW(), (SELECT MAX(id) FROM scraping_run WHERE status = 'completed'))
+        ON CONFLICT (email) DO UPDATE
+        SET confirmed_at = COALESCE(notification_subscription.confirmed_at, NOW())
+        RETURNING id
+        "#,
+        request.email
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM notification_subscription_module WHERE subscription_id = $1",
+        subscription_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO notification_subscription_module (subscription_id, module_id, module_version)
+        SELECT $1, id, version FROM UNNEST($2::int[], $3::int[]) AS m(id, version)
+        ON CONFLICT DO NOTHING
+        "#,
+        subscription_id,
+        &request.module_ids,
+        &request.module_versions
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Confirm button of the page opened by [`confirm_link`]
+#[cfg(feature = "ssr")]
+pub async fn confirm_subscription(path: web::Path<String>, pool: web::Data<PgPool>) -> HttpResponse {
+    match confirm_request(&pool, &path.into_inner()).await {
+        Ok(true) => redirect_with_status("confirmed"),
+        Ok(false) => redirect_with_status("invalid"),
+        Err(e) => {
+            tracing::error!("Failed to confirm subscription: {}", e);
+            HttpResponse::InternalServerError().body("Failed to confirm subscription")
+        }
+    }
+}
+
+/// Unsubscribe button of the page opened by [`unsubscribe_link`]
+#[cfg(feature = "ssr")]
+pub async fn unsubscribe(path: web::Path<String>, pool: web::Data<PgPool>) -> HttpResponse {
+    let result = sqlx::query!(
+        "DELETE FROM notification_subscription WHERE token = $1",
+        path.into_inner()
+    )
+    .execute(&**pool)
+    .await;
+
+    match result {
+        Ok(done) if done.rows_affected() > 0 => redirect_with_status("unsubscribed"),
+        Ok(_) => redirect_with_status("invalid"),
+        Err(e) => {
+            tracing::error!("Failed to unsubscribe: {}", e);
+            HttpResponse::InternalServerError().body("Failed to unsubscribe")
+        }
+    }
+}
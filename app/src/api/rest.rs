@@ -0,0 +1,431 @@
+//! Versioned public JSON API under `/api/v1`
+//!
+//! Unlike the server functions, paths and payloads of this API are a stable
+//! contract. Breaking changes get a new version prefix. The OpenAPI document
+//! is served at `/api/v1/openapi.json`.
+
+use super::module_detail::load_module_detail;
+use super::modules::{check_module_keys, count_modules, load_filter_options, search_modules};
+use crate::models::{FilterOptions, ModuleDetail, ModuleSummary, SearchFilters, SortOrder};
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use db::PgPool;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+/// Page size used when `page_size` is not given
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// Largest accepted `page_size`
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "TUB Modules API",
+        version = "1",
+        description = "Module catalog of TU Berlin as scraped from MOSES"
+    ),
+    paths(list_modules, get_module, filter_options, list_study_programs, list_scraping_runs)
+)]
+struct ApiDoc;
+
+/// Error body of all non-2xx responses
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiError {
+    pub error: String,
+}
+
+/// One page of search results
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ModulePage {
+    /// Number of module versions matching the filters across all pages
+    pub total: i64,
+    pub page: i64,
+    pub page_size: i64,
+    pub modules: Vec<ModuleSummary>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StudyProgram {
+    pub id: i32,
+    pub name: String,
+    /// Link to the study program page
+    pub link: String,
+    pub stupos: Vec<Stupo>,
+}
+
+/// Study and examination regulations (StuPO) of a study program
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Stupo {
+    pub id: i32,
+    pub name: String,
+    pub link: String,
+}
+
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
+pub struct ScrapingRunInfo {
+    pub id: i32,
+    /// One of `in_progress`, `completed` or `failed`
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Completed run the change report of this run is based on
+    pub previous_run_id: Option<i32>,
+    pub total_modules: Option<i32>,
+    pub successful_modules: Option<i32>,
+    pub failed_modules: Option<i32>,
+    pub skipped_modules: Option<i32>,
+}
+
+/// Query parameters of the module search
+///
+/// They mirror the search filters of the web interface. Starred modules and
+/// collections only exist in the browser, pass their keys as `module_keys`.
+/// List parameters are comma separated.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ModuleQuery {
+    /// Case-insensitive substring of the module title, may contain filters
+    /// like `lp:>=6 lang:en -"Projekt"` as in the search box. A query that
+    /// doesn't parse is matched as plain title text.
+    pub search: Option<String>,
+    /// Minimum credits (ECTS)
+    pub min_credits: Option<i32>,
+    /// Maximum credits (ECTS)
+    pub max_credits: Option<i32>,
+    /// Semester rotations of any component, e.g. `WiSe,WiSe/SoSe`
+    pub semester_rotations: Option<String>,
+    /// Exam categories, e.g. `written,oral`
+    pub exam_categories: Option<String>,
+    /// Study program ids as listed by `/api/v1/study-programs`
+    pub study_program_ids: Option<String>,
+    /// Parts of study program names, case-insensitive, e.g. `Informatik,Mathematik`
+    pub study_program_names: Option<String>,
+    /// Component types of any component, e.g. `VL,UE`
+    pub component_types: Option<String>,
+    /// Languages of any component, e.g. `Deutsch,Englisch`
+    pub component_languages: Option<String>,
+    /// Faculty ids as listed by `/api/v1/filter-options`
+    pub faculty_ids: Option<String>,
+    /// Institute ids as listed by `/api/v1/filter-options`
+    pub institute_ids: Option<String>,
+    /// Fachgebiet ids as listed by `/api/v1/filter-options`
+    pub fachgebiet_ids: Option<String>,
+    /// Responsible person ids as listed by `/api/v1/filter-options`
+    pub responsible_person_ids: Option<String>,
+    /// Examination board ids as listed by `/api/v1/filter-options`
+    pub examination_board_ids: Option<String>,
+    /// `true` for modules with a graded exam, `false` for an ungraded one
+    pub graded: Option<bool>,
+    /// Exam forms of any exam, e.g. `Portfolioprüfung`
+    pub exam_types: Option<String>,
+    /// Exam forms no exam may have
+    pub excluded_exam_types: Option<String>,
+    /// Minimum total workload in hours
+    pub min_workload_hours: Option<i32>,
+    /// Maximum total workload in hours
+    pub max_workload_hours: Option<i32>,
+    /// Only modules without a cap on attendees
+    #[param(default = false)]
+    pub no_attendance_limit: Option<bool>,
+    /// Modules with at least this many places or without a cap
+    pub min_attendee_places: Option<i32>,
+    /// Only these module versions, as `id:version` pairs, e.g. `40012:3,40500:1`, at most 2000
+    pub module_keys: Option<String>,
+    /// Keep only the newest still valid version per module number
+    #[param(default = false)]
+    pub newest_version_only: Option<bool>,
+    /// Include module versions missing from the latest full scraping run
+    #[param(default = false)]
+    pub include_discontinued: Option<bool>,
+    /// Result order: `relevance`, `title`, `credits_asc`, `credits_desc`,
+    /// `module_number`, `faculty`, `total_sws` or `recently_changed`
+    #[param(default = "relevance")]
+    pub sort: Option<String>,
+    /// Zero-based page number
+    #[param(default = 0, minimum = 0)]
+    pub page: Option<i64>,
+    /// Results per page
+    #[param(default = 50, minimum = 1, maximum = 500)]
+    pub page_size: Option<i64>,
+}
+
+fn split_list(value: &Option<String>) -> Option<Vec<String>> {
+    value.as_ref().map(|value| {
+        value
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    })
+}
+
+fn split_ids(value: &Option<String>, what: &str) -> Result<Option<Vec<i32>>, String> {
+    split_list(value)
+        .map(|ids| {
+            ids.iter()
+                .map(|id| id.parse::<i32>().map_err(|_| format!("Invalid {} id: {}", what, id)))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+}
+
+impl ModuleQuery {
+    fn to_filters(&self) -> Result<SearchFilters, String> {
+        let study_program_ids = split_ids(&self.study_program_ids, "study program")?;
+
+        let module_keys = split_list(&self.module_keys)
+            .map(|keys| {
+                keys.iter()
+                    .map(|key| {
+                        key.split_once(':')
+                            .and_then(|(id, version)| Some((id.parse::<i32>().ok()?, version.parse::<i32>().ok()?)))
+                            .ok_or_else(|| format!("Invalid module key, expected id:version: {}", key))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        let sort = self
+            .sort
+            .as_deref()
+            .map(|key| SortOrder::from_key(key).ok_or_else(|| format!("Invalid sort order: {}", key)))
+            .transpose()?
+            .unwrap_or_default();
+
+        let filters = SearchFilters {
+            search_query: self.search.clone(),
+            min_credits: self.min_credits,
+            max_credits: self.max_credits,
+            semester_rotations: split_list(&self.semester_rotations),
+            exam_categories: split_list(&self.exam_categories),
+            study_program_ids,
+            study_program_names: split_list(&self.study_program_names),
+            component_types: split_list(&self.component_types),
+            component_languages: split_list(&self.component_languages),
+            faculty_ids: split_ids(&self.faculty_ids, "faculty")?,
+            institute_ids: split_ids(&self.institute_ids, "institute")?,
+            fachgebiet_ids: split_ids(&self.fachgebiet_ids, "Fachgebiet")?,
+            responsible_person_ids: split_ids(&self.responsible_person_ids, "responsible person")?,
+            examination_board_ids: split_ids(&self.examination_board_ids, "examination board")?,
+            graded: self.graded,
+            exam_types: split_list(&self.exam_types),
+            excluded_exam_types: split_list(&self.excluded_exam_types),
+            min_workload_hours: self.min_workload_hours,
+            max_workload_hours: self.max_workload_hours,
+            no_attendance_limit: self.no_attendance_limit.unwrap_or(false),
+            min_attendee_places: self.min_attendee_places,
+            module_keys,
+            newest_version_only: self.newest_version_only.unwrap_or(false),
+            include_discontinued: self.include_discontinued.unwrap_or(false),
+            sort,
+            ..Default::default()
+        };
+        check_module_keys(&filters)?;
+        Ok(filters)
+    }
+}
+
+fn bad_request(message: String) -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiError { error: message })
+}
+
+fn not_found(message: &str) -> HttpResponse {
+    HttpResponse::NotFound().json(ApiError {
+        error: message.to_string(),
+    })
+}
+
+fn internal_error(context: &str, e: sqlx::Error) -> HttpResponse {
+    tracing::error!("{}: {}", context, e);
+    HttpResponse::InternalServerError().json(ApiError {
+        error: context.to_string(),
+    })
+}
+
+/// Search module versions
+///
+/// Results are ordered by `sort`, by title if missing and not searching. Only
+/// the latest snapshot of each module version is considered.
+#[utoipa::path(
+    get,
+    path = "/api/v1/modules",
+    tag = "modules",
+    params(ModuleQuery),
+    responses(
+        (status = 200, description = "Matching module versions", body = ModulePage),
+        (status = 400, description = "Invalid query parameter", body = ApiError)
+    )
+)]
+pub async fn list_modules(query: web::Query<ModuleQuery>, pool: web::Data<PgPool>) -> HttpResponse {
+    let filters = match query.to_filters() {
+        Ok(filters) => filters,
+        Err(message) => return bad_request(message),
+    };
+    let page = query.page.unwrap_or(0);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    if page < 0 {
+        return bad_request("page must not be negative".to_string());
+    }
+    if !(1..=MAX_PAGE_SIZE).contains(&page_size) {
+        return bad_request(format!("page_size must be between 1 and {}", MAX_PAGE_SIZE));
+    }
+    if page.checked_mul(page_size).is_none() {
+        return bad_request("page is too large".to_string());
+    }
+
+    let total = match count_modules(&pool, &filters, None).await {
+        Ok(total) => total,
+        Err(e) => return internal_error("Failed to count modules", e),
+    };
+    match search_modules(&pool, &filters, page, page_size).await {
+        Ok(modules) => HttpResponse::Ok().json(ModulePage {
+            total,
+            page,
+            page_size,
+            modules,
+        }),
+        Err(e) => internal_error("Failed to search modules", e),
+    }
+}
+
+/// Get all details of a module version
+#[utoipa::path(
+    get,
+    path = "/api/v1/modules/{id}/{version}",
+    tag = "modules",
+    params(
+        ("id" = i32, Path, description = "Module number"),
+        ("version" = i32, Path, description = "Module version")
+    ),
+    responses(
+        (status = 200, description = "Module details", body = ModuleDetail),
+        (status = 404, description = "Unknown module version", body = ApiError)
+    )
+)]
+pub async fn get_module(path: web::Path<(i32, i32)>, pool: web::Data<PgPool>) -> HttpResponse {
+    let (id, version) = path.into_inner();
+    match load_module_detail(&pool, id, version).await {
+        Ok(Some(module)) => HttpResponse::Ok().json(module),
+        Ok(None) => not_found("Module not found"),
+        Err(e) => internal_error("Failed to load module", e),
+    }
+}
+
+/// Get the values accepted by the module search filters
+#[utoipa::path(
+    get,
+    path = "/api/v1/filter-options",
+    tag = "modules",
+    responses((status = 200, description = "Filter values found in the catalog", body = FilterOptions))
+)]
+pub async fn filter_options(pool: web::Data<PgPool>) -> HttpResponse {
+    match load_filter_options(&pool).await {
+        Ok(options) => HttpResponse::Ok().json(options),
+        Err(e) => internal_error("Failed to load filter options", e),
+    }
+}
+
+/// List study programs and their StuPOs
+#[utoipa::path(
+    get,
+    path = "/api/v1/study-programs",
+    tag = "study programs",
+    responses((status = 200, description = "All study programs, ordered by name", body = Vec<StudyProgram>))
+)]
+pub async fn list_study_programs(pool: web::Data<PgPool>) -> HttpResponse {
+    let programs = match sqlx::query!("SELECT id, name, link FROM study_program ORDER BY name")
+        .fetch_all(&**pool)
+        .await
+    {
+        Ok(programs) => programs,
+        Err(e) => return internal_error("Failed to load study programs", e),
+    };
+    let stupos = match sqlx::query!("SELECT id, study_program_id, name, link FROM stupo ORDER BY name")
+        .fetch_all(&**pool)
+        .await
+    {
+        Ok(stupos) => stupos,
+        Err(e) => return internal_error("Failed to load study programs", e),
+    };
+
+    let programs: Vec<StudyProgram> = programs
+        .into_iter()
+        .map(|program| StudyProgram {
+            stupos: stupos
+                .iter()
+                .filter(|s| s.study_program_id == program.id)
+                .map(|s| Stupo {
+                    id: s.id,
+                    name: s.name.clone(),
+                    link: s.link.clone(),
+                })
+                .collect(),
+            id: program.id,
+            name: program.name,
+            link: program.link,
+        })
+        .collect();
+
+    HttpResponse::Ok().json(programs)
+}
+
+/// List scraping runs, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/scraping-runs",
+    tag = "scraping runs",
+    responses((status = 200, description = "All scraping runs", body = Vec<ScrapingRunInfo>))
+)]
+pub async fn list_scraping_runs(pool: web::Data<PgPool>) -> HttpResponse {
+    match load_scraping_runs(&pool).await {
+        Ok(runs) => HttpResponse::Ok().json(runs),
+        Err(e) => internal_error("Failed to load scraping runs", e),
+    }
+}
+
+/// Load all scraping runs, newest first
+pub async fn load_scraping_runs(pool: &PgPool) -> Result<Vec<ScrapingRunInfo>, sqlx::Error> {
+    let runs = sqlx::query!(
+        r#"
+        SELECT id, status, started_at, completed_at, previous_run_id,
+               total_modules, successful_modules, failed_modules, skipped_modules
+        FROM scraping_run
+        ORDER BY id DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(runs
+        .into_iter()
+        .map(|run| ScrapingRunInfo {
+            id: run.id,
+            status: run.status,
+            started_at: run.started_at,
+            completed_at: run.completed_at,
+            previous_run_id: run.previous_run_id,
+            total_modules: run.total_modules,
+            successful_modules: run.successful_modules,
+            failed_modules: run.failed_modules,
+            skipped_modules: run.skipped_modules,
+        })
+        .collect())
+}
+
+/// OpenAPI document of this API version
+pub async fn openapi_json() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
+
+/// All routes of the v1 API
+pub fn v1_scope() -> actix_web::Scope {
+    web::scope("/api/v1")
+        .route("/openapi.json", web::get().to(openapi_json))
+        .route("/modules", web::get().to(list_modules))
+        .route("/modules/{id}/{version}", web::get().to(get_module))
+        .route("/filter-options", web::get().to(filter_options))
+        .route("/study-programs", web::get().to(list_study_programs))
+        .route("/scraping-runs", web::get().to(list_scraping_runs))
+}
@@ -14,7 +14,9 @@ use crate::scraper_state::{CsvInfo, LogLevel, ScraperState};
 use leptos_actix::extract;
 
 #[cfg(feature = "ssr")]
-use moses_scraper::{parse_csv_content, run_scraper, validate_csv_content, ScraperConfig};
+use moses_scraper::{
+    complete_run, fail_run, parse_csv_content, run_scraper, validate_csv_content, ScraperConfig,
+};
 
 #[cfg(feature = "ssr")]
 use std::sync::Arc;
@@ -100,11 +102,15 @@ pub async fn upload_csv(
 }
 
 /// Start scraping run
+///
+/// `full_run` marks the uploaded CSV as the complete module list, so that
+/// modules missing from it are marked as discontinued and reported as removed.
 #[server(StartScraper)]
 pub async fn start_scraper(
     auth_key: String,
     workers: u32,
     url_pattern: Option<String>,
+    full_run: bool,
 ) -> Result<StartScraperResponse, ServerFnError> {
     // Check auth
     if !check_auth(&auth_key).await? {
@@ -172,6 +178,10 @@ pub async fn start_scraper(
     // Initialize state
     state.start_run(scraping_run_id, modules.len()).await;
 
+    // Only an upload marked as full run counts as the full module list
+    let listed: Option<Vec<(i32, i32)>> =
+        full_run.then(|| modules.iter().map(|m| (m.number, m.version)).collect());
+
     // Spawn scraping task
     let state_clone = state.get_ref().clone();
     let pool_clone = Arc::clone(&pool);
@@ -268,30 +278,55 @@ pub async fn start_scraper(
 
         match result {
             Ok(progress) => {
+                // Mark the run as completed and generate its change report
+                match complete_run(&pool_clone, scraping_run_id, &progress, listed.as_deref()).await {
+                    Ok(changes) => {
+                        if let Some(previous_run_id) = changes.previous_run_id {
+                            state_clone
+                                .add_log(
+                                    format!(
+                                        "Changes since run {}: {} new, {} removed, {} changed",
+                                        previous_run_id, changes.added, changes.removed, changes.changed
+                                    ),
+                                    LogLevel::Info,
+                                )
+                                .await;
+                        }
+                        if changes.discontinued > 0 {
+                            state_clone
+                                .add_log(
+                                    format!("{} modules marked as discontinued", changes.discontinued),
+                                    LogLevel::Info,
+                                )
+                                .await;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to complete scraping run {}: {}", scraping_run_id, e);
+                        state_clone
+                            .add_log(format!("Failed to generate change report: {}", e), LogLevel::Error)
+                            .await;
+                    }
+                }
+
+                // Email subscribers about changes to the modules they follow
+                if let Ok(Some(config)) = crate::notifications::MailConfig::from_env() {
+                    match crate::notifications::send_pending_digests(&pool_clone, &config).await {
+                        Ok(sent) if sent > 0 => {
+                            state_clone
+                                .add_log(format!("Sent {} notification digests", sent), LogLevel::Info)
+                                .await;
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::error!("Failed to send notification digests: {}", e),
+                    }
+                }
+
                 // Update final state
                 state_clone
                     .complete_run(progress.successful, progress.failed, progress.skipped)
                     .await;
 
-                // Update database
-                let _ = sqlx::query!(
-                    r#"
-                    UPDATE scraping_run
-                    SET completed_at = NOW(),
-                        status = 'completed',
-                        successful_modules = $1,
-                        failed_modules = $2,
-                        skipped_modules = $3
-                    WHERE id = $4
-                    "#,
-                    progress.successful as i32,
-                    progress.failed as i32,
-                    progress.skipped as i32,
-                    scraping_run_id
-                )
-                .execute(&*pool_clone)
-                .await;
-
                 tracing::info!("Scraping run {} completed successfully", scraping_run_id);
             }
             Err(e) => {
@@ -300,17 +335,7 @@ pub async fn start_scraper(
                 state_clone.add_log(error_msg, LogLevel::Error).await;
 
                 // Update database
-                let _ = sqlx::query!(
-                    r#"
-                    UPDATE scraping_run
-                    SET completed_at = NOW(),
-                        status = 'failed'
-                    WHERE id = $1
-                    "#,
-                    scraping_run_id
-                )
-                .execute(&*pool_clone)
-                .await;
+                let _ = fail_run(&pool_clone, scraping_run_id).await;
 
                 tracing::error!("Scraping run {} failed", scraping_run_id);
             }
@@ -0,0 +1,77 @@
+use crate::collections::CollectionEntry;
+use crate::models::SharedList;
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Maximum number of module versions in a shared list
+pub const MAX_SHARED_MODULES: usize = 500;
+
+/// Maximum length of a note in a shared list, longer notes are cut
+pub const MAX_SHARED_NOTE_CHARS: usize = 2000;
+
+/// Publish a module list and return the token that references it
+///
+/// Shared lists can not be changed afterwards, sharing again creates a new link.
+#[server(ShareModuleList)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all, fields(module_count = entries.len())))]
+pub async fn share_module_list(name: String, mut entries: Vec<CollectionEntry>) -> Result<String, ServerFnError> {
+    use leptos_actix::extract;
+
+    let name = name.trim().chars().take(200).collect::<String>();
+    if entries.is_empty() {
+        return Err(ServerFnError::new("Add at least one module before sharing"));
+    }
+    if entries.len() > MAX_SHARED_MODULES {
+        return Err(ServerFnError::new(format!(
+            "At most {} modules can be shared",
+            MAX_SHARED_MODULES
+        )));
+    }
+    for entry in &mut entries {
+        entry.note = entry.note.trim().chars().take(MAX_SHARED_NOTE_CHARS).collect();
+    }
+    let json = serde_json::to_string(&entries)?;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO shared_list (name, entries)
+        VALUES ($1, $2::text::jsonb)
+        RETURNING token
+        "#,
+        name,
+        json
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.token)
+}
+
+/// Get a shared module list by its token
+#[server(GetSharedList)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all))]
+pub async fn get_shared_list(token: String) -> Result<SharedList, ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let row = sqlx::query!(
+        r#"SELECT token, name, entries::text as "entries!" FROM shared_list WHERE token = $1"#,
+        token.trim()
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ServerFnError::new("This shared list does not exist"))?;
+
+    Ok(SharedList {
+        token: row.token,
+        name: row.name,
+        entries: serde_json::from_str(&row.entries)?,
+    })
+}
@@ -0,0 +1,182 @@
+use crate::models::{PushOutcome, SyncSnapshot, SyncState};
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use crate::models::SearchFilters;
+
+#[cfg(feature = "ssr")]
+use db::PgPool;
+
+/// Maximum number of starred modules stored under a sync code
+pub const MAX_SYNCED_MODULES: usize = 2000;
+
+/// Maximum number of values per filter list stored under a sync code
+pub const MAX_SYNCED_FILTER_VALUES: usize = 200;
+
+/// Maximum length of the search query and of each filter value stored under a sync code
+pub const MAX_SYNCED_TEXT_CHARS: usize = 500;
+
+#[cfg(feature = "ssr")]
+fn validate_filters(filters: &SearchFilters) -> Result<(), String> {
+    super::modules::check_module_keys(filters)?;
+
+    let texts = [
+        &filters.semester_rotations,
+        &filters.exam_categories,
+        &filters.study_program_names,
+        &filters.component_types,
+        &filters.component_languages,
+        &filters.exam_types,
+        &filters.excluded_exam_types,
+    ];
+    let ids = [
+        &filters.study_program_ids,
+        &filters.faculty_ids,
+        &filters.institute_ids,
+        &filters.fachgebiet_ids,
+        &filters.responsible_person_ids,
+        &filters.examination_board_ids,
+    ];
+
+    let value_counts = texts
+        .iter()
+        .map(|list| list.as_ref().map_or(0, Vec::len))
+        .chain(ids.iter().map(|list| list.as_ref().map_or(0, Vec::len)));
+    if value_counts.into_iter().any(|count| count > MAX_SYNCED_FILTER_VALUES) {
+        return Err(format!("At most {} values per filter can be synced", MAX_SYNCED_FILTER_VALUES));
+    }
+
+    let mut values = filters.search_query.iter().chain(texts.iter().copied().flatten().flatten());
+    if values.any(|value| value.chars().count() > MAX_SYNCED_TEXT_CHARS) {
+        return Err(format!(
+            "Search queries and filter values can be at most {} characters long",
+            MAX_SYNCED_TEXT_CHARS
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+fn validate_state(state: &SyncState) -> Result<String, ServerFnError> {
+    if state.starred.len() > MAX_SYNCED_MODULES {
+        return Err(ServerFnError::new(format!(
+            "At most {} starred modules can be synced",
+            MAX_SYNCED_MODULES
+        )));
+    }
+    validate_filters(&state.filters).map_err(ServerFnError::new)?;
+    Ok(serde_json::to_string(state)?)
+}
+
+#[cfg(feature = "ssr")]
+fn parse_state(json: &str) -> Result<SyncState, ServerFnError> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Store a state under a new random sync code
+#[server(CreateSyncCode)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all))]
+pub async fn create_sync_code(state: SyncState) -> Result<SyncSnapshot, ServerFnError> {
+    use leptos_actix::extract;
+
+    let json = validate_state(&state)?;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO sync_state (state)
+        VALUES ($1::text::jsonb)
+        RETURNING token, revision
+        "#,
+        json
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(SyncSnapshot {
+        token: row.token,
+        revision: row.revision,
+        state,
+    })
+}
+
+/// Get the state stored under a sync code
+#[server(PullSyncState)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all))]
+pub async fn pull_sync_state(token: String) -> Result<SyncSnapshot, ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let row = sqlx::query!(
+        r#"SELECT token, revision, state::text as "state!" FROM sync_state WHERE token = $1"#,
+        token.trim()
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ServerFnError::new("Unknown or revoked sync code"))?;
+
+    Ok(SyncSnapshot {
+        token: row.token,
+        revision: row.revision,
+        state: parse_state(&row.state)?,
+    })
+}
+
+/// Store a state under a sync code if nobody else wrote since `base_revision`
+///
+/// On a conflict nothing is written and the current server state is returned
+/// for the client to merge and push again.
+#[server(PushSyncState)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all, fields(base_revision = base_revision)))]
+pub async fn push_sync_state(
+    token: String,
+    base_revision: i32,
+    state: SyncState,
+) -> Result<PushOutcome, ServerFnError> {
+    use leptos_actix::extract;
+
+    let json = validate_state(&state)?;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    let saved = sqlx::query!(
+        r#"
+        UPDATE sync_state
+        SET state = $3::text::jsonb, revision = revision + 1, updated_at = NOW()
+        WHERE token = $1 AND revision = $2
+        RETURNING revision
+        "#,
+        token,
+        base_revision,
+        json
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    match saved {
+        Some(row) => Ok(PushOutcome::Saved { revision: row.revision }),
+        None => Ok(PushOutcome::Conflict(pull_sync_state(token).await?)),
+    }
+}
+
+/// Delete a sync code and its state, unlinking every device
+#[server(RevokeSyncCode)]
+#[cfg_attr(feature = "ssr", tracing::instrument(level = "info", skip_all))]
+pub async fn revoke_sync_code(token: String) -> Result<(), ServerFnError> {
+    use leptos_actix::extract;
+
+    let pool = extract::<actix_web::web::Data<PgPool>>().await?;
+    let pool: &PgPool = &*pool;
+
+    sqlx::query!("DELETE FROM sync_state WHERE token = $1", token)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
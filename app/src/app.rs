@@ -1,6 +1,6 @@
-use crate::pages::{HomePage, ModuleDetailPage, ScraperPage};
+use crate::pages::{CollectionsPage, ComparePage, DegreeAuditPage, HomePage, ModuleDetailPage, ModuleGraphPage, NotificationsPage, PlannerPage, ScraperPage, SyncPage};
 use leptos::prelude::*;
-use leptos_meta::{provide_meta_context, Stylesheet, Title};
+use leptos_meta::{provide_meta_context, Link, Stylesheet, Title};
 use leptos_router::{
     components::{Route, Router, Routes},
     ParamSegment, StaticSegment, WildcardSegment,
@@ -19,12 +19,22 @@ pub fn App() -> impl IntoView {
         // sets the document title
         <Title text="TU Berlin Module Search"/>
 
+        // advertises the feed of new and changed modules
+        <Link rel="alternate" type_="application/atom+xml" title="New and changed modules" href="/feeds/changes.atom"/>
+
         // content for this welcome page
         <Router>
             <main>
                 <Routes fallback=move || "Not found.">
                     <Route path=StaticSegment("") view=HomePage/>
                     <Route path=(StaticSegment("module"), ParamSegment("id"), ParamSegment("version")) view=ModuleDetailPage/>
+                    <Route path=(StaticSegment("module"), ParamSegment("id"), ParamSegment("version"), StaticSegment("graph")) view=ModuleGraphPage/>
+                    <Route path=StaticSegment("compare") view=ComparePage/>
+                    <Route path=StaticSegment("collections") view=CollectionsPage/>
+                    <Route path=StaticSegment("planner") view=PlannerPage/>
+                    <Route path=StaticSegment("audit") view=DegreeAuditPage/>
+                    <Route path=StaticSegment("notifications") view=NotificationsPage/>
+                    <Route path=StaticSegment("sync") view=SyncPage/>
                     <Route path=StaticSegment("scrape") view=ScraperPage/>
                     <Route path=WildcardSegment("any") view=NotFound/>
                 </Routes>
@@ -0,0 +1,202 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_arch = "wasm32")]
+use codee::string::JsonSerdeCodec;
+#[cfg(target_arch = "wasm32")]
+use leptos_use::storage::use_local_storage;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CollectionEntry {
+    pub id: i32,
+    pub version: i32,
+    #[serde(default)]
+    pub note: String,
+}
+
+/// A named list of module versions, e.g. "maybe next semester"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ModuleCollection {
+    /// Local identifier, reassigned on import
+    #[serde(default)]
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub entries: Vec<CollectionEntry>,
+}
+
+impl ModuleCollection {
+    pub fn contains(&self, id: i32, version: i32) -> bool {
+        self.entries.iter().any(|e| e.id == id && e.version == version)
+    }
+
+    pub fn keys(&self) -> Vec<(i32, i32)> {
+        self.entries.iter().map(|e| (e.id, e.version)).collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct ModuleCollections {
+    pub collections: Vec<ModuleCollection>,
+    /// Last id handed out, ids of deleted collections are never reused
+    last_id: u32,
+}
+
+impl ModuleCollections {
+    pub fn get(&self, collection_id: u32) -> Option<&ModuleCollection> {
+        self.collections.iter().find(|c| c.id == collection_id)
+    }
+
+    fn get_mut(&mut self, collection_id: u32) -> Option<&mut ModuleCollection> {
+        self.collections.iter_mut().find(|c| c.id == collection_id)
+    }
+
+    fn next_id(&mut self) -> u32 {
+        // Data saved before the counter existed starts at its highest id
+        let highest = self.collections.iter().map(|c| c.id).max().unwrap_or(0);
+        self.last_id = self.last_id.max(highest) + 1;
+        self.last_id
+    }
+
+    /// Append a collection under a fresh id, dropping duplicate entries
+    fn push(&mut self, name: &str, mut entries: Vec<CollectionEntry>) {
+        let mut seen = Vec::new();
+        entries.retain(|e| {
+            let key = (e.id, e.version);
+            let first = !seen.contains(&key);
+            seen.push(key);
+            first
+        });
+        let name = match name.trim() {
+            "" => "Imported".to_string(),
+            name => name.to_string(),
+        };
+        let id = self.next_id();
+        self.collections.push(ModuleCollection { id, name, entries });
+    }
+}
+
+/// Exported JSON is either a single collection or a list of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CollectionImport {
+    Many(Vec<ModuleCollection>),
+    One(ModuleCollection),
+}
+
+pub fn use_module_collections() -> (Signal<ModuleCollections>, WriteSignal<ModuleCollections>) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let (collections, set_collections, _remove) =
+            use_local_storage::<ModuleCollections, JsonSerdeCodec>("module_collections");
+        (collections, set_collections)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // SSR fallback - just return dummy signals
+        let collections = RwSignal::new(ModuleCollections::default());
+        (collections.read_only().into(), collections.write_only())
+    }
+}
+
+pub fn create_collection(collections: &ModuleCollections, name: &str) -> ModuleCollections {
+    add_collection(collections, name, Vec::new())
+}
+
+/// Add a new collection with the given entries, e.g. from a shared list
+pub fn add_collection(collections: &ModuleCollections, name: &str, entries: Vec<CollectionEntry>) -> ModuleCollections {
+    let mut new_collections = collections.clone();
+    new_collections.push(name, entries);
+    new_collections
+}
+
+pub fn rename_collection(collections: &ModuleCollections, collection_id: u32, name: &str) -> ModuleCollections {
+    let mut new_collections = collections.clone();
+    if let Some(collection) = new_collections.get_mut(collection_id) {
+        collection.name = name.trim().to_string();
+    }
+    new_collections
+}
+
+pub fn delete_collection(collections: &ModuleCollections, collection_id: u32) -> ModuleCollections {
+    let mut new_collections = collections.clone();
+    new_collections.collections.retain(|c| c.id != collection_id);
+    new_collections
+}
+
+/// Add a module version to a collection, or remove it if it is already in there
+pub fn toggle_in_collection(
+    collections: &ModuleCollections,
+    collection_id: u32,
+    id: i32,
+    version: i32,
+) -> ModuleCollections {
+    let mut new_collections = collections.clone();
+    if let Some(collection) = new_collections.get_mut(collection_id) {
+        if collection.contains(id, version) {
+            collection.entries.retain(|e| !(e.id == id && e.version == version));
+        } else {
+            collection.entries.push(CollectionEntry {
+                id,
+                version,
+                note: String::new(),
+            });
+        }
+    }
+    new_collections
+}
+
+pub fn set_entry_note(
+    collections: &ModuleCollections,
+    collection_id: u32,
+    id: i32,
+    version: i32,
+    note: &str,
+) -> ModuleCollections {
+    let mut new_collections = collections.clone();
+    if let Some(entry) = new_collections
+        .get_mut(collection_id)
+        .and_then(|c| c.entries.iter_mut().find(|e| e.id == id && e.version == version))
+    {
+        entry.note = note.to_string();
+    }
+    new_collections
+}
+
+/// Add collections from exported JSON next to the existing ones
+///
+/// Imported collections get fresh ids so they never replace local ones, and
+/// duplicate entries within a collection are dropped.
+pub fn import_collections(collections: &ModuleCollections, json: &str) -> Result<ModuleCollections, String> {
+    let imported = match serde_json::from_str::<CollectionImport>(json) {
+        Ok(CollectionImport::Many(imported)) => imported,
+        Ok(CollectionImport::One(imported)) => vec![imported],
+        Err(_) => return Err("This is not an exported collection".to_string()),
+    };
+
+    let mut new_collections = collections.clone();
+    for collection in imported {
+        new_collections.push(&collection.name, collection.entries);
+    }
+    Ok(new_collections)
+}
+
+/// Absolute link that opens the search filtered to a shared list
+pub fn shared_list_url(token: &str) -> String {
+    let path = format!("/?shared={}", token);
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        if let Some(origin) = web_sys::window().and_then(|w| w.location().origin().ok()) {
+            return format!("{}{}", origin, path);
+        }
+    }
+
+    path
+}
+
+pub fn export_collections(collections: &[ModuleCollection]) -> String {
+    serde_json::to_string_pretty(collections).unwrap_or_default()
+}
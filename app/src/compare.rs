@@ -0,0 +1,136 @@
+use crate::models::ModuleDetail;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_arch = "wasm32")]
+use codee::string::JsonSerdeCodec;
+#[cfg(target_arch = "wasm32")]
+use leptos_use::storage::use_local_storage;
+
+/// More columns don't fit side by side
+pub const MAX_COMPARE: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CompareModules {
+    pub modules: Vec<(i32, i32)>, // (id, version), in the order they were added
+}
+
+pub fn use_compare_modules() -> (Signal<CompareModules>, WriteSignal<CompareModules>) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let (compare, set_compare, _remove) = use_local_storage::<CompareModules, JsonSerdeCodec>("compare_modules");
+        (compare, set_compare)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // SSR fallback - just return dummy signals
+        let compare = RwSignal::new(CompareModules::default());
+        (compare.read_only().into(), compare.write_only())
+    }
+}
+
+pub fn is_compared(compare: &CompareModules, id: i32, version: i32) -> bool {
+    compare.modules.contains(&(id, version))
+}
+
+/// Add or remove a module, the oldest entry is dropped once the list is full
+pub fn toggle_compare(compare: &CompareModules, id: i32, version: i32) -> CompareModules {
+    let mut new_compare = compare.clone();
+    let key = (id, version);
+
+    if new_compare.modules.contains(&key) {
+        new_compare.modules.retain(|k| *k != key);
+    } else {
+        if new_compare.modules.len() >= MAX_COMPARE {
+            new_compare.modules.remove(0);
+        }
+        new_compare.modules.push(key);
+    }
+
+    new_compare
+}
+
+/// Link to the compare page, e.g. `/compare?m=40033:3,50830:2`
+pub fn compare_url(keys: &[(i32, i32)]) -> String {
+    let param: Vec<String> = keys.iter().map(|(id, version)| format!("{}:{}", id, version)).collect();
+    format!("/compare?m={}", param.join(","))
+}
+
+/// Parse the `m` query parameter, skipping malformed and duplicate entries
+pub fn parse_compare_param(param: &str) -> Vec<(i32, i32)> {
+    let mut keys = Vec::new();
+    for entry in param.split(',') {
+        let Some((id, version)) = entry.trim().split_once(':') else {
+            continue;
+        };
+        if let (Ok(id), Ok(version)) = (id.parse::<i32>(), version.parse::<i32>()) {
+            if !keys.contains(&(id, version)) {
+                keys.push((id, version));
+            }
+        }
+    }
+    keys.truncate(MAX_COMPARE);
+    keys
+}
+
+/// A compared attribute with one cell (list of lines) per module
+pub struct CompareRow {
+    pub label: &'static str,
+    pub cells: Vec<Vec<String>>,
+}
+
+impl CompareRow {
+    pub fn differs(&self) -> bool {
+        self.cells.windows(2).any(|pair| pair[0] != pair[1])
+    }
+}
+
+/// Line up the compared attributes of the given modules
+pub fn compare_rows(modules: &[ModuleDetail]) -> Vec<CompareRow> {
+    let row = |label: &'static str, cell: &dyn Fn(&ModuleDetail) -> Vec<String>| CompareRow {
+        label,
+        cells: modules.iter().map(cell).collect(),
+    };
+
+    vec![
+        row("Credits", &|m| vec![format!("{} CP", m.credits)]),
+        row("Languages", &|m| vec![m.languages.join(", ")]),
+        row("Rotation", &|m| {
+            let mut rotations: Vec<String> = m.components.iter().map(|c| c.rotation.clone()).collect();
+            rotations.sort();
+            rotations.dedup();
+            rotations
+        }),
+        row("Components", &|m| {
+            m.components
+                .iter()
+                .map(|c| match &c.name {
+                    Some(name) => format!("{} {} ({} SWS)", c.component_type, name, c.sws),
+                    None => format!("{} {} ({} SWS)", c.component_type, c.number, c.sws),
+                })
+                .collect()
+        }),
+        row("Total SWS", &|m| vec![m.components.iter().map(|c| c.sws).sum::<i32>().to_string()]),
+        row("Exam form", &|m| {
+            m.exams
+                .iter()
+                .map(|e| format!("{}{}", e.exam_type, if e.graded { ", graded" } else { ", ungraded" }))
+                .collect()
+        }),
+        row("Exam components", &|m| {
+            m.exams
+                .iter()
+                .flat_map(|e| &e.components)
+                .map(|c| format!("{} ({}, {} points)", c.name, c.category, c.points))
+                .collect()
+        }),
+        row("Workload", &|m| m.workload.iter().map(|w| format!("{}: {}h", w.description, w.hours)).collect()),
+        row("Study programs", &|m| {
+            let mut programs: Vec<String> = m.study_programs.iter().map(|p| p.program_name.clone()).collect();
+            programs.sort();
+            programs.dedup();
+            programs
+        }),
+    ]
+}
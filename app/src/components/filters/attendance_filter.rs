@@ -0,0 +1,51 @@
+use crate::models::{FilterOptions, SearchFilters};
+use leptos::prelude::*;
+
+#[component]
+pub fn AttendanceFilter(
+    filters: RwSignal<SearchFilters>,
+    filter_options: Signal<Option<FilterOptions>>,
+) -> impl IntoView {
+    let largest_limit = move || filter_options.get().map(|opts| opts.attendee_limit_range.1).unwrap_or(0);
+
+    view! {
+        <div class="form-control">
+            <label class="label pb-1">
+                <span class="label-text font-semibold text-base flex items-center gap-2">
+                    <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" viewBox="0 0 20 20" fill="currentColor">
+                        <path d="M13 6a3 3 0 11-6 0 3 3 0 016 0zM18 8a2 2 0 11-4 0 2 2 0 014 0zM14 15a4 4 0 00-8 0v3h8v-3zM6 8a2 2 0 11-4 0 2 2 0 014 0zM16 18v-3a5.972 5.972 0 00-.75-2.906A3.005 3.005 0 0119 15v3h-3zM4.75 12.094A5.973 5.973 0 004 15v3H1v-3a3 3 0 013.75-2.906z" />
+                    </svg>
+                    "Attendance Limit"
+                </span>
+            </label>
+            <label class="label cursor-pointer justify-start gap-3 py-1">
+                <input
+                    type="checkbox"
+                    class="checkbox checkbox-ghost checkbox-sm"
+                    prop:checked=move || filters.get().no_attendance_limit
+                    on:change=move |ev| {
+                        let checked = event_target_checked(&ev);
+                        filters.update(|f| f.no_attendance_limit = checked);
+                    }
+                />
+                <span class="label-text text-sm">"No attendance limit"</span>
+            </label>
+            <label class="input input-bordered input-sm flex items-center gap-2 mt-1">
+                <span class="text-base-content/60">"At least"</span>
+                <input
+                    type="number"
+                    min="1"
+                    class="grow w-0"
+                    placeholder=move || largest_limit().to_string()
+                    prop:disabled=move || filters.get().no_attendance_limit
+                    prop:value=move || filters.get().min_attendee_places.map(|n| n.to_string()).unwrap_or_default()
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev).trim().parse().ok();
+                        filters.update(|f| f.min_attendee_places = value);
+                    }
+                />
+                <span class="text-base-content/60">"places"</span>
+            </label>
+        </div>
+    }
+}
@@ -1,10 +1,12 @@
-use crate::models::{FilterOptions, SearchFilters};
+use crate::components::filters::FacetCount;
+use crate::models::{FilterFacets, FilterOptions, SearchFilters};
 use leptos::prelude::*;
 
 #[component]
 pub fn ComponentLanguageFilter(
     filters: RwSignal<SearchFilters>,
     filter_options: Signal<Option<FilterOptions>>,
+    facets: Signal<Option<FilterFacets>>,
 ) -> impl IntoView {
     view! {
         <div class="form-control">
@@ -26,6 +28,8 @@ pub fn ComponentLanguageFilter(
                                     let lang_for_check = language.clone();
                                     let lang_for_change = language.clone();
                                     let lang_label = language.clone();
+                                    let facet_key = language.clone();
+                                    let count = Signal::derive(move || facets.get().map(|f| f.component_languages.get(&facet_key).copied().unwrap_or(0)));
                                     let is_checked = move || {
                                         filters.get()
                                             .component_languages
@@ -33,12 +37,21 @@ pub fn ComponentLanguageFilter(
                                             .map(|langs| langs.contains(&lang_for_check))
                                             .unwrap_or(false)
                                     };
+                                    // Options without matches stay enabled while selected, so they can be cleared
+                                    let disabled = {
+                                        let is_checked = is_checked.clone();
+                                        move || count.get() == Some(0) && !is_checked()
+                                    };
                                     view! {
-                                        <label class="label cursor-pointer justify-start gap-2 border border-base-300 rounded-lg px-3 py-2 hover:bg-base-200 transition-colors">
+                                        <label
+                                            class="label cursor-pointer justify-start gap-2 border border-base-300 rounded-lg px-3 py-2 hover:bg-base-200 transition-colors"
+                                            class:opacity-50=disabled.clone()
+                                        >
                                             <input
                                                 type="checkbox"
                                                 class="checkbox checkbox-ghost checkbox-sm"
                                                 prop:checked=is_checked
+                                                prop:disabled=disabled
                                                 on:change=move |ev| {
                                                     let checked = event_target_checked(&ev);
                                                     let language = lang_for_change.clone();
@@ -59,6 +72,7 @@ pub fn ComponentLanguageFilter(
                                                 }
                                             />
                                             <span class="label-text text-sm">{lang_label}</span>
+                                            <FacetCount count=count />
                                         </label>
                                     }
                                 })
@@ -1,10 +1,12 @@
-use crate::models::{FilterOptions, SearchFilters};
+use crate::components::filters::FacetCount;
+use crate::models::{FilterFacets, FilterOptions, SearchFilters};
 use leptos::prelude::*;
 
 #[component]
 pub fn ExamFilter(
     filters: RwSignal<SearchFilters>,
     filter_options: Signal<Option<FilterOptions>>,
+    facets: Signal<Option<FilterFacets>>,
 ) -> impl IntoView {
     view! {
         <div class="form-control">
@@ -27,6 +29,8 @@ pub fn ExamFilter(
                                     let category_for_check = category.clone();
                                     let category_for_change = category.clone();
                                     let category_label = category.clone();
+                                    let facet_key = category.clone();
+                                    let count = Signal::derive(move || facets.get().map(|f| f.exam_categories.get(&facet_key).copied().unwrap_or(0)));
                                     let is_checked = move || {
                                         filters.get()
                                             .exam_categories
@@ -34,12 +38,21 @@ pub fn ExamFilter(
                                             .map(|cats| cats.contains(&category_for_check))
                                             .unwrap_or(false)
                                     };
+                                    // Options without matches stay enabled while selected, so they can be cleared
+                                    let disabled = {
+                                        let is_checked = is_checked.clone();
+                                        move || count.get() == Some(0) && !is_checked()
+                                    };
                                     view! {
-                                        <label class="label cursor-pointer justify-start gap-2 border border-base-300 rounded-lg px-3 py-2 hover:bg-base-200 transition-colors">
+                                        <label
+                                            class="label cursor-pointer justify-start gap-2 border border-base-300 rounded-lg px-3 py-2 hover:bg-base-200 transition-colors"
+                                            class:opacity-50=disabled.clone()
+                                        >
                                             <input
                                                 type="checkbox"
                                                 class="checkbox checkbox-ghost checkbox-sm"
                                                 prop:checked=is_checked
+                                                prop:disabled=disabled
                                                 on:change=move |ev| {
                                                     let checked = event_target_checked(&ev);
                                                     let category = category_for_change.clone();
@@ -60,6 +73,7 @@ pub fn ExamFilter(
                                                 }
                                             />
                                             <span class="label-text text-sm">{category_label}</span>
+                                            <FacetCount count=count />
                                         </label>
                                     }
                                 })
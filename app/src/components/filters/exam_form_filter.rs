@@ -0,0 +1,124 @@
+use crate::components::filters::FacetCount;
+use crate::models::{FilterFacets, FilterOptions, SearchFilters};
+use leptos::prelude::*;
+
+/// Add or remove a value from an optional list, `None` once it is empty
+fn set_listed(list: &mut Option<Vec<String>>, value: &str, listed: bool) {
+    let mut values = list.take().unwrap_or_default();
+    values.retain(|v| v != value);
+    if listed {
+        values.push(value.to_string());
+    }
+    *list = if values.is_empty() { None } else { Some(values) };
+}
+
+#[component]
+pub fn ExamFormFilter(
+    filters: RwSignal<SearchFilters>,
+    filter_options: Signal<Option<FilterOptions>>,
+    facets: Signal<Option<FilterFacets>>,
+) -> impl IntoView {
+    let grading = [(None, "Any"), (Some(true), "Graded"), (Some(false), "Ungraded")];
+
+    view! {
+        <div class="form-control">
+            <label class="label pb-1">
+                <span class="label-text font-semibold text-base flex items-center gap-2">
+                    <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" viewBox="0 0 20 20" fill="currentColor">
+                        <path fill-rule="evenodd" d="M4 4a2 2 0 012-2h4.586A2 2 0 0112 2.586L15.414 6A2 2 0 0116 7.414V16a2 2 0 01-2 2H6a2 2 0 01-2-2V4zm2 6a1 1 0 011-1h6a1 1 0 110 2H7a1 1 0 01-1-1zm1 3a1 1 0 100 2h6a1 1 0 100-2H7z" clip-rule="evenodd" />
+                    </svg>
+                    "Exam Form"
+                </span>
+            </label>
+
+            <div class="join w-full mb-2">
+                {grading.into_iter().map(|(value, label)| {
+                    let count = Signal::derive(move || {
+                        let facets = facets.get()?;
+                        value.map(|graded| if graded { facets.graded } else { facets.ungraded })
+                    });
+                    let selected = move || filters.get().graded == value;
+                    view! {
+                        <button
+                            class="btn btn-sm join-item flex-1 gap-1"
+                            class:btn-primary=selected
+                            prop:disabled=move || count.get() == Some(0) && !selected()
+                            on:click=move |_| filters.update(|f| f.graded = value)
+                        >
+                            {label}
+                            <FacetCount count=count />
+                        </button>
+                    }
+                }).collect_view()}
+            </div>
+
+            <div class="flex flex-col gap-1">
+                {move || {
+                    filter_options.get()
+                        .map(|opts| {
+                            opts.exam_types
+                                .into_iter()
+                                .map(|exam_type| {
+                                    let included = {
+                                        let exam_type = exam_type.clone();
+                                        move || filters.get().exam_types.is_some_and(|types| types.contains(&exam_type))
+                                    };
+                                    let excluded = {
+                                        let exam_type = exam_type.clone();
+                                        move || filters.get().excluded_exam_types.is_some_and(|types| types.contains(&exam_type))
+                                    };
+                                    let count = {
+                                        let exam_type = exam_type.clone();
+                                        Signal::derive(move || facets.get().map(|f| f.exam_types.get(&exam_type).copied().unwrap_or(0)))
+                                    };
+                                    let include_type = exam_type.clone();
+                                    let exclude_type = exam_type.clone();
+                                    view! {
+                                        <div class="flex items-center justify-between gap-2 py-1 px-2 rounded hover:bg-base-200">
+                                            <span class="label-text text-sm break-words flex-1">{exam_type}</span>
+                                            <FacetCount count=count />
+                                            <div class="join shrink-0">
+                                                <button
+                                                    class="btn btn-xs join-item"
+                                                    class:btn-success=included.clone()
+                                                    prop:disabled={
+                                                        let included = included.clone();
+                                                        move || count.get() == Some(0) && !included()
+                                                    }
+                                                    title="Only modules with this exam form"
+                                                    on:click=move |_| {
+                                                        let listed = !included();
+                                                        filters.update(|f| {
+                                                            set_listed(&mut f.exam_types, &include_type, listed);
+                                                            set_listed(&mut f.excluded_exam_types, &include_type, false);
+                                                        });
+                                                    }
+                                                >
+                                                    "Include"
+                                                </button>
+                                                <button
+                                                    class="btn btn-xs join-item"
+                                                    class:btn-error=excluded.clone()
+                                                    title="Hide modules with this exam form"
+                                                    on:click=move |_| {
+                                                        let listed = !excluded();
+                                                        filters.update(|f| {
+                                                            set_listed(&mut f.excluded_exam_types, &exclude_type, listed);
+                                                            set_listed(&mut f.exam_types, &exclude_type, false);
+                                                        });
+                                                    }
+                                                >
+                                                    "Exclude"
+                                                </button>
+                                            </div>
+                                        </div>
+                                    }
+                                })
+                                .collect_view()
+                        })
+                        .unwrap_or_else(|| vec![])
+                }}
+            </div>
+        </div>
+    }
+}
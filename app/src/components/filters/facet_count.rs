@@ -0,0 +1,11 @@
+use leptos::prelude::*;
+
+/// Number of modules an option would match, hidden until the counts are loaded
+#[component]
+pub fn FacetCount(count: Signal<Option<i64>>) -> impl IntoView {
+    view! {
+        {move || count.get().map(|count| view! {
+            <span class="badge badge-ghost badge-sm tabular-nums">{count}</span>
+        })}
+    }
+}
@@ -1,20 +1,37 @@
+use crate::collections::ModuleCollections;
 use crate::components::filters::{
-    ComponentLanguageFilter, ComponentTypeFilter, CreditFilter, ExamFilter, ProgramFilter,
-    SemesterFilter,
+    AttendanceFilter, ComponentLanguageFilter, ComponentTypeFilter, CreditFilter, ExamFilter,
+    ExamFormFilter, OrganizationFilter, ProgramFilter, SemesterFilter, WorkloadFilter,
 };
-use crate::models::{FilterOptions, SearchFilters};
+use crate::models::{FilterFacets, FilterOptions, OrganizationUnit, SearchFilters};
+use crate::query_syntax::{parse_query, QueryError, QUERY_KEYS};
 use leptos::prelude::*;
 
 #[component]
 pub fn FilterPanel(
     filters: RwSignal<SearchFilters>,
     filter_options: Signal<Option<Result<FilterOptions, ServerFnError>>>,
+    collections: Signal<ModuleCollections>,
+    /// Matches per option under the active filters, `None` while loading
+    facets: Signal<Option<FilterFacets>>,
 ) -> impl IntoView {
     let filter_options_ok = Memo::new(move |_| filter_options.get().and_then(|r| r.ok()));
 
     // Local search text state for debouncing
     let search_text = RwSignal::new(String::new());
     let debounce_timer = RwSignal::new(0_i32);
+    // Error of the typed query, the filters keep the last valid one meanwhile
+    let query_error = RwSignal::new(None::<(QueryError, String)>);
+
+    // Follow queries changed outside the search box, e.g. by removing a chip
+    let search_query = Memo::new(move |_| filters.with(|f| f.search_query.clone()));
+    Effect::new(move |_| {
+        let query = search_query.get().unwrap_or_default();
+        if query != search_text.get_untracked() {
+            search_text.set(query);
+            query_error.set(None);
+        }
+    });
 
     view! {
         <div class="card bg-base-100 shadow-xl overflow-hidden max-h-[calc(100vh-7rem)]">
@@ -31,6 +48,7 @@ pub fn FilterPanel(
                         on:click=move |_| {
                             filters.set(SearchFilters::default());
                             search_text.set(String::new());
+                            query_error.set(None);
                         }
                         title="Clear all filters"
                     >
@@ -63,6 +81,78 @@ pub fn FilterPanel(
                     </label>
                 </div>
 
+                // Collection select, only shown once a collection exists
+                {move || {
+                    let collections = collections.get().collections;
+                    if collections.is_empty() {
+                        return view! { <></> }.into_any();
+                    }
+                    let selected = filters.get().collection_id;
+                    view! {
+                        <div class="form-control">
+                            <select
+                                class="select select-sm w-full"
+                                on:change=move |ev| {
+                                    let value = event_target_value(&ev).parse::<u32>().ok();
+                                    filters.update(|f| f.collection_id = value);
+                                }
+                            >
+                                <option value="" selected=selected.is_none()>"All modules"</option>
+                                {collections.into_iter().map(|collection| {
+                                    let is_selected = selected == Some(collection.id);
+                                    view! {
+                                        <option value=collection.id.to_string() selected=is_selected>
+                                            {collection.name} " (" {collection.entries.len()} ")"
+                                        </option>
+                                    }
+                                }).collect_view()}
+                            </select>
+                        </div>
+                    }.into_any()
+                }}
+
+                // Newest valid version checkbox
+                <div class="form-control">
+                    <label class="label cursor-pointer justify-start gap-3 py-1">
+                        <input
+                            type="checkbox"
+                            class="checkbox checkbox-ghost"
+                            prop:checked=move || filters.get().newest_version_only
+                            on:change=move |ev| {
+                                let checked = event_target_checked(&ev);
+                                filters.update(|f| f.newest_version_only = checked);
+                            }
+                        />
+                        <span class="label-text flex items-center gap-2">
+                            <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M19 11H5m14 0a2 2 0 012 2v6a2 2 0 01-2 2H5a2 2 0 01-2-2v-6a2 2 0 012-2m14 0V9a2 2 0 00-2-2M5 11V9a2 2 0 012-2m0 0V5a2 2 0 012-2h6a2 2 0 012 2v2M7 7h10" />
+                            </svg>
+                            "Newest valid version only"
+                        </span>
+                    </label>
+                </div>
+
+                // Discontinued modules checkbox
+                <div class="form-control">
+                    <label class="label cursor-pointer justify-start gap-3 py-1">
+                        <input
+                            type="checkbox"
+                            class="checkbox checkbox-ghost"
+                            prop:checked=move || filters.get().include_discontinued
+                            on:change=move |ev| {
+                                let checked = event_target_checked(&ev);
+                                filters.update(|f| f.include_discontinued = checked);
+                            }
+                        />
+                        <span class="label-text flex items-center gap-2">
+                            <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M5 8h14M5 8a2 2 0 110-4h14a2 2 0 110 4M5 8v10a2 2 0 002 2h10a2 2 0 002-2V8m-9 4h4" />
+                            </svg>
+                            "Include discontinued"
+                        </span>
+                    </label>
+                </div>
+
                 <div class="divider my-0"></div>
 
                 // Search input
@@ -77,8 +167,9 @@ pub fn FilterPanel(
                     </label>
                     <input
                         type="text"
-                        placeholder="Search modules..."
+                        placeholder="Search modules, e.g. lp:>=6 lang:en"
                         class="input input-bordered w-full"
+                        class:input-error=move || query_error.with(Option::is_some)
                         prop:value=move || search_text.get()
                         on:input=move |ev| {
                             let value = event_target_value(&ev);
@@ -92,6 +183,11 @@ pub fn FilterPanel(
                             let _handle = gloo_timers::callback::Timeout::new(300, move || {
                                 // Only update if timer hasn't changed (no newer input)
                                 if debounce_timer.get_untracked() == timer_id {
+                                    if let Err(error) = parse_query(&value) {
+                                        query_error.set(Some((error, value)));
+                                        return;
+                                    }
+                                    query_error.set(None);
                                     let mut new_filters = filters.get_untracked();
                                     new_filters.search_query = if value.is_empty() {
                                         None
@@ -105,6 +201,29 @@ pub fn FilterPanel(
                             std::mem::forget(_handle);
                         }
                     />
+                    {move || query_error.get().map(|(error, query)| {
+                        let fragment = query.get(error.span.clone()).unwrap_or_default().to_string();
+                        view! {
+                            <p class="text-error text-xs mt-1 break-words">
+                                {error.message}
+                                {(!fragment.is_empty()).then(|| view! {
+                                    " in " <code class="font-mono">{fragment}</code>
+                                })}
+                            </p>
+                        }
+                    })}
+                    <details class="text-xs text-base-content/70 mt-1">
+                        <summary class="cursor-pointer">"Query syntax"</summary>
+                        <p class="mt-1">
+                            "Words match the title, "<code>"\"quoted phrases\""</code>" as a whole and "
+                            <code>"-word"</code>" excludes titles. Filters:"
+                        </p>
+                        <ul class="mt-1 space-y-0.5">
+                            {QUERY_KEYS.into_iter().map(|(key, description)| view! {
+                                <li><code class="font-mono">{key}":"</code>" "{description}</li>
+                            }).collect_view()}
+                        </ul>
+                    </details>
                 </div>
 
                 <div class="divider my-0"></div>
@@ -113,6 +232,7 @@ pub fn FilterPanel(
                 <ProgramFilter
                     filters=filters
                     filter_options=filter_options_ok.into()
+                    facets=facets
                 />
 
                 <div class="divider my-0"></div>
@@ -129,6 +249,7 @@ pub fn FilterPanel(
                 <SemesterFilter
                     filters=filters
                     filter_options=filter_options_ok.into()
+                    facets=facets
                 />
 
                 <div class="divider my-0"></div>
@@ -137,6 +258,32 @@ pub fn FilterPanel(
                 <ExamFilter
                     filters=filters
                     filter_options=filter_options_ok.into()
+                    facets=facets
+                />
+
+                <div class="divider my-0"></div>
+
+                // Graded and exam form filter
+                <ExamFormFilter
+                    filters=filters
+                    filter_options=filter_options_ok.into()
+                    facets=facets
+                />
+
+                <div class="divider my-0"></div>
+
+                // Total workload filter
+                <WorkloadFilter
+                    filters=filters
+                    filter_options=filter_options_ok.into()
+                />
+
+                <div class="divider my-0"></div>
+
+                // Attendance limit filter
+                <AttendanceFilter
+                    filters=filters
+                    filter_options=filter_options_ok.into()
                 />
 
                 <div class="divider my-0"></div>
@@ -145,6 +292,7 @@ pub fn FilterPanel(
                 <ComponentTypeFilter
                     filters=filters
                     filter_options=filter_options_ok.into()
+                    facets=facets
                 />
 
                 <div class="divider my-0"></div>
@@ -153,7 +301,19 @@ pub fn FilterPanel(
                 <ComponentLanguageFilter
                     filters=filters
                     filter_options=filter_options_ok.into()
+                    facets=facets
                 />
+
+                // Faculty, institute, Fachgebiet, responsible person and examination board filters
+                {OrganizationUnit::ALL.into_iter().map(|unit| view! {
+                    <div class="divider my-0"></div>
+                    <OrganizationFilter
+                        unit=unit
+                        filters=filters
+                        filter_options=filter_options_ok.into()
+                        facets=facets
+                    />
+                }).collect_view()}
             </div>
         </div>
     }
@@ -1,15 +1,27 @@
+pub mod attendance_filter;
 pub mod component_language_filter;
 pub mod component_type_filter;
 pub mod credit_filter;
 pub mod exam_filter;
+pub mod exam_form_filter;
+pub mod facet_count;
 pub mod filter_panel;
+pub mod organization_filter;
 pub mod program_filter;
+pub mod query_chips;
 pub mod semester_filter;
+pub mod workload_filter;
 
+pub use attendance_filter::*;
 pub use component_language_filter::*;
 pub use component_type_filter::*;
 pub use credit_filter::*;
 pub use exam_filter::*;
+pub use exam_form_filter::*;
+pub use facet_count::*;
 pub use filter_panel::*;
+pub use organization_filter::*;
 pub use program_filter::*;
+pub use query_chips::*;
 pub use semester_filter::*;
+pub use workload_filter::*;
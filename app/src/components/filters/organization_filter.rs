@@ -0,0 +1,92 @@
+use crate::components::filters::FacetCount;
+use crate::models::{FilterFacets, FilterOptions, OrganizationUnit, SearchFilters};
+use leptos::prelude::*;
+
+#[component]
+pub fn OrganizationFilter(
+    unit: OrganizationUnit,
+    filters: RwSignal<SearchFilters>,
+    filter_options: Signal<Option<FilterOptions>>,
+    facets: Signal<Option<FilterFacets>>,
+) -> impl IntoView {
+    let search_query = RwSignal::new(String::new());
+
+    let icon = match unit {
+        OrganizationUnit::ResponsiblePerson => "M10 9a3 3 0 100-6 3 3 0 000 6zm-7 9a7 7 0 1114 0H3z",
+        _ => "M4 4a2 2 0 012-2h8a2 2 0 012 2v12a1 1 0 110 2h-3a1 1 0 01-1-1v-2a1 1 0 00-1-1H9a1 1 0 00-1 1v2a1 1 0 01-1 1H4a1 1 0 110-2V4zm3 1h2v2H7V5zm2 4H7v2h2V9zm2-4h2v2h-2V5zm2 4h-2v2h2V9z",
+    };
+
+    view! {
+        <div class="form-control">
+            <label class="label pb-1">
+                <span class="label-text font-semibold text-base flex items-center gap-2">
+                    <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" viewBox="0 0 20 20" fill="currentColor">
+                        <path fill-rule="evenodd" d=icon clip-rule="evenodd" />
+                    </svg>
+                    {unit.label()}
+                </span>
+            </label>
+
+            <input
+                type="text"
+                placeholder=format!("Search {}...", unit.label().to_lowercase())
+                class="input input-bordered input-sm w-full mb-2"
+                prop:value=move || search_query.get()
+                on:input=move |ev| {
+                    search_query.set(event_target_value(&ev));
+                }
+            />
+
+            <div class="flex flex-col gap-1 max-h-48 overflow-y-auto overflow-x-hidden border border-base-300 rounded-lg p-2 bg-base-200/30">
+                {move || {
+                    let query = search_query.get().to_lowercase();
+                    filter_options.get()
+                        .map(|opts| {
+                            unit.options(&opts)
+                                .iter()
+                                .filter(|option| query.is_empty() || option.name.to_lowercase().contains(&query))
+                                .map(|option| {
+                                    let option_id = option.id;
+                                    let option_name = option.name.clone();
+                                    let count = Signal::derive(move || facets.get().map(|f| unit.facet(&f).get(&option_id).copied().unwrap_or(0)));
+                                    let is_checked = move || {
+                                        filters.with(|f| unit.selected(f).as_ref().is_some_and(|ids| ids.contains(&option_id)))
+                                    };
+                                    // Options without matches stay enabled while selected, so they can be cleared
+                                    let disabled = move || count.get() == Some(0) && !is_checked();
+                                    view! {
+                                        <label
+                                            class="flex items-start cursor-pointer gap-2 py-1 px-2 hover:bg-base-200 rounded w-full"
+                                            class:opacity-50=disabled
+                                        >
+                                            <input
+                                                type="checkbox"
+                                                class="checkbox checkbox-ghost checkbox-sm shrink-0 mt-0.5"
+                                                prop:checked=is_checked
+                                                prop:disabled=disabled
+                                                on:change=move |ev| {
+                                                    let checked = event_target_checked(&ev);
+                                                    filters.update(|f| {
+                                                        let selected = unit.selected_mut(f);
+                                                        let mut ids = selected.take().unwrap_or_default();
+                                                        ids.retain(|&id| id != option_id);
+                                                        if checked {
+                                                            ids.push(option_id);
+                                                        }
+                                                        *selected = if ids.is_empty() { None } else { Some(ids) };
+                                                    });
+                                                }
+                                            />
+                                            <span class="label-text text-sm break-words flex-1">{option_name}</span>
+                                            <FacetCount count=count />
+                                        </label>
+                                    }
+                                })
+                                .collect_view()
+                        })
+                        .unwrap_or_else(|| vec![])
+                }}
+            </div>
+        </div>
+    }
+}
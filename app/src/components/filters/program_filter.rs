@@ -1,10 +1,12 @@
-use crate::models::{FilterOptions, SearchFilters};
+use crate::components::filters::FacetCount;
+use crate::models::{FilterFacets, FilterOptions, SearchFilters};
 use leptos::prelude::*;
 
 #[component]
 pub fn ProgramFilter(
     filters: RwSignal<SearchFilters>,
     filter_options: Signal<Option<FilterOptions>>,
+    facets: Signal<Option<FilterFacets>>,
 ) -> impl IntoView {
     let search_query = RwSignal::new(String::new());
 
@@ -46,6 +48,7 @@ pub fn ProgramFilter(
                                 .map(|program| {
                                     let program_id = program.id;
                                     let program_name = program.name.clone();
+                                    let count = Signal::derive(move || facets.get().map(|f| f.study_programs.get(&program_id).copied().unwrap_or(0)));
                                     let is_checked = move || {
                                         filters.get()
                                             .study_program_ids
@@ -53,12 +56,18 @@ pub fn ProgramFilter(
                                             .map(|ids| ids.contains(&program_id))
                                             .unwrap_or(false)
                                     };
+                                    // Options without matches stay enabled while selected, so they can be cleared
+                                    let disabled = move || count.get() == Some(0) && !is_checked();
                                     view! {
-                                        <label class="flex items-start cursor-pointer gap-2 py-1 px-2 hover:bg-base-200 rounded w-full">
+                                        <label
+                                            class="flex items-start cursor-pointer gap-2 py-1 px-2 hover:bg-base-200 rounded w-full"
+                                            class:opacity-50=disabled
+                                        >
                                             <input
                                                 type="checkbox"
                                                 class="checkbox checkbox-ghost checkbox-sm shrink-0 mt-0.5"
                                                 prop:checked=is_checked
+                                                prop:disabled=disabled
                                                 on:change=move |ev| {
                                                     let checked = event_target_checked(&ev);
                                                     let mut new_filters = filters.get();
@@ -78,6 +87,7 @@ pub fn ProgramFilter(
                                                 }
                                             />
                                             <span class="label-text text-sm break-words flex-1">{program_name}</span>
+                                            <FacetCount count=count />
                                         </label>
                                     }
                                 })
@@ -86,6 +96,22 @@ pub fn ProgramFilter(
                         .unwrap_or_else(|| vec![])
                 }}
             </div>
+
+            // Feed of new and changed modules when a single program is selected
+            {move || match filters.get().study_program_ids.as_deref() {
+                Some([program_id]) => view! {
+                    <a
+                        href=format!("/feeds/program/{}.atom", program_id)
+                        class="link link-hover text-xs text-base-content/60 mt-1 flex items-center gap-1"
+                    >
+                        <svg xmlns="http://www.w3.org/2000/svg" class="h-3.5 w-3.5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M6 5c7.18 0 13 5.82 13 13M6 11a7 7 0 017 7m-6 0a1 1 0 11-2 0 1 1 0 012 0z" />
+                        </svg>
+                        "Feed of new and changed modules"
+                    </a>
+                }.into_any(),
+                _ => view! { <></> }.into_any(),
+            }}
         </div>
     }
 }
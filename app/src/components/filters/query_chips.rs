@@ -0,0 +1,47 @@
+use crate::models::SearchFilters;
+use crate::query_syntax::{parse_query, remove_clause};
+use leptos::prelude::*;
+
+/// Filters typed into the search box, each removable on its own
+#[component]
+pub fn QueryChips(filters: RwSignal<SearchFilters>) -> impl IntoView {
+    let query = Memo::new(move |_| filters.with(|f| f.search_query.clone()));
+
+    view! {
+        {move || {
+            let query = query.get()?;
+            let clauses: Vec<_> = parse_query(&query)
+                .ok()?
+                .clauses
+                .into_iter()
+                .filter(|clause| !clause.plain)
+                .collect();
+            if clauses.is_empty() {
+                return None;
+            }
+            Some(view! {
+                <div class="flex flex-wrap items-center gap-2 mb-4">
+                    <span class="text-sm text-base-content/60">"From your search:"</span>
+                    {clauses.into_iter().map(|clause| {
+                        let query = query.clone();
+                        view! {
+                            <span class="badge badge-primary badge-soft gap-1">
+                                {clause.label}
+                                <button
+                                    class="cursor-pointer opacity-70 hover:opacity-100"
+                                    title="Remove from search"
+                                    on:click=move |_| {
+                                        let rest = remove_clause(&query, &clause.span);
+                                        filters.update(|f| f.search_query = rest.clone());
+                                    }
+                                >
+                                    "✕"
+                                </button>
+                            </span>
+                        }
+                    }).collect_view()}
+                </div>
+            })
+        }}
+    }
+}
@@ -0,0 +1,59 @@
+use crate::models::{FilterOptions, SearchFilters};
+use leptos::prelude::*;
+
+/// Parse a number input, empty or invalid input clears the bound
+fn parse_bound(value: &str) -> Option<i32> {
+    value.trim().parse().ok()
+}
+
+#[component]
+pub fn WorkloadFilter(
+    filters: RwSignal<SearchFilters>,
+    filter_options: Signal<Option<FilterOptions>>,
+) -> impl IntoView {
+    let range = move || filter_options.get().map(|opts| opts.workload_range).unwrap_or((0, 0));
+
+    view! {
+        <div class="form-control">
+            <label class="label pb-1">
+                <span class="label-text font-semibold text-base flex items-center gap-2">
+                    <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" viewBox="0 0 20 20" fill="currentColor">
+                        <path fill-rule="evenodd" d="M10 18a8 8 0 100-16 8 8 0 000 16zm1-12a1 1 0 10-2 0v4a1 1 0 00.293.707l2.828 2.829a1 1 0 101.415-1.415L11 9.586V6z" clip-rule="evenodd" />
+                    </svg>
+                    "Workload"
+                </span>
+            </label>
+            <div class="flex items-center gap-2">
+                <label class="input input-bordered input-sm flex items-center gap-1 flex-1">
+                    <input
+                        type="number"
+                        min="0"
+                        class="grow w-0"
+                        placeholder=move || range().0.to_string()
+                        prop:value=move || filters.get().min_workload_hours.map(|h| h.to_string()).unwrap_or_default()
+                        on:change=move |ev| {
+                            let value = parse_bound(&event_target_value(&ev));
+                            filters.update(|f| f.min_workload_hours = value);
+                        }
+                    />
+                    <span class="text-base-content/60">"h"</span>
+                </label>
+                <span class="text-base-content/60">"–"</span>
+                <label class="input input-bordered input-sm flex items-center gap-1 flex-1">
+                    <input
+                        type="number"
+                        min="0"
+                        class="grow w-0"
+                        placeholder=move || range().1.to_string()
+                        prop:value=move || filters.get().max_workload_hours.map(|h| h.to_string()).unwrap_or_default()
+                        on:change=move |ev| {
+                            let value = parse_bound(&event_target_value(&ev));
+                            filters.update(|f| f.max_workload_hours = value);
+                        }
+                    />
+                    <span class="text-base-content/60">"h"</span>
+                </label>
+            </div>
+        </div>
+    }
+}
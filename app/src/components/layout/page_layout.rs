@@ -1,4 +1,5 @@
 use leptos::prelude::*;
+use leptos_router::components::A;
 use crate::api::get_latest_scraping_run;
 use chrono::{DateTime, Utc, Local};
 
@@ -48,6 +49,14 @@ pub fn PageLayout(children: Children) -> impl IntoView {
                                     </p>
                                 </div>
                             </div>
+                            <A href="/planner" attr:class="btn btn-ghost btn-sm gap-2 shrink-0 lg:hidden">
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 7V3m8 4V3m-9 8h10M5 21h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v12a2 2 0 002 2z" />
+                                </svg>
+                                <span class="hidden sm:inline">
+                                    "Planner"
+                                </span>
+                            </A>
                             <a
                                 href="https://github.com/zortax/tub-modules"
                                 target="_blank"
@@ -82,6 +91,12 @@ pub fn PageLayout(children: Children) -> impl IntoView {
                                     })
                                 })}
                             </Suspense>
+                            <A href="/planner" attr:class="btn btn-ghost btn-sm gap-2">
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 7V3m8 4V3m-9 8h10M5 21h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v12a2 2 0 002 2z" />
+                                </svg>
+                                "Planner"
+                            </A>
                             <a
                                 href="https://github.com/zortax/tub-modules"
                                 target="_blank"
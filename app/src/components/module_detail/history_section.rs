@@ -0,0 +1,125 @@
+use crate::api::get_module_history;
+use crate::models::{FieldChangeInfo, ModuleHistoryEntry};
+use leptos::prelude::*;
+
+#[component]
+pub fn HistorySection(id: i32, version: i32) -> impl IntoView {
+    let history = Resource::new(move || (id, version), |(id, version)| get_module_history(id, version));
+
+    view! {
+        <Suspense fallback=move || {
+            view! {
+                <div class="flex items-center justify-center py-12">
+                    <span class="loading loading-spinner loading-lg"></span>
+                </div>
+            }
+        }>
+            {move || Suspend::new(async move {
+                match history.await {
+                    Ok(entries) if entries.len() <= 1 => {
+                        view! {
+                            <div class="alert">
+                                <span>"No changes recorded since this module was first scraped."</span>
+                            </div>
+                        }
+                        .into_any()
+                    }
+                    Ok(entries) => {
+                        view! {
+                            <div class="flex flex-col gap-4">
+                                {entries.into_iter().map(|entry| view! { <HistoryEntry entry=entry /> }).collect_view()}
+                            </div>
+                        }
+                        .into_any()
+                    }
+                    Err(e) => {
+                        view! {
+                            <div class="alert alert-error">
+                                <span>"Error loading module history: " {e.to_string()}</span>
+                            </div>
+                        }
+                        .into_any()
+                    }
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn HistoryEntry(entry: ModuleHistoryEntry) -> impl IntoView {
+    let date = entry.started_at.format("%d.%m.%Y").to_string();
+
+    view! {
+        <div class="card bg-base-100 shadow-sm">
+            <div class="card-body p-5 gap-3">
+                <div class="flex items-center justify-between gap-3">
+                    <h2 class="card-title text-lg text-primary">{date}</h2>
+                    <div class="flex items-center gap-2">
+                        {if entry.first_seen {
+                            view! { <div class="badge badge-soft badge-success badge-sm">"First scraped"</div> }.into_any()
+                        } else {
+                            let count = entry.changes.len();
+                            view! {
+                                <div class="badge badge-soft badge-warning badge-sm">
+                                    {count} {if count == 1 { " change" } else { " changes" }}
+                                </div>
+                            }.into_any()
+                        }}
+                        <span class="text-xs text-base-content/50">"Run #" {entry.scraping_run_id}</span>
+                    </div>
+                </div>
+                {entry.changes.into_iter().map(|change| view! { <FieldChangeRow change=change /> }).collect_view()}
+            </div>
+        </div>
+    }
+}
+
+/// A changed field, list fields as removed and added lines, free text old against new
+#[component]
+pub fn FieldChangeRow(change: FieldChangeInfo) -> impl IntoView {
+    let label = change.label().to_string();
+
+    let body = if change.is_text() {
+        view! {
+            <details class="collapse collapse-arrow bg-base-200/50">
+                <summary class="collapse-title text-sm py-2 min-h-0">"Show old and new text"</summary>
+                <div class="collapse-content grid grid-cols-1 md:grid-cols-2 gap-3 text-sm">
+                    <div>
+                        <div class="text-xs font-semibold text-error mb-1">"Before"</div>
+                        <p class="whitespace-pre-wrap leading-relaxed text-base-content/80">
+                            {change.old_value.unwrap_or_else(|| "–".to_string())}
+                        </p>
+                    </div>
+                    <div>
+                        <div class="text-xs font-semibold text-success mb-1">"After"</div>
+                        <p class="whitespace-pre-wrap leading-relaxed text-base-content/80">
+                            {change.new_value.unwrap_or_else(|| "–".to_string())}
+                        </p>
+                    </div>
+                </div>
+            </details>
+        }
+        .into_any()
+    } else {
+        let (removed, added) = change.line_diff();
+        view! {
+            <ul class="flex flex-col gap-0.5 font-mono text-xs">
+                {removed.into_iter().map(|line| view! {
+                    <li class="px-2 py-0.5 rounded bg-error/10 text-error">"− " {line}</li>
+                }).collect_view()}
+                {added.into_iter().map(|line| view! {
+                    <li class="px-2 py-0.5 rounded bg-success/10 text-success">"+ " {line}</li>
+                }).collect_view()}
+            </ul>
+        }
+        .into_any()
+    };
+
+    view! {
+        <div class="flex flex-col gap-1.5 pb-3 border-b border-base-300/50 last:border-0 last:pb-0">
+            <h3 class="font-semibold text-sm">{label}</h3>
+            {body}
+        </div>
+    }
+}
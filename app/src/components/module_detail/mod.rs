@@ -5,12 +5,14 @@ pub mod contact_section;
 pub mod description_section;
 pub mod exams_section;
 pub mod header;
+pub mod history_section;
 pub mod metadata_section;
 pub mod modal;
 pub mod modal_header;
 pub mod page_header;
 pub mod programs_section;
 pub mod requirements_section;
+pub mod versions_section;
 pub mod view;
 pub mod workload_section;
 
@@ -21,11 +23,13 @@ pub use contact_section::*;
 pub use description_section::*;
 pub use exams_section::*;
 pub use header::*;
+pub use history_section::*;
 pub use metadata_section::*;
 pub use modal::*;
 pub use modal_header::*;
 pub use page_header::*;
 pub use programs_section::*;
 pub use requirements_section::*;
+pub use versions_section::*;
 pub use view::*;
 pub use workload_section::*;
@@ -1,13 +1,21 @@
+use crate::models::PrerequisiteInfo;
 use leptos::prelude::*;
+use leptos_router::components::A;
 
 #[component]
 pub fn RequirementsSection(
+    id: i32,
+    version: i32,
     requirements: Option<String>,
     registration: Option<String>,
+    prerequisites: Vec<PrerequisiteInfo>,
+    required_by: Vec<PrerequisiteInfo>,
 ) -> impl IntoView {
-    let has_requirements = requirements.is_some();
+    let has_requirements = requirements.is_some() || !prerequisites.is_empty();
     let has_registration = registration.is_some();
-    let has_content = has_requirements || has_registration;
+    let has_required_by = !required_by.is_empty();
+    let has_relations = !prerequisites.is_empty() || has_required_by;
+    let has_content = has_requirements || has_registration || has_required_by;
 
     if !has_content {
         return view! { <></> }.into_any();
@@ -16,13 +24,28 @@ pub fn RequirementsSection(
     view! {
         <div class="card bg-base-100 shadow-sm">
             <div class="card-body p-5 gap-5">
-                {if let Some(reqs) = requirements {
+                {if has_requirements {
                     view! {
                         <div>
                             <h3 class="font-semibold text-base mb-3 text-primary">Requirements</h3>
-                            <div class="prose prose-sm max-w-none text-base-content/80">
-                                <p class="whitespace-pre-wrap leading-relaxed">{reqs}</p>
-                            </div>
+                            {if !prerequisites.is_empty() {
+                                view! {
+                                    <div class="mb-3">
+                                        <PrerequisiteLinks modules=prerequisites />
+                                    </div>
+                                }.into_any()
+                            } else {
+                                view! { <></> }.into_any()
+                            }}
+                            {if let Some(reqs) = requirements {
+                                view! {
+                                    <div class="prose prose-sm max-w-none text-base-content/80">
+                                        <p class="whitespace-pre-wrap leading-relaxed">{reqs}</p>
+                                    </div>
+                                }.into_any()
+                            } else {
+                                view! { <></> }.into_any()
+                            }}
                         </div>
                     }.into_any()
                 } else {
@@ -47,8 +70,67 @@ pub fn RequirementsSection(
                 } else {
                     view! { <></> }.into_any()
                 }}
+
+                {if has_required_by && (has_requirements || has_registration) {
+                    view! { <div class="divider my-1 opacity-50"></div> }.into_any()
+                } else {
+                    view! { <></> }.into_any()
+                }}
+
+                {if has_required_by {
+                    view! {
+                        <div>
+                            <h3 class="font-semibold text-base mb-3 text-primary">Required By</h3>
+                            <PrerequisiteLinks modules=required_by />
+                        </div>
+                    }.into_any()
+                } else {
+                    view! { <></> }.into_any()
+                }}
+
+                {if has_relations {
+                    view! {
+                        <div class="flex justify-end">
+                            <A href=format!("/module/{}/{}/graph", id, version) attr:class="btn btn-sm btn-soft btn-primary">
+                                "Prerequisite graph"
+                            </A>
+                        </div>
+                    }.into_any()
+                } else {
+                    view! { <></> }.into_any()
+                }}
             </div>
         </div>
     }
     .into_any()
 }
+
+/// List of linked modules with their prerequisite kind
+#[component]
+fn PrerequisiteLinks(modules: Vec<PrerequisiteInfo>) -> impl IntoView {
+    view! {
+        <ul class="flex flex-col gap-1.5">
+            {modules.into_iter().map(|module| {
+                let href = format!("/module/{}/{}", module.id, module.version);
+                let is_mandatory = module.kind == "mandatory";
+                view! {
+                    <li class="flex items-center gap-2 text-sm">
+                        <div class=if is_mandatory {
+                            "badge badge-soft badge-warning badge-sm"
+                        } else {
+                            "badge badge-soft badge-info badge-sm"
+                        }>
+                            {if is_mandatory { "Mandatory" } else { "Recommended" }}
+                        </div>
+                        <A href=href attr:class="link link-hover font-medium">
+                            {module.title}
+                        </A>
+                        <span class="text-xs text-base-content/40 whitespace-nowrap">
+                            "#" {module.id} " v" {module.version}
+                        </span>
+                    </li>
+                }
+            }).collect_view()}
+        </ul>
+    }
+}
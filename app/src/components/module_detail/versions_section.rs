@@ -0,0 +1,102 @@
+use crate::components::module_detail::FieldChangeRow;
+use crate::models::{ModuleLineage, ModuleVersionInfo, VersionDiff};
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+/// Dropdown linking to the other versions of the module number
+#[component]
+pub fn VersionSwitcher(lineage: ModuleLineage, version: i32) -> impl IntoView {
+    if lineage.versions.len() <= 1 {
+        return view! { <></> }.into_any();
+    }
+
+    let id = lineage.id;
+
+    view! {
+        <div class="dropdown dropdown-end">
+            <div tabindex="0" role="button" class="btn btn-sm btn-soft gap-1">
+                "Version " {version}
+                <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M19 9l-7 7-7-7" />
+                </svg>
+            </div>
+            <ul tabindex="0" class="dropdown-content menu bg-base-100 rounded-box shadow-lg z-10 w-72 p-2">
+                {lineage.versions.into_iter().rev().map(|info| {
+                    let href = format!("/module/{}/{}", id, info.version);
+                    let active = info.version == version;
+                    view! {
+                        <li>
+                            <A href=href attr:class=if active { "menu-active" } else { "" }>
+                                <VersionLabel info=info />
+                            </A>
+                        </li>
+                    }
+                }).collect_view()}
+            </ul>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn VersionLabel(info: ModuleVersionInfo) -> impl IntoView {
+    let validity = match (info.valid_since, info.valid_until) {
+        (Some(since), Some(until)) => format!("{} – {}", since, until),
+        (Some(since), None) => format!("since {}", since),
+        (None, Some(until)) => format!("until {}", until),
+        (None, None) => "Validity unknown".to_string(),
+    };
+
+    view! {
+        <div class="flex flex-col gap-0.5">
+            <div class="flex items-center gap-2">
+                <span class="font-medium">"v" {info.version}</span>
+                {info.newest_valid.then(|| view! {
+                    <div class="badge badge-soft badge-success badge-xs">"Current"</div>
+                })}
+                {info.expired.then(|| view! {
+                    <div class="badge badge-soft badge-xs">"Expired"</div>
+                })}
+            </div>
+            <span class="text-xs text-base-content/50">{validity}</span>
+        </div>
+    }
+}
+
+/// Changes between each pair of consecutive versions, newest first
+#[component]
+pub fn VersionsSection(lineage: ModuleLineage) -> impl IntoView {
+    if lineage.diffs.is_empty() {
+        return view! {
+            <div class="alert">
+                <span>"This is the only known version of the module."</span>
+            </div>
+        }
+        .into_any();
+    }
+
+    view! {
+        <div class="flex flex-col gap-4">
+            {lineage.diffs.into_iter().rev().map(|diff| view! { <VersionDiffCard diff=diff /> }).collect_view()}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn VersionDiffCard(diff: VersionDiff) -> impl IntoView {
+    view! {
+        <div class="card bg-base-100 shadow-sm">
+            <div class="card-body p-5 gap-3">
+                <h2 class="card-title text-lg text-primary">
+                    "v" {diff.from_version} " → v" {diff.to_version}
+                </h2>
+                {if diff.changes.is_empty() {
+                    view! { <p class="text-sm text-base-content/60">"No differences in the compared fields."</p> }.into_any()
+                } else {
+                    diff.changes.into_iter().map(|change| view! { <FieldChangeRow change=change /> }).collect_view().into_any()
+                }}
+            </div>
+        </div>
+    }
+}
@@ -1,12 +1,26 @@
+use crate::api::get_module_lineage;
 use crate::components::module_detail::*;
 use crate::models::ModuleDetail;
 use leptos::prelude::*;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DetailTab {
+    Details,
+    History,
+    Versions,
+}
+
 #[component]
 pub fn ModuleDetailView(
     module: ModuleDetail,
     on_close: Option<Callback<()>>,
 ) -> impl IntoView {
+    // History and versions are only offered on the full page, the modal stays a quick preview
+    let is_page = on_close.is_none();
+    let active_tab = RwSignal::new(DetailTab::Details);
+    let (id, version) = (module.id, module.version);
+    let lineage = is_page.then(|| Resource::new(move || id, get_module_lineage));
+
     view! {
         <div class="flex flex-col gap-4">
             // Conditional header based on context (modal vs page)
@@ -35,51 +49,129 @@ pub fn ModuleDetailView(
                 }.into_any()
             }}
 
-            // Metadata section
-            <MetadataSection
-                faculty=module.faculty.clone()
-                institute=module.institute.clone()
-                fachgebiet=module.fachgebiet.clone()
-                responsible_person=module.responsible_person.clone()
-                examination_board=module.examination_board.clone()
-                valid_since=module.valid_since.clone()
-                valid_until=module.valid_until.clone()
-            />
-
-            // Description section
-            <DescriptionSection
-                learning_result=module.learning_result.clone()
-                content=module.content.clone()
-                teaching_information=module.teaching_information.clone()
-            />
-
-            // Components section
-            <ComponentsSection components=module.components.clone() />
-
-            // Exams section
-            <ExamsSection exams=module.exams.clone() />
-
-            // Workload section
-            <WorkloadSection workload=module.workload.clone() />
-
-            // Requirements section
-            <RequirementsSection
-                requirements=module.requirements.clone()
-                registration=module.registration.clone()
-            />
-
-            // Administrative section
-            <AdministrativeSection
-                max_attendees=module.max_attendees
-                duration=module.duration.clone()
-                additional_info=module.additional_info.clone()
-            />
-
-            // Study programs section
-            <ProgramsSection programs=module.study_programs.clone() />
-
-            // Contact section
-            <ContactSection contact=module.contact.clone() />
+            {module.discontinued_in_run_id.map(|run_id| view! {
+                <div role="alert" class="alert alert-warning alert-soft">
+                    <svg xmlns="http://www.w3.org/2000/svg" class="stroke-current shrink-0 h-5 w-5" fill="none" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 9v2m0 4h.01m-6.938 4h13.856c1.54 0 2.502-1.667 1.732-3L13.732 4c-.77-1.333-2.694-1.333-3.464 0L3.34 16c-.77 1.333.192 3 1.732 3z" />
+                    </svg>
+                    <span>
+                        "This module is discontinued. It has been missing from the MOSES export since scraping run #"
+                        {run_id} "."
+                    </span>
+                </div>
+            })}
+
+            {is_page.then(|| view! {
+                <div class="flex items-center justify-between gap-3">
+                    <div role="tablist" class="tabs tabs-border">
+                        <DetailTabButton tab=DetailTab::Details label="Details" active=active_tab />
+                        <DetailTabButton tab=DetailTab::History label="History" active=active_tab />
+                        <DetailTabButton tab=DetailTab::Versions label="Versions" active=active_tab />
+                    </div>
+                    {lineage.map(|lineage| view! {
+                        <Suspense fallback=|| ()>
+                            {move || Suspend::new(async move {
+                                lineage.await.ok().map(|lineage| view! { <VersionSwitcher lineage=lineage version=version /> })
+                            })}
+                        </Suspense>
+                    })}
+                </div>
+            })}
+
+            <Show when=move || active_tab.get() == DetailTab::History>
+                <HistorySection id=id version=version />
+            </Show>
+
+            {lineage.map(|lineage| view! {
+                <Show when=move || active_tab.get() == DetailTab::Versions>
+                    <Suspense fallback=move || {
+                        view! {
+                            <div class="flex items-center justify-center py-12">
+                                <span class="loading loading-spinner loading-lg"></span>
+                            </div>
+                        }
+                    }>
+                        {move || Suspend::new(async move {
+                            match lineage.await {
+                                Ok(lineage) => view! { <VersionsSection lineage=lineage /> }.into_any(),
+                                Err(e) => {
+                                    view! {
+                                        <div class="alert alert-error">
+                                            <span>"Error loading module versions: " {e.to_string()}</span>
+                                        </div>
+                                    }
+                                    .into_any()
+                                }
+                            }
+                        })}
+                    </Suspense>
+                </Show>
+            })}
+
+            <div class="flex flex-col gap-4" class:hidden=move || active_tab.get() != DetailTab::Details>
+                // Metadata section
+                <MetadataSection
+                    faculty=module.faculty.clone()
+                    institute=module.institute.clone()
+                    fachgebiet=module.fachgebiet.clone()
+                    responsible_person=module.responsible_person.clone()
+                    examination_board=module.examination_board.clone()
+                    valid_since=module.valid_since.clone()
+                    valid_until=module.valid_until.clone()
+                />
+
+                // Description section
+                <DescriptionSection
+                    learning_result=module.learning_result.clone()
+                    content=module.content.clone()
+                    teaching_information=module.teaching_information.clone()
+                />
+
+                // Components section
+                <ComponentsSection components=module.components.clone() />
+
+                // Exams section
+                <ExamsSection exams=module.exams.clone() />
+
+                // Workload section
+                <WorkloadSection workload=module.workload.clone() />
+
+                // Requirements section
+                <RequirementsSection
+                    id=module.id
+                    version=module.version
+                    requirements=module.requirements.clone()
+                    registration=module.registration.clone()
+                    prerequisites=module.prerequisites.clone()
+                    required_by=module.required_by.clone()
+                />
+
+                // Administrative section
+                <AdministrativeSection
+                    max_attendees=module.max_attendees
+                    duration=module.duration.clone()
+                    additional_info=module.additional_info.clone()
+                />
+
+                // Study programs section
+                <ProgramsSection programs=module.study_programs.clone() />
+
+                // Contact section
+                <ContactSection contact=module.contact.clone() />
+            </div>
         </div>
     }
 }
+
+#[component]
+fn DetailTabButton(tab: DetailTab, label: &'static str, active: RwSignal<DetailTab>) -> impl IntoView {
+    view! {
+        <button
+            role="tab"
+            class=move || if active.get() == tab { "tab tab-active" } else { "tab" }
+            on:click=move |_| active.set(tab)
+        >
+            {label}
+        </button>
+    }
+}
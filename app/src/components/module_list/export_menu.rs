@@ -0,0 +1,54 @@
+use crate::export::{export_url, toggle_export_column, use_export_columns, ExportColumn, ExportFormat};
+use crate::handbook::{handbook_url, HandbookFormat, HandbookSource};
+use crate::models::SearchFilters;
+use leptos::prelude::*;
+
+/// Download all modules matching the filters, not just the loaded pages
+#[component]
+pub fn ExportMenu(#[prop(into)] filters: Signal<SearchFilters>) -> impl IntoView {
+    let (columns, set_columns) = use_export_columns();
+
+    view! {
+        <div class="dropdown dropdown-end">
+            <div tabindex="0" role="button" class="btn btn-sm btn-soft">"Export"</div>
+            <div tabindex="0" class="dropdown-content bg-base-100 rounded-box z-20 w-64 p-3 shadow-lg flex flex-col gap-2">
+                <span class="text-xs font-semibold text-base-content/60">"Columns"</span>
+                {ExportColumn::ALL.into_iter().map(|column| view! {
+                    <label class="flex items-center gap-2 text-sm cursor-pointer">
+                        <input
+                            type="checkbox"
+                            class="checkbox checkbox-sm"
+                            prop:checked=move || columns.get().columns.contains(&column)
+                            on:change=move |_| set_columns.set(toggle_export_column(&columns.get_untracked(), column))
+                        />
+                        {column.label()}
+                    </label>
+                }).collect_view()}
+                <div class="join w-full mt-1">
+                    {ExportFormat::ALL.into_iter().map(|format| view! {
+                        <a
+                            class="btn btn-sm btn-soft btn-primary join-item flex-1"
+                            class:btn-disabled=move || columns.get().columns.is_empty()
+                            href=move || export_url(format, &filters.get(), &columns.get().columns)
+                            download=format!("modules.{}", format.extension())
+                        >
+                            {format.label()}
+                        </a>
+                    }).collect_view()}
+                </div>
+                <span class="text-xs font-semibold text-base-content/60 mt-2">"Module handbook"</span>
+                <div class="join w-full">
+                    {[(HandbookFormat::Html, "Print view"), (HandbookFormat::Pdf, "PDF")].into_iter().map(|(format, label)| view! {
+                        <a
+                            class="btn btn-sm btn-soft join-item flex-1"
+                            href=move || handbook_url(format, None, &HandbookSource::Filters(filters.get()))
+                            target="_blank"
+                        >
+                            {label}
+                        </a>
+                    }).collect_view()}
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -1,5 +1,9 @@
+pub mod export_menu;
 pub mod module_card;
 pub mod module_list;
+pub mod sort_select;
 
+pub use export_menu::*;
 pub use module_card::*;
 pub use module_list::*;
+pub use sort_select::*;
@@ -1,12 +1,18 @@
+use crate::collections::ModuleCollections;
 use crate::components::shared::ComponentTable;
 use crate::models::ModuleSummary;
 use leptos::prelude::*;
+use leptos_router::components::A;
 
 #[component]
 pub fn ModuleCard(
     module: ModuleSummary,
     is_starred: Signal<bool>,
     on_toggle_star: impl Fn() + 'static,
+    collections: Signal<ModuleCollections>,
+    on_toggle_collection: impl Fn(u32) + 'static + Copy + Send,
+    is_compared: Signal<bool>,
+    on_toggle_compare: impl Fn() + 'static,
     on_click: impl Fn() + 'static,
 ) -> impl IntoView {
     let _module_id = module.id;
@@ -65,8 +71,66 @@ pub fn ModuleCard(
                         <span class="text-sm font-normal text-base-content/40 whitespace-nowrap">
                             "Module " {module.id} " v" {module.version}
                         </span>
+                        {module.discontinued_in_run_id.map(|run_id| view! {
+                            <div class="tooltip tooltip-bottom" data-tip=format!("Missing from the export since run #{}", run_id)>
+                                <div class="badge badge-soft badge-error badge-sm">"Discontinued"</div>
+                            </div>
+                        })}
                     </div>
                     <div class="flex items-center gap-2 shrink-0">
+                        <div class="tooltip tooltip-left" attr:data-tip=move || if is_compared.get() { "Remove from comparison" } else { "Add to comparison" }>
+                            <button
+                                class=move || if is_compared.get() { "btn btn-ghost btn-sm btn-circle text-primary" } else { "btn btn-ghost btn-sm btn-circle" }
+                                on:click=move |ev| {
+                                    ev.stop_propagation();
+                                    on_toggle_compare();
+                                }
+                            >
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 17V7m0 10a2 2 0 01-2 2H5a2 2 0 01-2-2V7a2 2 0 012-2h2a2 2 0 012 2m0 10a2 2 0 002 2h2a2 2 0 002-2M9 7a2 2 0 012-2h2a2 2 0 012 2m0 10V7m0 10a2 2 0 002 2h2a2 2 0 002-2V7a2 2 0 00-2-2h-2a2 2 0 00-2 2" />
+                                </svg>
+                            </button>
+                        </div>
+                        // Collection picker - prevent click propagation
+                        <div class="dropdown dropdown-end" on:click=|ev| ev.stop_propagation()>
+                            <div
+                                tabindex="0"
+                                role="button"
+                                class=move || {
+                                    let in_any = collections.with(|c| {
+                                        c.collections.iter().any(|c| c.contains(_module_id, _module_version))
+                                    });
+                                    if in_any { "btn btn-ghost btn-sm btn-circle text-primary" } else { "btn btn-ghost btn-sm btn-circle" }
+                                }
+                                title="Add to collection"
+                            >
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M5 5a2 2 0 012-2h10a2 2 0 012 2v16l-7-3.5L5 21V5z" />
+                                </svg>
+                            </div>
+                            <ul tabindex="0" class="dropdown-content menu bg-base-100 rounded-box z-10 w-56 p-2 shadow-lg">
+                                {move || collections.get().collections.into_iter().map(|collection| {
+                                    let collection_id = collection.id;
+                                    let checked = collection.contains(_module_id, _module_version);
+                                    view! {
+                                        <li>
+                                            <label class="flex items-center gap-2">
+                                                <input
+                                                    type="checkbox"
+                                                    class="checkbox checkbox-sm"
+                                                    prop:checked=checked
+                                                    on:change=move |_| on_toggle_collection(collection_id)
+                                                />
+                                                <span class="truncate">{collection.name}</span>
+                                            </label>
+                                        </li>
+                                    }
+                                }).collect_view()}
+                                <li>
+                                    <A href="/collections" attr:class="text-base-content/60">"Manage collections"</A>
+                                </li>
+                            </ul>
+                        </div>
                         <div class="tooltip tooltip-left" attr:data-tip=move || if is_starred.get() { "Unstar module" } else { "Star module" }>
                             <button
                                 class="btn btn-ghost btn-sm btn-circle"
@@ -1,3 +1,5 @@
+use crate::collections::{toggle_in_collection, ModuleCollections};
+use crate::compare::{is_compared, toggle_compare, CompareModules};
 use crate::components::module_list::ModuleCard;
 use crate::models::ModuleSummary;
 use crate::starred::{is_starred, toggle_starred, StarredModules};
@@ -8,6 +10,10 @@ pub fn ModuleList(
     modules: RwSignal<Vec<ModuleSummary>>,
     starred: Signal<StarredModules>,
     set_starred: WriteSignal<StarredModules>,
+    collections: Signal<ModuleCollections>,
+    set_collections: WriteSignal<ModuleCollections>,
+    compare: Signal<CompareModules>,
+    set_compare: WriteSignal<CompareModules>,
     on_module_click: impl Fn(i32, i32) + 'static + Copy + Send,
     #[prop(optional)] is_loading: Option<Signal<bool>>,
 ) -> impl IntoView {
@@ -44,6 +50,19 @@ pub fn ModuleList(
                                         *s = toggle_starred(s, module_id, module_version);
                                     });
                                 };
+                                let on_toggle_collection = move |collection_id: u32| {
+                                    set_collections.update(|c| {
+                                        *c = toggle_in_collection(c, collection_id, module_id, module_version);
+                                    });
+                                };
+                                let is_compared_signal = Signal::derive(move || {
+                                    is_compared(&compare.get(), module_id, module_version)
+                                });
+                                let on_toggle_compare = move || {
+                                    set_compare.update(|c| {
+                                        *c = toggle_compare(c, module_id, module_version);
+                                    });
+                                };
 
                                 view! {
                                     <div class="animate-fade-in">
@@ -51,6 +70,10 @@ pub fn ModuleList(
                                             module=module
                                             is_starred=is_starred_signal
                                             on_toggle_star=on_toggle
+                                            collections=collections
+                                            on_toggle_collection=on_toggle_collection
+                                            is_compared=is_compared_signal
+                                            on_toggle_compare=on_toggle_compare
                                             on_click=move || on_module_click(module_id, module_version)
                                         />
                                     </div>
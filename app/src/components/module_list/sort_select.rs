@@ -0,0 +1,37 @@
+use crate::models::{SearchFilters, SortOrder};
+use leptos::prelude::*;
+
+/// Result order, saved together with the other search filters
+#[component]
+pub fn SortSelect(filters: RwSignal<SearchFilters>) -> impl IntoView {
+    view! {
+        {move || {
+            let current = filters.get();
+            let searching = current.search_query.as_deref().is_some_and(|q| !q.trim().is_empty());
+            // Without a query relevance is the same as sorting by title
+            let selected = match current.sort {
+                SortOrder::Relevance if !searching => SortOrder::Title,
+                sort => sort,
+            };
+            view! {
+                <select
+                    class="select select-sm w-auto"
+                    aria-label="Sort modules"
+                    on:change=move |ev| {
+                        if let Some(sort) = SortOrder::from_key(&event_target_value(&ev)) {
+                            filters.update(|f| f.sort = sort);
+                        }
+                    }
+                >
+                    {SortOrder::ALL
+                        .into_iter()
+                        .filter(|sort| searching || *sort != SortOrder::Relevance)
+                        .map(|sort| view! {
+                            <option value=sort.key() selected={sort == selected}>{sort.label()}</option>
+                        })
+                        .collect_view()}
+                </select>
+            }
+        }}
+    }
+}
@@ -0,0 +1,253 @@
+use crate::models::{ModuleSummary, SearchFilters};
+use crate::planner::percent_encode;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[cfg(target_arch = "wasm32")]
+use codee::string::JsonSerdeCodec;
+#[cfg(target_arch = "wasm32")]
+use leptos_use::storage::use_local_storage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Csv, ExportFormat::Json, ExportFormat::Xlsx];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.extension() == extension)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Xlsx => "Excel",
+        }
+    }
+}
+
+/// A column of the search result export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportColumn {
+    Id,
+    Version,
+    Title,
+    Credits,
+    Languages,
+    Faculty,
+    SemesterRotations,
+    Components,
+    ExamCategories,
+    StudyPrograms,
+    DiscontinuedInRunId,
+}
+
+impl ExportColumn {
+    pub const ALL: [ExportColumn; 11] = [
+        ExportColumn::Id,
+        ExportColumn::Version,
+        ExportColumn::Title,
+        ExportColumn::Credits,
+        ExportColumn::Languages,
+        ExportColumn::Faculty,
+        ExportColumn::SemesterRotations,
+        ExportColumn::Components,
+        ExportColumn::ExamCategories,
+        ExportColumn::StudyPrograms,
+        ExportColumn::DiscontinuedInRunId,
+    ];
+
+    /// Name in the `columns` query parameter and the JSON export
+    pub fn key(self) -> &'static str {
+        match self {
+            ExportColumn::Id => "id",
+            ExportColumn::Version => "version",
+            ExportColumn::Title => "title",
+            ExportColumn::Credits => "credits",
+            ExportColumn::Languages => "languages",
+            ExportColumn::Faculty => "faculty",
+            ExportColumn::SemesterRotations => "semester_rotations",
+            ExportColumn::Components => "components",
+            ExportColumn::ExamCategories => "exam_categories",
+            ExportColumn::StudyPrograms => "study_programs",
+            ExportColumn::DiscontinuedInRunId => "discontinued_in_run_id",
+        }
+    }
+
+    /// Header in the CSV and Excel export
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportColumn::Id => "Module number",
+            ExportColumn::Version => "Version",
+            ExportColumn::Title => "Title",
+            ExportColumn::Credits => "Credits",
+            ExportColumn::Languages => "Languages",
+            ExportColumn::Faculty => "Faculty",
+            ExportColumn::SemesterRotations => "Semesters",
+            ExportColumn::Components => "Components",
+            ExportColumn::ExamCategories => "Exam categories",
+            ExportColumn::StudyPrograms => "Study programs",
+            ExportColumn::DiscontinuedInRunId => "Discontinued in run",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|column| column.key() == key)
+    }
+
+    /// Value of this column for a module, lists stay arrays
+    pub fn value(self, module: &ModuleSummary) -> Value {
+        match self {
+            ExportColumn::Id => json!(module.id),
+            ExportColumn::Version => json!(module.version),
+            ExportColumn::Title => json!(module.title),
+            ExportColumn::Credits => json!(module.credits),
+            ExportColumn::Languages => json!(module.languages),
+            ExportColumn::Faculty => json!(module.faculty_name),
+            ExportColumn::SemesterRotations => json!(module.semester_rotations),
+            ExportColumn::Components => json!(module
+                .components
+                .iter()
+                .map(|c| format!("{} {} ({}, {} SWS)", c.component_type, c.number, c.rotation, c.sws))
+                .collect::<Vec<_>>()),
+            ExportColumn::ExamCategories => json!(module.exam_categories),
+            ExportColumn::StudyPrograms => json!(module.study_programs),
+            ExportColumn::DiscontinuedInRunId => json!(module.discontinued_in_run_id),
+        }
+    }
+}
+
+/// Columns picked for the export, in the order of `ExportColumn::ALL`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportColumns {
+    pub columns: Vec<ExportColumn>,
+}
+
+impl Default for ExportColumns {
+    fn default() -> Self {
+        ExportColumns {
+            columns: vec![
+                ExportColumn::Id,
+                ExportColumn::Version,
+                ExportColumn::Title,
+                ExportColumn::Credits,
+                ExportColumn::Languages,
+                ExportColumn::SemesterRotations,
+                ExportColumn::ExamCategories,
+                ExportColumn::StudyPrograms,
+            ],
+        }
+    }
+}
+
+pub fn use_export_columns() -> (Signal<ExportColumns>, WriteSignal<ExportColumns>) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let (columns, set_columns, _remove) = use_local_storage::<ExportColumns, JsonSerdeCodec>("export_columns");
+        (columns, set_columns)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // SSR fallback - just return dummy signals
+        let columns = RwSignal::new(ExportColumns::default());
+        (columns.read_only().into(), columns.write_only())
+    }
+}
+
+pub fn toggle_export_column(columns: &ExportColumns, column: ExportColumn) -> ExportColumns {
+    let selected: Vec<ExportColumn> = ExportColumn::ALL
+        .into_iter()
+        .filter(|c| columns.columns.contains(c) != (*c == column))
+        .collect();
+    ExportColumns { columns: selected }
+}
+
+/// Download link for all modules matching the filters, e.g.
+/// `/api/modules/export.csv?columns=id,title&filters={...}`
+pub fn export_url(format: ExportFormat, filters: &SearchFilters, columns: &[ExportColumn]) -> String {
+    let columns: Vec<&str> = columns.iter().map(|c| c.key()).collect();
+    format!(
+        "/api/modules/export.{}?columns={}&filters={}",
+        format.extension(),
+        columns.join(","),
+        percent_encode(&serde_json::to_string(filters).unwrap_or_default())
+    )
+}
+
+/// Parse the `columns` query parameter, unknown names are an error
+pub fn parse_export_columns(param: &str) -> Result<Vec<ExportColumn>, String> {
+    let columns = param
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(|key| ExportColumn::from_key(key).ok_or_else(|| format!("Unknown column: {}", key)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if columns.is_empty() {
+        return Err("Pick at least one column".to_string());
+    }
+    Ok(columns)
+}
+
+/// Flatten a value into a single spreadsheet cell
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(cell_text).collect::<Vec<_>>().join("; "),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_export_columns() {
+        assert_eq!(
+            parse_export_columns("id, title,,credits"),
+            Ok(vec![ExportColumn::Id, ExportColumn::Title, ExportColumn::Credits])
+        );
+        assert_eq!(parse_export_columns("id,grade"), Err("Unknown column: grade".to_string()));
+        assert_eq!(parse_export_columns(" , "), Err("Pick at least one column".to_string()));
+    }
+
+    #[test]
+    fn test_toggle_export_column() {
+        let columns = ExportColumns {
+            columns: vec![ExportColumn::Title, ExportColumn::Id],
+        };
+
+        // Added columns follow the order of `ExportColumn::ALL`
+        let added = toggle_export_column(&columns, ExportColumn::Version);
+        assert_eq!(added.columns, vec![ExportColumn::Id, ExportColumn::Version, ExportColumn::Title]);
+
+        let removed = toggle_export_column(&added, ExportColumn::Id);
+        assert_eq!(removed.columns, vec![ExportColumn::Version, ExportColumn::Title]);
+    }
+
+    #[test]
+    fn test_cell_text() {
+        assert_eq!(cell_text(&Value::Null), "");
+        assert_eq!(cell_text(&json!("Analysis I")), "Analysis I");
+        assert_eq!(cell_text(&json!(6)), "6");
+        assert_eq!(cell_text(&json!(["Deutsch", "Englisch"])), "Deutsch; Englisch");
+        assert_eq!(cell_text(&json!([])), "");
+    }
+}
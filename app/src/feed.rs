@@ -0,0 +1,94 @@
+use crate::graph::escape_xml;
+use crate::models::{ChangeReport, ChangeReportEntry};
+use chrono::{DateTime, Utc};
+
+/// Number of recent completed runs whose changes are listed in a feed
+pub const FEED_RUNS: i64 = 5;
+
+/// An added or changed module version of one run, as listed in a feed
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    pub run_id: i32,
+    pub updated: DateTime<Utc>,
+    pub entry: ChangeReportEntry,
+}
+
+/// Collect the added and changed module versions of the given reports
+///
+/// Reports without a completion time are skipped, `keep` decides which module
+/// versions are listed. Entries are ordered newest run first.
+pub fn collect_feed_entries(
+    reports: Vec<ChangeReport>,
+    keep: impl Fn(&ChangeReportEntry) -> bool,
+) -> Vec<FeedEntry> {
+    let mut entries = Vec::new();
+
+    for report in reports {
+        let Some(updated) = report.completed_at else {
+            continue;
+        };
+        for entry in report.entries {
+            if entry.change_type != "removed" && keep(&entry) {
+                entries.push(FeedEntry {
+                    run_id: report.run_id,
+                    updated,
+                    entry,
+                });
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| b.updated.cmp(&a.updated));
+    entries
+}
+
+/// Render an Atom feed
+///
+/// `base_url` is the scheme and host the feed is served from, `path` the
+/// path of the feed itself.
+pub fn render_atom_feed(title: &str, base_url: &str, path: &str, entries: &[FeedEntry]) -> String {
+    let updated = entries
+        .iter()
+        .map(|e| e.updated)
+        .max()
+        .unwrap_or_else(Utc::now);
+
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+    xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+    xml.push_str(&format!(
+        r#"<title>{title}</title><id>{base}{path}</id><link rel="self" href="{base}{path}"/><link href="{base}/"/><updated>{updated}</updated><author><name>TU Berlin Module Search</name></author>"#,
+        title = escape_xml(title),
+        base = escape_xml(base_url),
+        path = escape_xml(path),
+        updated = updated.to_rfc3339(),
+    ));
+
+    for feed_entry in entries {
+        let entry = &feed_entry.entry;
+        let prefix = if entry.change_type == "added" { "New" } else { "Changed" };
+        let link = format!("{}/module/{}/{}", base_url, entry.module_id, entry.module_version);
+        xml.push_str(&format!(
+            r#"<entry><title>{prefix}: {title} (#{id} v{version})</title><id>{link}#run-{run_id}</id><link href="{link}"/><updated>{updated}</updated><summary>{summary}</summary></entry>"#,
+            title = escape_xml(&entry.title),
+            id = entry.module_id,
+            version = entry.module_version,
+            link = escape_xml(&link),
+            run_id = feed_entry.run_id,
+            updated = feed_entry.updated.to_rfc3339(),
+            summary = escape_xml(&entry_summary(entry)),
+        ));
+    }
+
+    xml.push_str("</feed>");
+    xml
+}
+
+fn entry_summary(entry: &ChangeReportEntry) -> String {
+    if entry.change_type == "added" {
+        return "New module version in MOSES.".to_string();
+    }
+
+    let changes: Vec<String> = entry.changes.iter().map(|change| change.summary()).collect();
+
+    format!("Changed: {}.", changes.join(", "))
+}
@@ -0,0 +1,293 @@
+use crate::models::{GraphEdge, GraphNode, ModuleGraph};
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+/// Maximum number of prerequisite (or unlock) steps followed from the root module
+pub const MAX_GRAPH_DEPTH: i32 = 6;
+
+const NODE_WIDTH: f64 = 220.0;
+const NODE_HEIGHT: f64 = 52.0;
+const LAYER_GAP: f64 = 72.0;
+const ROW_GAP: f64 = 20.0;
+const MARGIN: f64 = 20.0;
+const MAX_LABEL_CHARS: usize = 30;
+
+/// A prerequisite relation stored for the latest snapshot of a module
+#[derive(Debug, Clone)]
+pub struct PrerequisiteEdge {
+    pub module_id: i32,
+    pub module_version: i32,
+    pub required_id: i32,
+    pub required_version: i32,
+    pub kind: String,
+}
+
+/// Title and credits used to label a module version in the graph
+#[derive(Debug, Clone)]
+pub struct NodeLabel {
+    pub title: String,
+    pub credits: i32,
+}
+
+/// Collect the transitive prerequisites and unlocked modules of a module and lay them out
+///
+/// Nodes are keyed by module number. Prerequisites are placed in negative layers
+/// left of the root module, unlocked modules in positive layers to its right, so
+/// that every edge points from left to right (longest path layering).
+pub fn build_module_graph(
+    root_id: i32,
+    root_version: i32,
+    relations: &[PrerequisiteEdge],
+    labels: &HashMap<(i32, i32), NodeLabel>,
+) -> ModuleGraph {
+    let mut by_module: HashMap<(i32, i32), Vec<&PrerequisiteEdge>> = HashMap::new();
+    let mut by_required: HashMap<i32, Vec<&PrerequisiteEdge>> = HashMap::new();
+    for relation in relations {
+        by_module
+            .entry((relation.module_id, relation.module_version))
+            .or_default()
+            .push(relation);
+        by_required.entry(relation.required_id).or_default().push(relation);
+    }
+
+    let mut versions: HashMap<i32, i32> = HashMap::from([(root_id, root_version)]);
+    let mut layers: HashMap<i32, i32> = HashMap::from([(root_id, 0)]);
+    let mut edges: HashMap<(i32, i32), String> = HashMap::new();
+
+    // Walk prerequisites backwards from the root module
+    let mut queue = VecDeque::from([(root_id, 0)]);
+    while let Some((number, depth)) = queue.pop_front() {
+        if depth >= MAX_GRAPH_DEPTH {
+            continue;
+        }
+        let key = (number, versions[&number]);
+        for relation in by_module.get(&key).into_iter().flatten() {
+            if relation.required_id == root_id {
+                continue;
+            }
+            add_edge(&mut edges, relation.required_id, number, &relation.kind);
+            if let Entry::Vacant(entry) = layers.entry(relation.required_id) {
+                entry.insert(-(depth + 1));
+                versions.insert(relation.required_id, relation.required_version);
+                queue.push_back((relation.required_id, depth + 1));
+            }
+        }
+    }
+
+    // Walk unlocked modules forwards from the root module
+    let mut queue = VecDeque::from([(root_id, 0)]);
+    while let Some((number, depth)) = queue.pop_front() {
+        if depth >= MAX_GRAPH_DEPTH {
+            continue;
+        }
+        for relation in by_required.get(&number).into_iter().flatten() {
+            match layers.get(&relation.module_id) {
+                // A prerequisite that also depends on this module is a cycle in the source data
+                Some(&layer) if layer <= 0 => continue,
+                Some(_) => {
+                    let version = versions.entry(relation.module_id).or_default();
+                    *version = (*version).max(relation.module_version);
+                }
+                None => {
+                    versions.insert(relation.module_id, relation.module_version);
+                    layers.insert(relation.module_id, depth + 1);
+                    queue.push_back((relation.module_id, depth + 1));
+                }
+            }
+            add_edge(&mut edges, number, relation.module_id, &relation.kind);
+        }
+    }
+
+    // Push nodes apart until every edge points to a later layer
+    for _ in 0..layers.len() {
+        let mut changed = false;
+        for &(from, to) in edges.keys() {
+            let (from_layer, to_layer) = (layers[&from], layers[&to]);
+            if from_layer < to_layer {
+                continue;
+            }
+            if to_layer <= 0 {
+                let layer = (to_layer - 1).max(-MAX_GRAPH_DEPTH);
+                if layer < from_layer {
+                    layers.insert(from, layer);
+                    changed = true;
+                }
+            } else {
+                let layer = (from_layer + 1).min(MAX_GRAPH_DEPTH);
+                if layer > to_layer {
+                    layers.insert(to, layer);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let label_of = |number: i32| -> NodeLabel {
+        labels
+            .get(&(number, versions[&number]))
+            .cloned()
+            .unwrap_or_else(|| NodeLabel {
+                title: format!("Module {}", number),
+                credits: 0,
+            })
+    };
+
+    let min_layer = layers.values().copied().min().unwrap_or(0);
+    let max_layer = layers.values().copied().max().unwrap_or(0);
+    let mut columns: Vec<Vec<i32>> = vec![Vec::new(); (max_layer - min_layer + 1) as usize];
+    for (&number, &layer) in &layers {
+        columns[(layer - min_layer) as usize].push(number);
+    }
+
+    let tallest = columns.iter().map(Vec::len).max().unwrap_or(1);
+    let width = MARGIN * 2.0 + columns.len() as f64 * (NODE_WIDTH + LAYER_GAP) - LAYER_GAP;
+    let height = MARGIN * 2.0 + tallest as f64 * (NODE_HEIGHT + ROW_GAP) - ROW_GAP;
+
+    // Order each column by the average position of its predecessors to reduce crossings
+    let mut positions: HashMap<i32, (f64, f64)> = HashMap::new();
+    for (index, column) in columns.iter().enumerate() {
+        let mut keyed: Vec<(f64, String, i32)> = column
+            .iter()
+            .map(|&number| {
+                let predecessors: Vec<f64> = edges
+                    .keys()
+                    .filter(|(_, to)| *to == number)
+                    .filter_map(|(from, _)| positions.get(from).map(|p| p.1))
+                    .collect();
+                let barycenter = if predecessors.is_empty() {
+                    f64::MAX
+                } else {
+                    predecessors.iter().sum::<f64>() / predecessors.len() as f64
+                };
+                (barycenter, label_of(number).title, number)
+            })
+            .collect();
+        keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+        let column_height = keyed.len() as f64 * (NODE_HEIGHT + ROW_GAP) - ROW_GAP;
+        let top = (height - column_height) / 2.0;
+        let x = MARGIN + index as f64 * (NODE_WIDTH + LAYER_GAP);
+        for (row, (_, _, number)) in keyed.into_iter().enumerate() {
+            positions.insert(number, (x, top + row as f64 * (NODE_HEIGHT + ROW_GAP)));
+        }
+    }
+
+    let mut nodes: Vec<GraphNode> = positions
+        .iter()
+        .map(|(&number, &(x, y))| {
+            let label = label_of(number);
+            GraphNode {
+                id: number,
+                version: versions[&number],
+                title: label.title,
+                credits: label.credits,
+                layer: layers[&number],
+                x,
+                y,
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.layer.cmp(&b.layer).then(a.y.total_cmp(&b.y)));
+
+    let mut edges: Vec<GraphEdge> = edges
+        .into_iter()
+        .map(|((from, to), kind)| GraphEdge { from, to, kind })
+        .collect();
+    edges.sort_by(|a, b| a.from.cmp(&b.from).then(a.to.cmp(&b.to)));
+
+    ModuleGraph {
+        root_id,
+        root_version,
+        nodes,
+        edges,
+        width,
+        height,
+    }
+}
+
+/// Render a laid out graph as a standalone SVG document
+///
+/// Nodes link to their module detail page; mandatory prerequisites are drawn
+/// as solid lines, recommended ones dashed.
+pub fn render_graph_svg(graph: &ModuleGraph) -> String {
+    let nodes: HashMap<i32, &GraphNode> = graph.nodes.iter().map(|n| (n.id, n)).collect();
+
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {h}" width="{w}" height="{h}" font-family="ui-sans-serif, system-ui, sans-serif" font-size="12">"##,
+        w = graph.width,
+        h = graph.height
+    );
+    svg.push_str(
+        r##"<defs><marker id="arrow-mandatory" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="7" markerHeight="7" orient="auto"><path d="M 0 0 L 10 5 L 0 10 z" fill="#d97706"/></marker><marker id="arrow-recommended" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="7" markerHeight="7" orient="auto"><path d="M 0 0 L 10 5 L 0 10 z" fill="#0ea5e9"/></marker></defs>"##,
+    );
+
+    for edge in &graph.edges {
+        let (Some(from), Some(to)) = (nodes.get(&edge.from), nodes.get(&edge.to)) else {
+            continue;
+        };
+        let (x1, y1) = (from.x + NODE_WIDTH, from.y + NODE_HEIGHT / 2.0);
+        let (x2, y2) = (to.x, to.y + NODE_HEIGHT / 2.0);
+        let mid = (x1 + x2) / 2.0;
+        let (color, dash) = if edge.kind == "mandatory" {
+            ("#d97706", "")
+        } else {
+            ("#0ea5e9", r#" stroke-dasharray="6 4""#)
+        };
+        svg.push_str(&format!(
+            r#"<path d="M {x1} {y1} C {mid} {y1}, {mid} {y2}, {x2} {y2}" fill="none" stroke="{color}" stroke-width="1.5"{dash} marker-end="url(#arrow-{kind})"/>"#,
+            kind = if edge.kind == "mandatory" { "mandatory" } else { "recommended" },
+        ));
+    }
+
+    for node in &graph.nodes {
+        let is_root = node.id == graph.root_id;
+        let (fill, stroke, stroke_width) = if is_root {
+            ("#eef2ff", "#4f46e5", 2)
+        } else {
+            ("#ffffff", "#d1d5db", 1)
+        };
+        svg.push_str(&format!(
+            r##"<a href="/module/{id}/{version}"><title>{title}</title><rect x="{x}" y="{y}" width="{NODE_WIDTH}" height="{NODE_HEIGHT}" rx="8" fill="{fill}" stroke="{stroke}" stroke-width="{stroke_width}"/><text x="{tx}" y="{ty1}" font-weight="600" fill="#1f2937">{label}</text><text x="{tx}" y="{ty2}" fill="#6b7280">#{id} v{version} · {credits} CP</text></a>"##,
+            id = node.id,
+            version = node.version,
+            title = escape_xml(&node.title),
+            x = node.x,
+            y = node.y,
+            tx = node.x + 12.0,
+            ty1 = node.y + 21.0,
+            ty2 = node.y + 39.0,
+            label = escape_xml(&truncate_label(&node.title)),
+            credits = node.credits,
+        ));
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn add_edge(edges: &mut HashMap<(i32, i32), String>, from: i32, to: i32, kind: &str) {
+    let entry = edges.entry((from, to)).or_insert_with(|| kind.to_string());
+    // A mandatory relation outweighs a recommended one
+    if kind == "mandatory" {
+        *entry = kind.to_string();
+    }
+}
+
+fn truncate_label(title: &str) -> String {
+    if title.chars().count() <= MAX_LABEL_CHARS {
+        return title.to_string();
+    }
+    let mut label: String = title.chars().take(MAX_LABEL_CHARS - 1).collect();
+    label.push('…');
+    label
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,767 @@
+//! Printable module handbooks
+//!
+//! Both the HTML and the PDF output are rendered from the same list of blocks
+//! per module, so the two documents always show the same information.
+
+use crate::graph::escape_xml;
+use crate::models::{ModuleDetail, SearchFilters};
+use crate::planner::percent_encode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandbookFormat {
+    Html,
+    Pdf,
+}
+
+impl HandbookFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            HandbookFormat::Html => "html",
+            HandbookFormat::Pdf => "pdf",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "html" => Some(HandbookFormat::Html),
+            "pdf" => Some(HandbookFormat::Pdf),
+            _ => None,
+        }
+    }
+}
+
+/// Modules a handbook is generated for
+#[derive(Debug, Clone, PartialEq)]
+pub enum HandbookSource {
+    /// All modules matching search filters, e.g. the module keys of a collection
+    Filters(SearchFilters),
+    /// All modules listed in a catalog area of a StuPO
+    Area(i32),
+}
+
+/// Link to a generated handbook, e.g. `/api/handbook.pdf?area=12`
+pub fn handbook_url(format: HandbookFormat, title: Option<&str>, source: &HandbookSource) -> String {
+    let mut url = format!("/api/handbook.{}?", format.extension());
+    match source {
+        HandbookSource::Filters(filters) => {
+            url.push_str("filters=");
+            url.push_str(&percent_encode(&serde_json::to_string(filters).unwrap_or_default()));
+        }
+        HandbookSource::Area(area_id) => url.push_str(&format!("area={}", area_id)),
+    }
+    if let Some(title) = title {
+        url.push_str("&title=");
+        url.push_str(&percent_encode(title));
+    }
+    url
+}
+
+pub struct Handbook {
+    pub title: String,
+    /// Date shown on the first page, e.g. "2025-01-20"
+    pub generated_on: String,
+    pub modules: Vec<ModuleDetail>,
+}
+
+/// A piece of a module page
+enum Block {
+    /// Label and value pairs
+    Facts(Vec<(&'static str, String)>),
+    /// Free text written by the module authors
+    Text { heading: String, text: String },
+    Table {
+        heading: String,
+        /// Text shown between the heading and the table
+        note: Option<String>,
+        columns: Vec<&'static str>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+fn semester_range(module: &ModuleDetail) -> Option<String> {
+    match (&module.valid_since, &module.valid_until) {
+        (None, None) => None,
+        (since, until) => Some(format!(
+            "{} – {}",
+            since.as_deref().unwrap_or("?"),
+            until.as_deref().unwrap_or("open")
+        )),
+    }
+}
+
+fn module_blocks(module: &ModuleDetail) -> Vec<Block> {
+    let mut blocks = Vec::new();
+
+    let mut facts = vec![
+        ("Module", format!("{} · v{}", module.id, module.version)),
+        ("Credits", format!("{} CP", module.credits)),
+        ("Languages", module.languages.join(", ")),
+    ];
+    if let Some(range) = semester_range(module) {
+        facts.push(("Valid", range));
+    }
+    facts.extend([
+        ("Faculty", module.faculty.clone()),
+        ("Institute", module.institute.clone()),
+        ("Fachgebiet", module.fachgebiet.clone()),
+        ("Responsible Person", module.responsible_person.clone()),
+        ("Examination Board", module.examination_board.clone()),
+    ]);
+    if let Some(contact) = &module.contact {
+        facts.extend(
+            [
+                ("Secretariat", &contact.secretariat),
+                ("Contact Person", &contact.contact_person),
+                ("Email", &contact.email),
+                ("Website", &contact.website),
+            ]
+            .into_iter()
+            .filter_map(|(label, value)| value.clone().map(|value| (label, value))),
+        );
+    }
+    if let Some(duration) = &module.duration {
+        facts.push(("Duration", duration.clone()));
+    }
+    if let Some(max_attendees) = module.max_attendees {
+        facts.push(("Max Attendees", max_attendees.to_string()));
+    }
+    facts.push(("MOSES", module.moses_link.clone()));
+    facts.retain(|(_, value)| !value.trim().is_empty());
+    blocks.push(Block::Facts(facts));
+
+    for (heading, text) in [
+        ("Learning Objectives", &module.learning_result),
+        ("Module Content", &module.content),
+        ("Teaching Information", &module.teaching_information),
+        ("Requirements", &module.requirements),
+        ("Registration", &module.registration),
+        ("Additional Information", &module.additional_info),
+    ] {
+        if let Some(text) = text.as_ref().filter(|text| !text.trim().is_empty()) {
+            blocks.push(Block::Text {
+                heading: heading.to_string(),
+                text: text.trim().to_string(),
+            });
+        }
+    }
+
+    if !module.components.is_empty() {
+        blocks.push(Block::Table {
+            heading: "Components".to_string(),
+            note: None,
+            columns: vec!["Type", "Name", "Number", "Rotation", "SWS", "Language"],
+            rows: module
+                .components
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.component_type.clone(),
+                        c.name.clone().unwrap_or_default(),
+                        c.number.clone(),
+                        c.rotation.clone(),
+                        c.sws.to_string(),
+                        c.language.clone(),
+                    ]
+                })
+                .collect(),
+        });
+    }
+
+    for exam in &module.exams {
+        let graded = if exam.graded { "Graded" } else { "Ungraded" };
+        blocks.push(Block::Table {
+            heading: format!("Exam: {} ({})", exam.exam_type, graded),
+            note: exam.description.clone().filter(|d| !d.trim().is_empty()),
+            columns: vec!["Name", "Category", "Points", "Scope"],
+            rows: exam
+                .components
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.name.clone(),
+                        c.category.clone(),
+                        c.points.to_string(),
+                        c.scope.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+        });
+    }
+
+    if !module.workload.is_empty() {
+        let total: f64 = module.workload.iter().map(|w| w.hours).sum();
+        let mut rows: Vec<Vec<String>> = module
+            .workload
+            .iter()
+            .map(|w| vec![w.description.clone(), format!("{:.0}", w.hours)])
+            .collect();
+        rows.push(vec!["Total".to_string(), format!("{:.0}", total)]);
+        blocks.push(Block::Table {
+            heading: "Workload".to_string(),
+            note: None,
+            columns: vec!["Description", "Hours"],
+            rows,
+        });
+    }
+
+    if !module.prerequisites.is_empty() {
+        blocks.push(Block::Table {
+            heading: "Prerequisites".to_string(),
+            note: None,
+            columns: vec!["Module", "Kind"],
+            rows: module
+                .prerequisites
+                .iter()
+                .map(|p| {
+                    let kind = if p.kind == "mandatory" { "Mandatory" } else { "Recommended" };
+                    vec![format!("{} ({} · v{})", p.title, p.id, p.version), kind.to_string()]
+                })
+                .collect(),
+        });
+    }
+
+    if !module.study_programs.is_empty() {
+        blocks.push(Block::Table {
+            heading: "Study Programs".to_string(),
+            note: None,
+            columns: vec!["Program", "StuPO", "From", "Until"],
+            rows: module
+                .study_programs
+                .iter()
+                .map(|p| {
+                    vec![
+                        p.program_name.clone(),
+                        p.stupo_name.clone(),
+                        p.first_usage.clone(),
+                        p.last_usage.clone(),
+                    ]
+                })
+                .collect(),
+        });
+    }
+
+    blocks
+}
+
+fn anchor(module: &ModuleDetail) -> String {
+    format!("module-{}-{}", module.id, module.version)
+}
+
+const HTML_STYLE: &str = r#"
+body { font-family: Helvetica, Arial, sans-serif; font-size: 10.5pt; color: #111; max-width: 180mm; margin: 0 auto; padding: 12mm 0; line-height: 1.4; }
+h1 { font-size: 22pt; margin: 0 0 4pt; }
+h2 { font-size: 15pt; margin: 0 0 2pt; }
+h3 { font-size: 11pt; margin: 12pt 0 4pt; }
+a { color: inherit; }
+.subtitle { color: #555; margin: 0 0 18pt; }
+.toc ol { padding-left: 0; list-style: none; }
+.toc li { display: flex; gap: 8pt; border-bottom: 1px dotted #bbb; padding: 2pt 0; }
+.toc li a { flex: 1; text-decoration: none; }
+.module { break-before: page; padding-top: 8pt; }
+.facts { display: grid; grid-template-columns: 38mm 1fr; gap: 1pt 8pt; margin: 8pt 0; }
+.facts dt { font-weight: bold; }
+.facts dd { margin: 0; overflow-wrap: anywhere; }
+.text { white-space: pre-wrap; margin: 0 0 4pt; }
+table { border-collapse: collapse; width: 100%; font-size: 9.5pt; }
+th, td { text-align: left; vertical-align: top; padding: 2pt 4pt; border-bottom: 1px solid #ccc; }
+th { border-bottom: 1.5px solid #333; }
+.print { position: fixed; top: 8mm; right: 8mm; }
+@page { size: A4; margin: 18mm 16mm; }
+@media print { .print { display: none; } body { padding: 0; max-width: none; } }
+"#;
+
+/// Standalone HTML document, meant to be printed from the browser
+pub fn render_handbook_html(handbook: &Handbook) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_xml(&handbook.title)));
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", HTML_STYLE));
+    html.push_str("<button class=\"print\" onclick=\"window.print()\">Print</button>\n");
+    html.push_str(&format!("<h1>{}</h1>\n", escape_xml(&handbook.title)));
+    html.push_str(&format!(
+        "<p class=\"subtitle\">{} modules · {} CP · generated on {}</p>\n",
+        handbook.modules.len(),
+        handbook.modules.iter().map(|m| m.credits).sum::<i32>(),
+        escape_xml(&handbook.generated_on)
+    ));
+
+    html.push_str("<nav class=\"toc\">\n<h2>Contents</h2>\n<ol>\n");
+    for module in &handbook.modules {
+        html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a><span>{} CP</span></li>\n",
+            anchor(module),
+            escape_xml(&module.title),
+            module.credits
+        ));
+    }
+    html.push_str("</ol>\n</nav>\n");
+
+    for module in &handbook.modules {
+        html.push_str(&format!("<section class=\"module\" id=\"{}\">\n", anchor(module)));
+        html.push_str(&format!("<h2>{}</h2>\n", escape_xml(&module.title)));
+        for block in module_blocks(module) {
+            match block {
+                Block::Facts(facts) => {
+                    html.push_str("<dl class=\"facts\">\n");
+                    for (label, value) in facts {
+                        html.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", label, escape_xml(&value)));
+                    }
+                    html.push_str("</dl>\n");
+                }
+                Block::Text { heading, text } => {
+                    html.push_str(&format!(
+                        "<h3>{}</h3>\n<p class=\"text\">{}</p>\n",
+                        escape_xml(&heading),
+                        escape_xml(&text)
+                    ));
+                }
+                Block::Table { heading, note, columns, rows } => {
+                    html.push_str(&format!("<h3>{}</h3>\n", escape_xml(&heading)));
+                    if let Some(note) = note {
+                        html.push_str(&format!("<p class=\"text\">{}</p>\n", escape_xml(&note)));
+                    }
+                    if rows.is_empty() {
+                        continue;
+                    }
+                    html.push_str("<table>\n<thead><tr>");
+                    for column in columns {
+                        html.push_str(&format!("<th>{}</th>", column));
+                    }
+                    html.push_str("</tr></thead>\n<tbody>\n");
+                    for row in rows {
+                        html.push_str("<tr>");
+                        for cell in row {
+                            html.push_str(&format!("<td>{}</td>", escape_xml(&cell)));
+                        }
+                        html.push_str("</tr>\n");
+                    }
+                    html.push_str("</tbody>\n</table>\n");
+                }
+            }
+        }
+        html.push_str("</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(feature = "ssr")]
+pub use pdf::render_handbook_pdf;
+
+#[cfg(feature = "ssr")]
+mod pdf {
+    use super::{module_blocks, Block, Handbook};
+    use printpdf::{BuiltinFont, Line, Mm, PdfDocument, Point};
+
+    const PAGE_WIDTH: f32 = 210.0;
+    const PAGE_HEIGHT: f32 = 297.0;
+    const MARGIN: f32 = 18.0;
+    const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+    /// Lowest baseline of the body text, leaves room for the footer
+    const BOTTOM: f32 = PAGE_HEIGHT - MARGIN - 6.0;
+    const LABEL_WIDTH: f32 = 40.0;
+    const CELL_PADDING: f32 = 2.0;
+
+    const BODY_SIZE: f32 = 9.5;
+    const SMALL_SIZE: f32 = 8.5;
+    const HEADING_SIZE: f32 = 11.0;
+    const TITLE_SIZE: f32 = 15.0;
+
+    /// Advance widths of Helvetica for ASCII 32..=126, in 1/1000 em
+    const HELVETICA_WIDTHS: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556, 556, 556,
+        556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667, 611, 778, 722, 278,
+        500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469,
+        556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500,
+        278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+    ];
+
+    /// Stand-ins for common characters the built-in fonts can't show
+    const REPLACEMENTS: [(char, &str); 9] = [
+        ('≥', ">="),
+        ('≤', "<="),
+        ('≠', "!="),
+        ('≈', "~"),
+        ('→', "->"),
+        ('←', "<-"),
+        ('⇒', "=>"),
+        ('−', "-"),
+        ('‐', "-"),
+    ];
+
+    /// Whether Helvetica with WinAnsiEncoding has a glyph for the character
+    fn is_win_ansi(c: char) -> bool {
+        matches!(c, ' '..='~' | '\u{a0}'..='\u{ff}') || "€‚ƒ„…†‡ˆ‰Š‹ŒŽ‘’“”•–—˜™š›œžŸ".contains(c)
+    }
+
+    /// Text as it is written to the PDF
+    ///
+    /// The built-in fonts only cover WinAnsi, other characters would be
+    /// dropped silently. Those without a stand-in become `?` so the gap stays
+    /// visible.
+    fn pdf_text(text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            if is_win_ansi(c) {
+                out.push(c);
+            } else if let Some((_, replacement)) = REPLACEMENTS.iter().find(|(from, _)| *from == c) {
+                out.push_str(replacement);
+            } else if c.is_whitespace() || c.is_control() {
+                out.push(' ');
+            } else {
+                out.push('?');
+            }
+        }
+        out
+    }
+
+    /// Advance width of a WinAnsi character in 1/1000 em
+    fn char_width(c: char) -> u32 {
+        match c {
+            ' '..='~' => HELVETICA_WIDTHS[(c as u32 - 32) as usize] as u32,
+            'Ä' | 'À' | 'Á' | 'Â' | 'Ã' | 'Å' | 'Ë' | 'È' | 'É' | 'Ê' | 'Š' => 667,
+            'Ö' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ç' => 778,
+            'Ü' | 'Ù' | 'Ú' | 'Û' | 'Ñ' => 722,
+            'ì' | 'í' | 'î' | 'ï' | 'Ì' | 'Í' | 'Î' | 'Ï' | '·' | '\u{a0}' => 278,
+            'ß' => 611,
+            '—' | '…' | '‰' | '™' | 'Œ' => 1000,
+            '‘' | '’' | '‚' => 222,
+            '“' | '”' | '„' | '´' | '¨' => 333,
+            '•' => 350,
+            '°' => 400,
+            '×' | '±' | '¬' | '÷' => 584,
+            _ => 556,
+        }
+    }
+
+    /// Approximate width of a text in mm as written by `pdf_text`, bold text runs about 6% wider
+    fn text_width(text: &str, size: f32, bold: bool) -> f32 {
+        let units: u32 = pdf_text(text).chars().map(char_width).sum();
+        let width = units as f32 / 1000.0 * size * 0.3528;
+        if bold {
+            width * 1.06
+        } else {
+            width
+        }
+    }
+
+    fn line_height(size: f32) -> f32 {
+        size * 0.3528 * 1.4
+    }
+
+    /// Break a text into lines that fit the width, keeping its line breaks
+    fn wrap(text: &str, width: f32, size: f32, bold: bool) -> Vec<String> {
+        let mut lines = Vec::new();
+        for paragraph in text.lines() {
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+                if text_width(&candidate, size, bold) <= width {
+                    line = candidate;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                // Split words that are longer than a whole line, e.g. URLs
+                for c in word.chars() {
+                    line.push(c);
+                    if text_width(&line, size, bold) > width {
+                        let last = line.pop().unwrap_or_default();
+                        lines.push(std::mem::take(&mut line));
+                        line.push(last);
+                    }
+                }
+            }
+            lines.push(line);
+        }
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines
+    }
+
+    enum Op {
+        Text { x: f32, y: f32, size: f32, bold: bool, text: String },
+        Rule { y: f32, width: f32 },
+    }
+
+    /// Pages of positioned text, `y` grows downwards from the top edge
+    struct Layout {
+        pages: Vec<Vec<Op>>,
+        y: f32,
+    }
+
+    impl Layout {
+        fn new() -> Self {
+            Layout {
+                pages: vec![Vec::new()],
+                y: MARGIN,
+            }
+        }
+
+        fn page_break(&mut self) {
+            self.pages.push(Vec::new());
+            self.y = MARGIN;
+        }
+
+        /// Start a new page unless `height` still fits on the current one
+        fn keep(&mut self, height: f32) {
+            if self.y + height > BOTTOM && self.y > MARGIN {
+                self.page_break();
+            }
+        }
+
+        fn space(&mut self, height: f32) {
+            self.y += height;
+        }
+
+        fn text(&mut self, x: f32, size: f32, bold: bool, text: &str) {
+            self.pages.last_mut().expect("layout has a page").push(Op::Text {
+                x,
+                y: self.y + size * 0.3528,
+                size,
+                bold,
+                text: pdf_text(text),
+            });
+        }
+
+        fn rule(&mut self, width: f32) {
+            self.pages.last_mut().expect("layout has a page").push(Op::Rule { y: self.y, width });
+        }
+
+        /// Wrapped paragraph, breaking pages between lines
+        fn paragraph(&mut self, x: f32, width: f32, size: f32, bold: bool, text: &str) {
+            for line in wrap(text, width, size, bold) {
+                self.keep(line_height(size));
+                self.text(x, size, bold, &line);
+                self.y += line_height(size);
+            }
+        }
+
+        fn heading(&mut self, text: &str) {
+            // Keep headings together with the first lines below them
+            self.keep(line_height(HEADING_SIZE) + 3.0 * line_height(BODY_SIZE));
+            self.space(2.0);
+            self.paragraph(MARGIN, CONTENT_WIDTH, HEADING_SIZE, true, text);
+            self.space(0.5);
+        }
+
+        fn facts(&mut self, facts: &[(&'static str, String)]) {
+            for (label, value) in facts {
+                let lines = wrap(value, CONTENT_WIDTH - LABEL_WIDTH, BODY_SIZE, false);
+                self.keep(line_height(BODY_SIZE));
+                self.text(MARGIN, BODY_SIZE, true, label);
+                for line in lines {
+                    self.keep(line_height(BODY_SIZE));
+                    self.text(MARGIN + LABEL_WIDTH, BODY_SIZE, false, &line);
+                    self.y += line_height(BODY_SIZE);
+                }
+            }
+        }
+
+        fn table(&mut self, columns: &[&'static str], rows: &[Vec<String>]) {
+            // Share the width by the longest text per column, within limits
+            let weights: Vec<f32> = columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    let longest = rows
+                        .iter()
+                        .map(|row| row[i].chars().count())
+                        .chain([column.chars().count()])
+                        .max()
+                        .unwrap_or(1);
+                    longest.clamp(4, 40) as f32
+                })
+                .collect();
+            let total: f32 = weights.iter().sum();
+            let widths: Vec<f32> = weights.iter().map(|w| w / total * CONTENT_WIDTH).collect();
+
+            let header: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+            self.table_row(&widths, &header, true);
+            self.rule(CONTENT_WIDTH);
+            self.space(0.8);
+            for row in rows {
+                self.table_row(&widths, row, false);
+            }
+        }
+
+        fn table_row(&mut self, widths: &[f32], cells: &[String], bold: bool) {
+            let wrapped: Vec<Vec<String>> = cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| wrap(cell, width - CELL_PADDING, SMALL_SIZE, bold))
+                .collect();
+            let lines = wrapped.iter().map(Vec::len).max().unwrap_or(1);
+            self.keep(lines as f32 * line_height(SMALL_SIZE));
+
+            let mut x = MARGIN;
+            for (cell, width) in wrapped.iter().zip(widths) {
+                for (i, line) in cell.iter().enumerate() {
+                    self.pages.last_mut().expect("layout has a page").push(Op::Text {
+                        x,
+                        y: self.y + i as f32 * line_height(SMALL_SIZE) + SMALL_SIZE * 0.3528,
+                        size: SMALL_SIZE,
+                        bold,
+                        text: line.clone(),
+                    });
+                }
+                x += width;
+            }
+            self.y += lines as f32 * line_height(SMALL_SIZE);
+        }
+    }
+
+    /// Table of contents with the page of each module
+    fn contents(handbook: &Handbook, module_pages: &[usize], first_content_page: usize) -> Layout {
+        let mut layout = Layout::new();
+        layout.paragraph(MARGIN, CONTENT_WIDTH, 20.0, true, &handbook.title);
+        layout.space(1.0);
+        layout.paragraph(
+            MARGIN,
+            CONTENT_WIDTH,
+            BODY_SIZE,
+            false,
+            &format!(
+                "{} modules · {} CP · generated on {}",
+                handbook.modules.len(),
+                handbook.modules.iter().map(|m| m.credits).sum::<i32>(),
+                handbook.generated_on
+            ),
+        );
+        layout.space(6.0);
+        layout.heading("Contents");
+
+        for (module, page) in handbook.modules.iter().zip(module_pages) {
+            let number = (first_content_page + page + 1).to_string();
+            let lines = wrap(&module.title, CONTENT_WIDTH - 30.0, BODY_SIZE, false);
+            layout.keep(lines.len() as f32 * line_height(BODY_SIZE));
+            layout.text(
+                MARGIN + CONTENT_WIDTH - 20.0 - text_width(&format!("{} CP", module.credits), BODY_SIZE, false),
+                BODY_SIZE,
+                false,
+                &format!("{} CP", module.credits),
+            );
+            layout.text(
+                MARGIN + CONTENT_WIDTH - text_width(&number, BODY_SIZE, false),
+                BODY_SIZE,
+                false,
+                &number,
+            );
+            for line in lines {
+                layout.text(MARGIN, BODY_SIZE, false, &line);
+                layout.y += line_height(BODY_SIZE);
+            }
+        }
+        layout
+    }
+
+    /// Lay out all modules, each starting on a new page
+    fn module_pages(handbook: &Handbook) -> (Layout, Vec<usize>) {
+        let mut layout = Layout::new();
+        let mut starts = Vec::new();
+
+        for (i, module) in handbook.modules.iter().enumerate() {
+            if i > 0 {
+                layout.page_break();
+            }
+            starts.push(layout.pages.len() - 1);
+            layout.paragraph(MARGIN, CONTENT_WIDTH, TITLE_SIZE, true, &module.title);
+            layout.space(2.0);
+
+            for block in module_blocks(module) {
+                match block {
+                    Block::Facts(facts) => layout.facts(&facts),
+                    Block::Text { heading, text } => {
+                        layout.heading(&heading);
+                        layout.paragraph(MARGIN, CONTENT_WIDTH, BODY_SIZE, false, &text);
+                    }
+                    Block::Table { heading, note, columns, rows } => {
+                        layout.heading(&heading);
+                        if let Some(note) = note {
+                            layout.paragraph(MARGIN, CONTENT_WIDTH, BODY_SIZE, false, &note);
+                            layout.space(1.0);
+                        }
+                        if !rows.is_empty() {
+                            layout.table(&columns, &rows);
+                        }
+                    }
+                }
+            }
+        }
+        (layout, starts)
+    }
+
+    pub fn render_handbook_pdf(handbook: &Handbook) -> Result<Vec<u8>, printpdf::Error> {
+        let (body, starts) = module_pages(handbook);
+        // The length of the contents doesn't depend on the page numbers in it
+        let contents_length = contents(handbook, &starts, 0).pages.len();
+        let toc = contents(handbook, &starts, contents_length);
+        let pages: Vec<Vec<Op>> = toc.pages.into_iter().chain(body.pages).collect();
+        let page_count = pages.len();
+
+        let (doc, first_page, first_layer) =
+            PdfDocument::new(&handbook.title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+        let regular = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+        let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+
+        for (number, ops) in pages.into_iter().enumerate() {
+            let layer = if number == 0 {
+                doc.get_page(first_page).get_layer(first_layer)
+            } else {
+                let (page, layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+                doc.get_page(page).get_layer(layer)
+            };
+
+            for op in ops {
+                match op {
+                    Op::Text { x, y, size, bold: is_bold, text } => {
+                        let font = if is_bold { &bold } else { &regular };
+                        layer.use_text(text, size, Mm(x), Mm(PAGE_HEIGHT - y), font);
+                    }
+                    Op::Rule { y, width } => {
+                        layer.set_outline_thickness(0.5);
+                        layer.add_line(Line {
+                            points: vec![
+                                (Point::new(Mm(MARGIN), Mm(PAGE_HEIGHT - y)), false),
+                                (Point::new(Mm(MARGIN + width), Mm(PAGE_HEIGHT - y)), false),
+                            ],
+                            is_closed: false,
+                        });
+                    }
+                }
+            }
+
+            let footer = pdf_text(&format!("{} · {} / {}", handbook.title, number + 1, page_count));
+            layer.use_text(
+                footer.as_str(),
+                SMALL_SIZE,
+                Mm(MARGIN + CONTENT_WIDTH - text_width(&footer, SMALL_SIZE, false)),
+                Mm(MARGIN / 2.0),
+                &regular,
+            );
+        }
+
+        doc.save_to_bytes()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_pdf_text() {
+            assert_eq!(pdf_text("Prüfung – Übung (§ 3)"), "Prüfung – Übung (§ 3)");
+            assert_eq!(pdf_text("≥ 6 LP → Modul"), ">= 6 LP -> Modul");
+            assert_eq!(pdf_text("Σ λ\tx"), "? ? x");
+        }
+
+        #[test]
+        fn test_text_width_matches_written_text() {
+            assert_eq!(text_width("≥", BODY_SIZE, false), text_width(">=", BODY_SIZE, false));
+            assert!(text_width("Ü", BODY_SIZE, false) > text_width("u", BODY_SIZE, false));
+        }
+    }
+}
@@ -1,14 +1,26 @@
 pub mod api;
 pub mod app;
+pub mod collections;
+pub mod compare;
 pub mod components;
+pub mod export;
+pub mod feed;
+pub mod graph;
+pub mod handbook;
 pub mod models;
 pub mod pages;
+pub mod planner;
+pub mod query_syntax;
 pub mod starred;
+pub mod sync;
 pub mod scraper_types;
 
 #[cfg(feature = "ssr")]
 pub mod scraper_state;
 
+#[cfg(feature = "ssr")]
+pub mod notifications;
+
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub fn hydrate() {
@@ -34,6 +34,21 @@ async fn main() -> std::io::Result<()> {
         .expect("Failed to run database migrations");
     tracing::info!("Database migrations completed successfully");
 
+    // Send notification digests for runs completed while the server was down,
+    // e.g. by the CLI scraper
+    let mail_config = app::notifications::MailConfig::from_env().expect("Invalid email notification settings");
+    if let Some(mail_config) = mail_config {
+        let pool = pool.clone();
+        actix_web::rt::spawn(async move {
+            match app::notifications::send_pending_digests(&pool, &mail_config).await {
+                Ok(sent) => tracing::info!("Sent {} pending notification digests", sent),
+                Err(e) => tracing::error!("Failed to send pending notification digests: {}", e),
+            }
+        });
+    } else {
+        tracing::info!("SMTP_HOST not set, email notifications are disabled");
+    }
+
     // Initialize scraper state
     let scraper_state = app::scraper_state::ScraperState::new();
 
@@ -60,6 +75,65 @@ async fn main() -> std::io::Result<()> {
                 "/api/scraper/events",
                 web::get().to(app::api::scraper::scraper_events_sse),
             )
+            // Prerequisite graph exports
+            .route(
+                "/api/modules/{id}/{version}/graph.json",
+                web::get().to(app::api::module_graph::module_graph_json),
+            )
+            .route(
+                "/api/modules/{id}/{version}/graph.svg",
+                web::get().to(app::api::module_graph::module_graph_svg),
+            )
+            // Search result exports
+            .route(
+                "/api/modules/export.{format}",
+                web::get().to(app::api::module_export::export_modules),
+            )
+            // Printable module handbooks
+            .route(
+                "/api/handbook.{format}",
+                web::get().to(app::api::handbook::module_handbook),
+            )
+            // Run change report exports
+            .route(
+                "/api/runs/{id}/changes.json",
+                web::get().to(app::api::change_report::change_report_json),
+            )
+            .route(
+                "/api/runs/{id}/changes.md",
+                web::get().to(app::api::change_report::change_report_markdown),
+            )
+            // Notification subscription links from emails open a page, its buttons POST
+            .route(
+                "/notifications/confirm/{token}",
+                web::get().to(app::api::notifications::confirm_link),
+            )
+            .route(
+                "/notifications/confirm/{token}",
+                web::post().to(app::api::notifications::confirm_subscription),
+            )
+            .route(
+                "/notifications/unsubscribe/{token}",
+                web::get().to(app::api::notifications::unsubscribe_link),
+            )
+            .route(
+                "/notifications/unsubscribe/{token}",
+                web::post().to(app::api::notifications::unsubscribe),
+            )
+            // Atom feeds of new and changed modules
+            .route(
+                "/feeds/changes.atom",
+                web::get().to(app::api::feeds::changes_feed),
+            )
+            .route(
+                "/feeds/program/{id}.atom",
+                web::get().to(app::api::feeds::program_feed),
+            )
+            // Versioned public REST API and its OpenAPI document
+            .service(app::api::rest::v1_scope())
+            // GraphQL endpoint, GraphiQL on GET
+            .route("/graphql", web::post().to(app::api::graphql::graphql))
+            .route("/graphql", web::get().to(app::api::graphql::graphiql))
             .leptos_routes(routes, {
                 let leptos_options = leptos_options.clone();
                 move || {
@@ -83,6 +157,7 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(leptos_options.to_owned()))
             .app_data(web::Data::new(pool.clone()))
             .app_data(web::Data::new(scraper_state.clone()))
+            .app_data(web::Data::new(app::api::graphql::build_schema(pool.clone())))
         //.wrap(middleware::Compress::default())
     })
     .bind(&addr)?
@@ -0,0 +1,96 @@
+use super::module_history::FieldChangeInfo;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Counts of a run's change report, for listing reports
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChangeReportSummary {
+    pub run_id: i32,
+    pub previous_run_id: i32,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub added: i64,
+    pub removed: i64,
+    pub changed: i64,
+}
+
+/// New, removed and changed module versions of a run compared to the previous completed run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChangeReport {
+    pub run_id: i32,
+    pub previous_run_id: Option<i32>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub entries: Vec<ChangeReportEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChangeReportEntry {
+    pub module_id: i32,
+    pub module_version: i32,
+    pub title: String,
+    /// "added", "removed" or "changed"
+    pub change_type: String,
+    pub changes: Vec<FieldChangeInfo>,
+}
+
+impl ChangeReport {
+    pub fn entries_of<'a>(&'a self, change_type: &'a str) -> impl Iterator<Item = &'a ChangeReportEntry> {
+        self.entries.iter().filter(move |e| e.change_type == change_type)
+    }
+
+    /// Render the report as a Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# Change report for run {}\n\n", self.run_id);
+
+        match self.previous_run_id {
+            Some(previous) => md.push_str(&format!("Compared to run {}.\n", previous)),
+            None => md.push_str("No previous completed run to compare against.\n"),
+        }
+        if let Some(completed_at) = self.completed_at {
+            md.push_str(&format!("Completed {}.\n", completed_at.format("%Y-%m-%d %H:%M UTC")));
+        }
+
+        for (change_type, heading) in [
+            ("added", "New module versions"),
+            ("removed", "Removed modules"),
+            ("changed", "Changed modules"),
+        ] {
+            let entries: Vec<&ChangeReportEntry> = self.entries_of(change_type).collect();
+            md.push_str(&format!("\n## {} ({})\n\n", heading, entries.len()));
+            if entries.is_empty() {
+                md.push_str("None.\n");
+                continue;
+            }
+            for entry in entries {
+                md.push_str(&format!(
+                    "- **#{} v{}** {}\n",
+                    entry.module_id, entry.module_version, entry.title
+                ));
+                for change in &entry.changes {
+                    if change.is_text() {
+                        md.push_str(&format!("  - {}: text changed\n", change.label()));
+                        continue;
+                    }
+                    let (removed, added) = change.line_diff();
+                    md.push_str(&format!("  - {}:", change.label()));
+                    if removed.len() <= 1 && added.len() <= 1 {
+                        md.push_str(&format!(
+                            " {} → {}\n",
+                            removed.first().map(String::as_str).unwrap_or("–"),
+                            added.first().map(String::as_str).unwrap_or("–")
+                        ));
+                    } else {
+                        md.push('\n');
+                        for line in removed {
+                            md.push_str(&format!("    - − {}\n", line));
+                        }
+                        for line in added {
+                            md.push_str(&format!("    - + {}\n", line));
+                        }
+                    }
+                }
+            }
+        }
+
+        md
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// A StuPO that modules can be audited against
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StupoOption {
+    pub id: i32,
+    pub program_name: String,
+    pub name: String,
+}
+
+/// Result of checking a module set against the catalog areas of a StuPO
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DegreeAudit {
+    pub stupo_id: i32,
+    pub areas: Vec<AreaAudit>,
+    /// Modules listed in more than one area of the StuPO
+    pub shared_modules: Vec<AuditModule>,
+    /// Modules not listed in any area of the StuPO
+    pub outside_modules: Vec<AuditModule>,
+    pub total_credits: i32,
+}
+
+/// Credits earned in a single catalog area
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AreaAudit {
+    pub id: i32,
+    pub name: String,
+    pub required_credits: Option<i32>,
+    pub earned_credits: i32,
+    pub missing_credits: Option<i32>,
+    pub modules: Vec<AuditModule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditModule {
+    pub id: i32,
+    pub version: i32,
+    pub title: String,
+    pub credits: i32,
+    pub areas: Vec<String>,
+}
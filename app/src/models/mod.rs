@@ -1,5 +1,19 @@
+pub mod change_report;
+pub mod degree_audit;
 pub mod module_detail;
+pub mod module_graph;
+pub mod module_history;
+pub mod module_lineage;
 pub mod module_summary;
+pub mod shared_list;
+pub mod sync;
 
+pub use change_report::*;
+pub use degree_audit::*;
 pub use module_detail::*;
+pub use module_graph::*;
+pub use module_history::*;
+pub use module_lineage::*;
 pub use module_summary::*;
+pub use shared_list::*;
+pub use sync::*;
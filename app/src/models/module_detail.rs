@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 /// Complete module information for detail view
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
 pub struct ModuleDetail {
     // Basic info
     pub id: i32,
@@ -43,12 +44,20 @@ pub struct ModuleDetail {
     pub workload: Vec<WorkloadInfo>,
     pub study_programs: Vec<StudyProgramInfo>,
 
+    // Prerequisite relations
+    pub prerequisites: Vec<PrerequisiteInfo>,
+    pub required_by: Vec<PrerequisiteInfo>,
+
     // Moses link
     pub moses_link: String,
+
+    /// Run that first no longer listed this module version
+    pub discontinued_in_run_id: Option<i32>,
 }
 
 /// Contact information for a module
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema, async_graphql::SimpleObject))]
 pub struct ContactInfo {
     pub secretariat: Option<String>,
     pub contact_person: Option<String>,
@@ -60,6 +69,7 @@ pub struct ContactInfo {
 
 /// Exam information with components
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema, async_graphql::SimpleObject))]
 pub struct ExamInfo {
     pub id: i32,
     pub graded: bool,
@@ -70,6 +80,7 @@ pub struct ExamInfo {
 
 /// Individual exam component
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema, async_graphql::SimpleObject))]
 pub struct ExamComponentInfo {
     pub name: String,
     pub points: i32,
@@ -79,6 +90,7 @@ pub struct ExamComponentInfo {
 
 /// Workload distribution entry
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema, async_graphql::SimpleObject))]
 pub struct WorkloadInfo {
     pub description: String,
     pub hours: f64,
@@ -86,9 +98,20 @@ pub struct WorkloadInfo {
 
 /// Study program usage information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
 pub struct StudyProgramInfo {
     pub program_name: String,
     pub stupo_name: String,
     pub first_usage: String,
     pub last_usage: String,
 }
+
+/// A module linked as prerequisite (or dependent) of another module
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema, async_graphql::SimpleObject))]
+pub struct PrerequisiteInfo {
+    pub id: i32,
+    pub version: i32,
+    pub title: String,
+    pub kind: String,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Prerequisite graph around a module with server-computed layout
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModuleGraph {
+    pub root_id: i32,
+    pub root_version: i32,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// A module in the graph, positioned by its layer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GraphNode {
+    pub id: i32,
+    pub version: i32,
+    pub title: String,
+    pub credits: i32,
+    /// Negative for prerequisites, 0 for the root module, positive for unlocked modules
+    pub layer: i32,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Prerequisite relation, pointing from the required module to the module requiring it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GraphEdge {
+    pub from: i32,
+    pub to: i32,
+    pub kind: String,
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A scraping run in which a module first appeared or changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModuleHistoryEntry {
+    pub scraping_run_id: i32,
+    pub started_at: DateTime<Utc>,
+    /// The module was not part of any earlier run
+    pub first_seen: bool,
+    pub changes: Vec<FieldChangeInfo>,
+}
+
+/// Old and new value of a changed field, list fields are newline separated
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldChangeInfo {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl FieldChangeInfo {
+    pub fn label(&self) -> &str {
+        match self.field.as_str() {
+            "title" => "Title",
+            "credits" => "Credits",
+            "responsible_person" => "Responsible person",
+            "learning_result" => "Learning objectives",
+            "content" => "Module content",
+            "components" => "Components",
+            "exam_form" => "Exam form",
+            "exam_components" => "Exam components",
+            other => other,
+        }
+    }
+
+    /// Free text is shown old against new instead of line by line
+    pub fn is_text(&self) -> bool {
+        matches!(self.field.as_str(), "learning_result" | "content")
+    }
+
+    /// One-line description, e.g. "Credits: 6 → 9"
+    pub fn summary(&self) -> String {
+        let single_line = |value: &Option<String>| value.as_deref().is_none_or(|v| !v.contains('\n'));
+        if self.is_text() {
+            return format!("{} changed", self.label());
+        }
+        if single_line(&self.old_value) && single_line(&self.new_value) {
+            return format!(
+                "{}: {} → {}",
+                self.label(),
+                self.old_value.as_deref().unwrap_or("–"),
+                self.new_value.as_deref().unwrap_or("–")
+            );
+        }
+        let (removed, added) = self.line_diff();
+        format!("{}: {} removed, {} added", self.label(), removed.len(), added.len())
+    }
+
+    /// Lines only in the old value and lines only in the new value
+    pub fn line_diff(&self) -> (Vec<String>, Vec<String>) {
+        let lines = |value: &Option<String>| -> Vec<String> {
+            value
+                .as_deref()
+                .map(|v| v.lines().map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+        let old_lines = lines(&self.old_value);
+        let new_lines = lines(&self.new_value);
+
+        let removed = old_lines.iter().filter(|l| !new_lines.contains(l)).cloned().collect();
+        let added = new_lines.iter().filter(|l| !old_lines.contains(l)).cloned().collect();
+        (removed, added)
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<db::FieldChange> for FieldChangeInfo {
+    fn from(change: db::FieldChange) -> Self {
+        FieldChangeInfo {
+            field: change.field,
+            old_value: change.old_value,
+            new_value: change.new_value,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use super::module_history::FieldChangeInfo;
+use serde::{Deserialize, Serialize};
+
+/// All versions of a module number with the changes between consecutive versions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModuleLineage {
+    pub id: i32,
+    pub versions: Vec<ModuleVersionInfo>, // oldest version first
+    pub diffs: Vec<VersionDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModuleVersionInfo {
+    pub version: i32,
+    pub title: String,
+    pub valid_since: Option<String>,
+    pub valid_until: Option<String>,
+    /// The validity window ended before the current semester
+    pub expired: bool,
+    /// The newest version that is still valid, or the newest one if all have expired
+    pub newest_valid: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionDiff {
+    pub from_version: i32,
+    pub to_version: i32,
+    pub changes: Vec<FieldChangeInfo>,
+}
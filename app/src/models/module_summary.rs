@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Module component info for display
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema, async_graphql::SimpleObject))]
 pub struct ComponentInfo {
     pub component_type: String,
     pub name: Option<String>,
@@ -13,6 +15,7 @@ pub struct ComponentInfo {
 
 /// Simplified module data for list view
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
 pub struct ModuleSummary {
     pub id: i32,
     pub version: i32,
@@ -25,6 +28,84 @@ pub struct ModuleSummary {
     pub study_program_ids: Vec<i32>,
     pub faculty_name: String,
     pub components: Vec<ComponentInfo>,
+    /// Run that first no longer listed this module version
+    pub discontinued_in_run_id: Option<i32>,
+}
+
+/// One page of search results
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModuleSearchPage {
+    pub modules: Vec<ModuleSummary>,
+    /// Passed back to load the next page, `None` after the last page
+    pub next_cursor: Option<String>,
+    /// Newest scraping run included, `None` before the first run completed
+    pub snapshot: Option<i32>,
+}
+
+/// Order of search results
+///
+/// Every order falls back to the module number and version, so pages never
+/// overlap or skip modules.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema, async_graphql::Enum))]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Best title match first while searching, by title otherwise
+    #[default]
+    Relevance,
+    Title,
+    CreditsAsc,
+    CreditsDesc,
+    ModuleNumber,
+    Faculty,
+    /// Fewest weekly hours (SWS) of all components first
+    TotalSws,
+    /// Most recently added or changed in a scraping run first
+    RecentlyChanged,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 8] = [
+        SortOrder::Relevance,
+        SortOrder::Title,
+        SortOrder::CreditsAsc,
+        SortOrder::CreditsDesc,
+        SortOrder::ModuleNumber,
+        SortOrder::Faculty,
+        SortOrder::TotalSws,
+        SortOrder::RecentlyChanged,
+    ];
+
+    /// Value used in select inputs
+    pub fn key(self) -> &'static str {
+        match self {
+            SortOrder::Relevance => "relevance",
+            SortOrder::Title => "title",
+            SortOrder::CreditsAsc => "credits_asc",
+            SortOrder::CreditsDesc => "credits_desc",
+            SortOrder::ModuleNumber => "module_number",
+            SortOrder::Faculty => "faculty",
+            SortOrder::TotalSws => "total_sws",
+            SortOrder::RecentlyChanged => "recently_changed",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|order| order.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::Relevance => "Relevance",
+            SortOrder::Title => "Title",
+            SortOrder::CreditsAsc => "Credits (low to high)",
+            SortOrder::CreditsDesc => "Credits (high to low)",
+            SortOrder::ModuleNumber => "Module number",
+            SortOrder::Faculty => "Faculty",
+            SortOrder::TotalSws => "Total SWS",
+            SortOrder::RecentlyChanged => "Recently changed",
+        }
+    }
 }
 
 /// Search filters for module queries
@@ -37,24 +118,186 @@ pub struct SearchFilters {
     pub semester_rotations: Option<Vec<String>>,
     pub exam_categories: Option<Vec<String>>,
     pub study_program_ids: Option<Vec<i32>>,
+    /// Study programs whose name contains one of these, case-insensitive
+    pub study_program_names: Option<Vec<String>>,
     pub component_types: Option<Vec<String>>,
     pub component_languages: Option<Vec<String>>,
+    pub faculty_ids: Option<Vec<i32>>,
+    pub institute_ids: Option<Vec<i32>>,
+    pub fachgebiet_ids: Option<Vec<i32>>,
+    pub responsible_person_ids: Option<Vec<i32>>,
+    pub examination_board_ids: Option<Vec<i32>>,
+    /// `Some(true)` for modules with a graded exam, `Some(false)` for an ungraded one
+    pub graded: Option<bool>,
+    /// Modules with an exam of one of these forms, e.g. `Portfolioprüfung`
+    pub exam_types: Option<Vec<String>>,
+    /// Modules without an exam of any of these forms
+    pub excluded_exam_types: Option<Vec<String>>,
+    /// Bounds of the total workload, modules without workload information never match
+    pub min_workload_hours: Option<i32>,
+    pub max_workload_hours: Option<i32>,
+    /// Only modules without a cap on attendees
+    pub no_attendance_limit: bool,
+    /// Modules with at least this many places or without a cap
+    pub min_attendee_places: Option<i32>,
     pub starred_only: bool,
+    pub newest_version_only: bool,
+    pub include_discontinued: bool,
+    /// Only show modules of this local collection, resolved to `module_keys` by the client
+    pub collection_id: Option<u32>,
+    /// Explicit (id, version) list to search in, `Some(vec![])` matches nothing
+    pub module_keys: Option<Vec<(i32, i32)>>,
+    pub sort: SortOrder,
 }
 
 /// Options available for filters (populated from database)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
 pub struct FilterOptions {
     pub study_programs: Vec<StudyProgramOption>,
     pub exam_categories: Vec<String>,
     pub semester_rotations: Vec<String>,
     pub component_types: Vec<String>,
     pub component_languages: Vec<String>,
+    pub faculties: Vec<OrganizationOption>,
+    pub institutes: Vec<OrganizationOption>,
+    pub fachgebiete: Vec<OrganizationOption>,
+    pub responsible_persons: Vec<OrganizationOption>,
+    pub examination_boards: Vec<OrganizationOption>,
+    pub exam_types: Vec<String>,
     pub credit_range: (i32, i32),
+    /// Smallest and largest total workload in hours
+    pub workload_range: (i32, i32),
+    /// Smallest and largest cap on attendees
+    pub attendee_limit_range: (i32, i32),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
 pub struct StudyProgramOption {
     pub id: i32,
     pub name: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct OrganizationOption {
+    pub id: i32,
+    pub name: String,
+}
+
+/// Number of matching modules per filter option
+///
+/// Each facet is counted under all active filters except its own, so the
+/// counts tell how many results picking an option would add. Options missing
+/// from a map have no matches.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct FilterFacets {
+    pub study_programs: HashMap<i32, i64>,
+    pub semester_rotations: HashMap<String, i64>,
+    pub exam_categories: HashMap<String, i64>,
+    pub component_types: HashMap<String, i64>,
+    pub component_languages: HashMap<String, i64>,
+    pub exam_types: HashMap<String, i64>,
+    pub graded: i64,
+    pub ungraded: i64,
+    pub faculties: HashMap<i32, i64>,
+    pub institutes: HashMap<i32, i64>,
+    pub fachgebiete: HashMap<i32, i64>,
+    pub responsible_persons: HashMap<i32, i64>,
+    pub examination_boards: HashMap<i32, i64>,
+}
+
+/// Organizational unit a module belongs to, each with its own multi-select filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrganizationUnit {
+    Faculty,
+    Institute,
+    Fachgebiet,
+    ResponsiblePerson,
+    ExaminationBoard,
+}
+
+impl OrganizationUnit {
+    pub const ALL: [OrganizationUnit; 5] = [
+        OrganizationUnit::Faculty,
+        OrganizationUnit::Institute,
+        OrganizationUnit::Fachgebiet,
+        OrganizationUnit::ResponsiblePerson,
+        OrganizationUnit::ExaminationBoard,
+    ];
+
+    /// Heading of the filter
+    pub fn label(self) -> &'static str {
+        match self {
+            OrganizationUnit::Faculty => "Faculties",
+            OrganizationUnit::Institute => "Institutes",
+            OrganizationUnit::Fachgebiet => "Fachgebiete",
+            OrganizationUnit::ResponsiblePerson => "Responsible Persons",
+            OrganizationUnit::ExaminationBoard => "Examination Boards",
+        }
+    }
+
+    /// Table holding the names
+    pub fn table(self) -> &'static str {
+        match self {
+            OrganizationUnit::Faculty => "faculty",
+            OrganizationUnit::Institute => "institute",
+            OrganizationUnit::Fachgebiet => "fachgebiet",
+            OrganizationUnit::ResponsiblePerson => "responsible_person",
+            OrganizationUnit::ExaminationBoard => "examination_board",
+        }
+    }
+
+    /// Column of the `module` table referencing `table()`
+    pub fn module_column(self) -> &'static str {
+        match self {
+            OrganizationUnit::Faculty => "faculty_id",
+            OrganizationUnit::Institute => "institute_id",
+            OrganizationUnit::Fachgebiet => "fg_id",
+            OrganizationUnit::ResponsiblePerson => "responsible_id",
+            OrganizationUnit::ExaminationBoard => "examination_board_id",
+        }
+    }
+
+    pub fn facet(self, facets: &FilterFacets) -> &HashMap<i32, i64> {
+        match self {
+            OrganizationUnit::Faculty => &facets.faculties,
+            OrganizationUnit::Institute => &facets.institutes,
+            OrganizationUnit::Fachgebiet => &facets.fachgebiete,
+            OrganizationUnit::ResponsiblePerson => &facets.responsible_persons,
+            OrganizationUnit::ExaminationBoard => &facets.examination_boards,
+        }
+    }
+
+    pub fn options(self, options: &FilterOptions) -> &[OrganizationOption] {
+        match self {
+            OrganizationUnit::Faculty => &options.faculties,
+            OrganizationUnit::Institute => &options.institutes,
+            OrganizationUnit::Fachgebiet => &options.fachgebiete,
+            OrganizationUnit::ResponsiblePerson => &options.responsible_persons,
+            OrganizationUnit::ExaminationBoard => &options.examination_boards,
+        }
+    }
+
+    /// Selected ids in the search filters, `None` if the filter is not used
+    pub fn selected(self, filters: &SearchFilters) -> &Option<Vec<i32>> {
+        match self {
+            OrganizationUnit::Faculty => &filters.faculty_ids,
+            OrganizationUnit::Institute => &filters.institute_ids,
+            OrganizationUnit::Fachgebiet => &filters.fachgebiet_ids,
+            OrganizationUnit::ResponsiblePerson => &filters.responsible_person_ids,
+            OrganizationUnit::ExaminationBoard => &filters.examination_board_ids,
+        }
+    }
+
+    pub fn selected_mut(self, filters: &mut SearchFilters) -> &mut Option<Vec<i32>> {
+        match self {
+            OrganizationUnit::Faculty => &mut filters.faculty_ids,
+            OrganizationUnit::Institute => &mut filters.institute_ids,
+            OrganizationUnit::Fachgebiet => &mut filters.fachgebiet_ids,
+            OrganizationUnit::ResponsiblePerson => &mut filters.responsible_person_ids,
+            OrganizationUnit::ExaminationBoard => &mut filters.examination_board_ids,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::collections::CollectionEntry;
+use serde::{Deserialize, Serialize};
+
+/// A read-only list of module versions published under a link
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SharedList {
+    pub token: String,
+    pub name: String,
+    pub entries: Vec<CollectionEntry>,
+}
+
+impl SharedList {
+    pub fn contains(&self, id: i32, version: i32) -> bool {
+        self.entries.iter().any(|e| e.id == id && e.version == version)
+    }
+}
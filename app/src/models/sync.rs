@@ -0,0 +1,28 @@
+use super::module_summary::SearchFilters;
+use serde::{Deserialize, Serialize};
+
+/// Starred modules and saved filters shared between devices
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct SyncState {
+    /// Starred (id, version) pairs, sorted
+    pub starred: Vec<(i32, i32)>,
+    pub filters: SearchFilters,
+}
+
+/// The state stored under a sync code and its revision
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SyncSnapshot {
+    pub token: String,
+    pub revision: i32,
+    pub state: SyncState,
+}
+
+/// Result of pushing a state to the server
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PushOutcome {
+    /// The state was stored under the new revision
+    Saved { revision: i32 },
+    /// Another device pushed first, the current server state has to be merged
+    Conflict(SyncSnapshot),
+}
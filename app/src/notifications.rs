@@ -0,0 +1,327 @@
+use crate::api::change_report::load_change_report;
+use crate::models::ChangeReport;
+use db::PgPool;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// How the connection to the SMTP relay is secured
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmtpTls {
+    /// Plain connection, only meant for local mail catchers
+    None,
+    StartTls,
+    Tls,
+}
+
+/// SMTP relay and link settings read from the environment
+#[derive(Debug, Clone)]
+pub struct MailConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub credentials: Option<(String, String)>,
+    pub from: String,
+    /// Scheme and host used for links in emails
+    pub base_url: String,
+}
+
+/// Public scheme and host of the app from `PUBLIC_BASE_URL`, without a trailing slash
+///
+/// Links that leave the app (emails, feeds) use this instead of the request's
+/// `Host`, which clients control.
+pub fn public_base_url() -> String {
+    std::env::var("PUBLIC_BASE_URL")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| "http://localhost:3000".to_string())
+        .trim()
+        .trim_end_matches('/')
+        .to_string()
+}
+
+impl MailConfig {
+    /// Read the settings from `SMTP_*` and `PUBLIC_BASE_URL`
+    ///
+    /// Returns `None` if `SMTP_HOST` is not set, which disables notifications.
+    /// `SMTP_TLS` defaults to `starttls`, other values than `none`, `starttls`
+    /// and `tls` are an error.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let Some(host) = var("SMTP_HOST") else {
+            return Ok(None);
+        };
+        let tls = match var("SMTP_TLS").map(|v| v.trim().to_lowercase()).as_deref() {
+            Some("none") => SmtpTls::None,
+            Some("starttls") | None => SmtpTls::StartTls,
+            Some("tls") => SmtpTls::Tls,
+            Some(other) => {
+                return Err(format!(
+                    "Invalid SMTP_TLS value `{}`, expected none, starttls or tls",
+                    other
+                ))
+            }
+        };
+        let default_port = match tls {
+            SmtpTls::None => 25,
+            SmtpTls::StartTls => 587,
+            SmtpTls::Tls => 465,
+        };
+
+        Ok(Some(Self {
+            host,
+            port: var("SMTP_PORT").and_then(|p| p.parse().ok()).unwrap_or(default_port),
+            tls,
+            credentials: var("SMTP_USERNAME").map(|user| (user, var("SMTP_PASSWORD").unwrap_or_default())),
+            from: var("SMTP_FROM").unwrap_or_else(|| "TU Berlin Module Search <noreply@localhost>".to_string()),
+            base_url: public_base_url(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub enum NotificationError {
+    Database(sqlx::Error),
+    Mail(String),
+}
+
+impl fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationError::Database(e) => write!(f, "Database error: {}", e),
+            NotificationError::Mail(e) => write!(f, "Mail error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for NotificationError {}
+
+impl From<sqlx::Error> for NotificationError {
+    fn from(e: sqlx::Error) -> Self {
+        NotificationError::Database(e)
+    }
+}
+
+/// Send a plain text email through the configured relay
+pub async fn send_mail(config: &MailConfig, to: &str, subject: &str, body: String) -> Result<(), NotificationError> {
+    let mail_error = |e: &dyn fmt::Display| NotificationError::Mail(e.to_string());
+
+    let message = Message::builder()
+        .from(config.from.parse().map_err(|e| mail_error(&e))?)
+        .to(to.parse().map_err(|e| mail_error(&e))?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)
+        .map_err(|e| mail_error(&e))?;
+
+    let builder = match config.tls {
+        SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+        SmtpTls::StartTls => {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host).map_err(|e| mail_error(&e))?
+        }
+        SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host).map_err(|e| mail_error(&e))?,
+    };
+    let mut builder = builder.port(config.port);
+    if let Some((user, password)) = &config.credentials {
+        builder = builder.credentials(Credentials::new(user.clone(), password.clone()));
+    }
+
+    builder.build().send(message).await.map_err(|e| mail_error(&e))?;
+    Ok(())
+}
+
+/// Ask a new subscriber to confirm their address
+pub async fn send_confirmation(
+    config: &MailConfig,
+    email: &str,
+    token: &str,
+    module_count: usize,
+) -> Result<(), NotificationError> {
+    let body = format!(
+        "Hello,\n\n\
+         someone asked to be notified when any of {count} modules changes on TU Berlin Module Search.\n\
+         If that was you, confirm your address here:\n\n\
+         {base}/notifications/confirm/{token}\n\n\
+         If not, you can ignore this email.\n\n\
+         If this address already follows modules, confirming replaces them.\n",
+        count = module_count,
+        base = config.base_url,
+        token = token,
+    );
+
+    send_mail(config, email, "Confirm your module notifications", body).await
+}
+
+/// Send each confirmed subscriber a digest of the runs completed since they
+/// were last notified
+///
+/// Runs the web scraper completes trigger this directly, runs of the CLI
+/// scraper are picked up on the next server start. Returns the number of
+/// digests sent. A subscriber whose digest fails is retried next time.
+pub async fn send_pending_digests(pool: &PgPool, config: &MailConfig) -> Result<usize, NotificationError> {
+    let Some(latest_run_id) = sqlx::query_scalar!("SELECT MAX(id) FROM scraping_run WHERE status = 'completed'")
+        .fetch_one(pool)
+        .await?
+    else {
+        return Ok(0);
+    };
+
+    let subscriptions = sqlx::query!(
+        r#"
+        SELECT id, email, token, COALESCE(last_notified_run_id, 0) as "last_notified_run_id!"
+        FROM notification_subscription
+        WHERE confirmed_at IS NOT NULL AND COALESCE(last_notified_run_id, 0) < $1
+        "#,
+        latest_run_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let Some(oldest_notified) = subscriptions.iter().map(|s| s.last_notified_run_id).min() else {
+        return Ok(0);
+    };
+
+    let mut reports = Vec::new();
+    for run in sqlx::query!(
+        r#"
+        SELECT id
+        FROM scraping_run
+        WHERE status = 'completed' AND previous_run_id IS NOT NULL AND id > $1
+        ORDER BY id
+        "#,
+        oldest_notified
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        if let Some(report) = load_change_report(pool, run.id).await? {
+            reports.push(report);
+        }
+    }
+
+    let mut followed: HashMap<i32, HashSet<(i32, i32)>> = HashMap::new();
+    for row in sqlx::query!(
+        r#"
+        SELECT sm.subscription_id, sm.module_id, sm.module_version
+        FROM notification_subscription_module sm
+        JOIN notification_subscription s ON s.id = sm.subscription_id
+        WHERE s.confirmed_at IS NOT NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        followed
+            .entry(row.subscription_id)
+            .or_default()
+            .insert((row.module_id, row.module_version));
+    }
+
+    let mut sent = 0;
+    for subscription in subscriptions {
+        let keys = followed.remove(&subscription.id).unwrap_or_default();
+        let new_reports: Vec<&ChangeReport> = reports
+            .iter()
+            .filter(|r| r.run_id > subscription.last_notified_run_id)
+            .collect();
+
+        if let Some(digest) = render_digest(&new_reports, &keys, &config.base_url, &subscription.token) {
+            if let Err(e) = send_mail(config, &subscription.email, "Changes to your followed modules", digest).await {
+                tracing::error!("Failed to send digest to subscription {}: {}", subscription.id, e);
+                continue;
+            }
+            sent += 1;
+        }
+
+        sqlx::query!(
+            "UPDATE notification_subscription SET last_notified_run_id = $1 WHERE id = $2",
+            latest_run_id,
+            subscription.id
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(sent)
+}
+
+/// Render the digest of the given reports for a set of followed module versions
+///
+/// Lists changed and removed followed versions as well as new versions of
+/// followed module numbers. Returns `None` if nothing relevant happened.
+pub fn render_digest(
+    reports: &[&ChangeReport],
+    followed: &HashSet<(i32, i32)>,
+    base_url: &str,
+    token: &str,
+) -> Option<String> {
+    let followed_ids: HashSet<i32> = followed.iter().map(|(id, _)| *id).collect();
+    let mut sections = Vec::new();
+
+    for report in reports {
+        let mut lines = Vec::new();
+        for entry in &report.entries {
+            let key = (entry.module_id, entry.module_version);
+            let link = format!("{}/module/{}/{}", base_url, entry.module_id, entry.module_version);
+            match entry.change_type.as_str() {
+                "changed" if followed.contains(&key) => {
+                    lines.push(format!("* {} (#{} v{})", entry.title, entry.module_id, entry.module_version));
+                    for change in &entry.changes {
+                        let multiline = [&change.old_value, &change.new_value]
+                            .iter()
+                            .any(|v| v.as_deref().is_some_and(|v| v.contains('\n')));
+                        if change.is_text() || !multiline {
+                            lines.push(format!("    {}", change.summary()));
+                            continue;
+                        }
+                        // List fields such as components, where rotation changes show up
+                        let (removed, added) = change.line_diff();
+                        lines.push(format!("    {}:", change.label()));
+                        lines.extend(removed.iter().map(|l| format!("      - {}", l)));
+                        lines.extend(added.iter().map(|l| format!("      + {}", l)));
+                    }
+                    lines.push(format!("    {}", link));
+                }
+                "removed" if followed.contains(&key) => {
+                    lines.push(format!(
+                        "* {} (#{} v{}) is no longer listed in MOSES",
+                        entry.title, entry.module_id, entry.module_version
+                    ));
+                }
+                "added" if followed_ids.contains(&entry.module_id) && !followed.contains(&key) => {
+                    lines.push(format!(
+                        "* New version v{} of {} (#{})",
+                        entry.module_version, entry.title, entry.module_id
+                    ));
+                    lines.push(format!("    {}", link));
+                }
+                _ => {}
+            }
+        }
+
+        if !lines.is_empty() {
+            let date = report
+                .completed_at
+                .map(|at| format!(" ({})", at.format("%Y-%m-%d")))
+                .unwrap_or_default();
+            sections.push(format!("Scraping run #{}{}:\n{}", report.run_id, date, lines.join("\n")));
+        }
+    }
+
+    if sections.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "Hello,\n\n\
+         the following changes affect modules you follow on TU Berlin Module Search.\n\n\
+         {sections}\n\n\
+         Unsubscribe: {base}/notifications/unsubscribe/{token}\n",
+        sections = sections.join("\n\n"),
+        base = base_url,
+        token = token,
+    ))
+}
@@ -0,0 +1,348 @@
+use crate::api::{get_modules_by_keys, share_module_list};
+use crate::collections::{
+    create_collection, delete_collection, export_collections, import_collections, rename_collection,
+    set_entry_note, shared_list_url, toggle_in_collection, use_module_collections, ModuleCollection,
+    ModuleCollections,
+};
+use crate::components::layout::PageLayout;
+use crate::handbook::{handbook_url, HandbookFormat, HandbookSource};
+use crate::models::{ModuleSummary, SearchFilters};
+use crate::planner::data_url;
+use crate::starred::use_search_filters;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::components::A;
+use leptos_router::hooks::use_navigate;
+
+#[component]
+pub fn CollectionsPage() -> impl IntoView {
+    let (collections, set_collections) = use_module_collections();
+    let filters = use_search_filters();
+
+    let new_name = RwSignal::new(String::new());
+    let import_text = RwSignal::new(String::new());
+    let (import_result, set_import_result) = signal(None::<Result<String, String>>);
+
+    // Every module in any collection, for titles and credits
+    let keys = Memo::new(move |_| {
+        let mut keys: Vec<(i32, i32)> = collections
+            .get()
+            .collections
+            .iter()
+            .flat_map(|c| c.keys())
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    });
+
+    // Collections only exist in local storage, so load on the client
+    let modules = LocalResource::new(move || {
+        let keys = keys.get();
+        async move { get_modules_by_keys(keys).await }
+    });
+
+    let on_create = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let name = new_name.get_untracked();
+        if name.trim().is_empty() {
+            return;
+        }
+        set_collections.set(create_collection(&collections.get_untracked(), &name));
+        new_name.set(String::new());
+    };
+
+    let on_import = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let current = collections.get_untracked();
+        match import_collections(&current, &import_text.get_untracked()) {
+            Ok(imported) => {
+                let count = imported.collections.len() - current.collections.len();
+                set_collections.set(imported);
+                import_text.set(String::new());
+                set_import_result.set(Some(Ok(format!("Imported {} collection(s).", count))));
+            }
+            Err(e) => set_import_result.set(Some(Err(e))),
+        }
+    };
+
+    let export_all_href = move || {
+        data_url(
+            "application/json",
+            &export_collections(&collections.get().collections),
+        )
+    };
+
+    view! {
+        <PageLayout>
+            <div class="py-8 flex flex-col gap-6">
+                <div class="flex items-center justify-between gap-4 pb-3 border-b border-base-300/50">
+                    <div class="flex items-center gap-3 flex-1 min-w-0">
+                        <div class="tooltip tooltip-right" data-tip="Back to search">
+                            <A href="/" attr:class="btn btn-sm btn-soft btn-circle shrink-0">
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7" />
+                                </svg>
+                            </A>
+                        </div>
+                        <h1 class="text-2xl font-bold leading-tight text-base-content">"Collections"</h1>
+                    </div>
+                    <a href=export_all_href download="module-collections.json" class="btn btn-sm btn-soft">
+                        "Export all"
+                    </a>
+                </div>
+
+                <div class="grid grid-cols-1 lg:grid-cols-2 gap-4">
+                    <div class="card bg-base-100 shadow-sm">
+                        <form class="card-body p-5 gap-3" on:submit=on_create>
+                            <h2 class="card-title text-lg text-primary">"New collection"</h2>
+                            <input
+                                type="text"
+                                required
+                                placeholder="e.g. Maybe next semester"
+                                class="input input-sm w-full"
+                                prop:value=move || new_name.get()
+                                on:input=move |ev| new_name.set(event_target_value(&ev))
+                            />
+                            <div class="card-actions justify-end">
+                                <button type="submit" class="btn btn-sm btn-primary">"Create"</button>
+                            </div>
+                        </form>
+                    </div>
+                    <div class="card bg-base-100 shadow-sm">
+                        <form class="card-body p-5 gap-3" on:submit=on_import>
+                            <h2 class="card-title text-lg text-primary">"Import"</h2>
+                            <textarea
+                                required
+                                placeholder="Paste an exported collection"
+                                class="textarea textarea-sm w-full font-mono h-20"
+                                prop:value=move || import_text.get()
+                                on:input=move |ev| import_text.set(event_target_value(&ev))
+                            ></textarea>
+                            {move || import_result.get().map(|result| match result {
+                                Ok(message) => view! {
+                                    <div class="alert alert-success alert-soft"><span>{message}</span></div>
+                                }.into_any(),
+                                Err(message) => view! {
+                                    <div class="alert alert-error alert-soft"><span>{message}</span></div>
+                                }.into_any(),
+                            })}
+                            <div class="card-actions justify-end">
+                                <button type="submit" class="btn btn-sm btn-soft">"Import"</button>
+                            </div>
+                        </form>
+                    </div>
+                </div>
+
+                <Suspense fallback=move || {
+                    view! {
+                        <div class="flex items-center justify-center py-12">
+                            <span class="loading loading-spinner loading-lg"></span>
+                        </div>
+                    }
+                }>
+                    {move || Suspend::new(async move {
+                        match modules.await {
+                            Ok(modules) => {
+                                let modules = StoredValue::new(modules);
+                                view! {
+                                    <For
+                                        each=move || collections.get().collections
+                                        key=|collection| collection.clone()
+                                        children=move |collection| {
+                                            view! {
+                                                <CollectionCard
+                                                    collection=collection
+                                                    modules=modules
+                                                    collections=collections
+                                                    set_collections=set_collections
+                                                    filters=filters
+                                                />
+                                            }
+                                        }
+                                    />
+                                }.into_any()
+                            }
+                            Err(e) => {
+                                view! {
+                                    <div class="alert alert-error">
+                                        <span>"Error loading modules: " {e.to_string()}</span>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+#[component]
+fn CollectionCard(
+    collection: ModuleCollection,
+    modules: StoredValue<Vec<ModuleSummary>>,
+    collections: Signal<ModuleCollections>,
+    set_collections: WriteSignal<ModuleCollections>,
+    filters: RwSignal<SearchFilters>,
+) -> impl IntoView {
+    let collection_id = collection.id;
+    let navigate = use_navigate();
+    let confirm_delete = RwSignal::new(false);
+    let (sharing, set_sharing) = signal(false);
+    let (share_link, set_share_link) = signal(None::<Result<String, String>>);
+
+    let credits: i32 = modules.with_value(|modules| {
+        modules
+            .iter()
+            .filter(|m| collection.contains(m.id, m.version))
+            .map(|m| m.credits)
+            .sum()
+    });
+    let export_href = data_url(
+        "application/json",
+        &export_collections(std::slice::from_ref(&collection)),
+    );
+    let collection_empty = collection.entries.is_empty();
+    let handbook_href = handbook_url(
+        HandbookFormat::Html,
+        Some(&collection.name),
+        &HandbookSource::Filters(SearchFilters {
+            module_keys: Some(collection.keys()),
+            include_discontinued: true,
+            ..Default::default()
+        }),
+    );
+    let export_name = format!("{}.json", collection.name.replace(['/', '\\'], "-"));
+
+    let share_name = collection.name.clone();
+    let share_entries = collection.entries.clone();
+    let on_share = move |_| {
+        let (name, entries) = (share_name.clone(), share_entries.clone());
+        spawn_local(async move {
+            set_sharing.set(true);
+            match share_module_list(name, entries).await {
+                Ok(token) => set_share_link.set(Some(Ok(shared_list_url(&token)))),
+                Err(e) => set_share_link.set(Some(Err(e.to_string()))),
+            }
+            set_sharing.set(false);
+        });
+    };
+
+    let on_show = move |_| {
+        filters.update(|f| f.collection_id = Some(collection_id));
+        navigate("/", Default::default());
+    };
+
+    let on_delete = move |_| {
+        if !confirm_delete.get_untracked() {
+            confirm_delete.set(true);
+            return;
+        }
+        set_collections.set(delete_collection(&collections.get_untracked(), collection_id));
+        if filters.with_untracked(|f| f.collection_id == Some(collection_id)) {
+            filters.update(|f| f.collection_id = None);
+        }
+    };
+
+    view! {
+        <div class="card bg-base-100 shadow-sm">
+            <div class="card-body p-5 gap-3">
+                <div class="flex items-center justify-between gap-3 flex-wrap">
+                    <input
+                        type="text"
+                        class="input input-ghost input-sm text-lg font-semibold text-primary flex-1 min-w-48"
+                        prop:value=collection.name.clone()
+                        on:change=move |ev| {
+                            let name = event_target_value(&ev);
+                            if !name.trim().is_empty() {
+                                set_collections.set(rename_collection(&collections.get_untracked(), collection_id, &name));
+                            }
+                        }
+                    />
+                    <div class="flex items-center gap-2">
+                        <div class="badge badge-soft badge-primary">{credits} " CP"</div>
+                        <button class="btn btn-xs btn-soft btn-primary" on:click=on_show>"Show in search"</button>
+                        <button
+                            class="btn btn-xs btn-soft"
+                            disabled=move || sharing.get() || collection_empty
+                            on:click=on_share
+                        >
+                            "Share link"
+                        </button>
+                        <a href=export_href download=export_name class="btn btn-xs btn-soft">"Export"</a>
+                        <a href=handbook_href target="_blank" class="btn btn-xs btn-soft" class:btn-disabled=collection_empty>
+                            "Handbook"
+                        </a>
+                        <button class="btn btn-xs btn-soft btn-error" on:click=on_delete>
+                            {move || if confirm_delete.get() { "Really delete?" } else { "Delete" }}
+                        </button>
+                    </div>
+                </div>
+                {move || share_link.get().map(|link| match link {
+                    Ok(url) => view! {
+                        <label class="form-control w-full">
+                            <span class="label-text text-xs mb-1">
+                                "Read-only link to this list. Later changes to the collection need a new link."
+                            </span>
+                            <input type="text" readonly class="input input-sm w-full font-mono" prop:value=url />
+                        </label>
+                    }.into_any(),
+                    Err(message) => view! {
+                        <div class="alert alert-error alert-soft"><span>{message}</span></div>
+                    }.into_any(),
+                })}
+                {if collection.entries.is_empty() {
+                    view! {
+                        <p class="text-sm text-base-content/60">
+                            "No modules yet. Add modules from the search with the bookmark button."
+                        </p>
+                    }.into_any()
+                } else {
+                    collection.entries.into_iter().map(|entry| {
+                        let (id, version) = (entry.id, entry.version);
+                        let title = modules.with_value(|modules| {
+                            modules
+                                .iter()
+                                .find(|m| m.id == id && m.version == version)
+                                .map(|m| m.title.clone())
+                        });
+                        view! {
+                            <div class="flex items-center justify-between gap-3 py-1.5 border-b border-base-300/50 last:border-0">
+                                <div class="flex flex-col min-w-0">
+                                    <A href=format!("/module/{}/{}", id, version) attr:class="link link-hover font-medium text-sm truncate">
+                                        {title.unwrap_or_else(|| "Unknown module".to_string())}
+                                    </A>
+                                    <span class="text-xs text-base-content/50">"#" {id} " v" {version}</span>
+                                </div>
+                                <div class="flex items-center gap-2 shrink-0">
+                                    <input
+                                        type="text"
+                                        placeholder="Note"
+                                        class="input input-xs w-56"
+                                        prop:value=entry.note
+                                        on:change=move |ev| {
+                                            let note = event_target_value(&ev);
+                                            set_collections.set(set_entry_note(&collections.get_untracked(), collection_id, id, version, &note));
+                                        }
+                                    />
+                                    <div class="tooltip tooltip-left" data-tip="Remove from collection">
+                                        <button
+                                            class="btn btn-ghost btn-xs btn-circle"
+                                            on:click=move |_| set_collections.set(toggle_in_collection(&collections.get_untracked(), collection_id, id, version))
+                                        >
+                                            <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M6 18L18 6M6 6l12 12" />
+                                            </svg>
+                                        </button>
+                                    </div>
+                                </div>
+                            </div>
+                        }
+                    }).collect_view().into_any()
+                }}
+            </div>
+        </div>
+    }
+}
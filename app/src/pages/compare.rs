@@ -0,0 +1,163 @@
+use crate::api::get_module_details;
+use crate::compare::{compare_rows, compare_url, parse_compare_param};
+use crate::components::layout::PageLayout;
+use crate::models::ModuleDetail;
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::use_query_map;
+
+#[component]
+pub fn ComparePage() -> impl IntoView {
+    let query = use_query_map();
+    let keys = Memo::new(move |_| {
+        query
+            .read()
+            .get("m")
+            .map(|param| parse_compare_param(&param))
+            .unwrap_or_default()
+    });
+
+    // Unknown keys, e.g. stale entries from local storage, are left out
+    let modules = Resource::new(move || keys.get(), get_module_details);
+
+    view! {
+        <PageLayout>
+            <div class="py-8 flex flex-col gap-6">
+                <div class="flex items-center justify-between gap-4 pb-3 border-b border-base-300/50">
+                    <div class="flex items-center gap-3 flex-1 min-w-0">
+                        <div class="tooltip tooltip-right" data-tip="Back to search">
+                            <A href="/" attr:class="btn btn-sm btn-soft btn-circle shrink-0">
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7" />
+                                </svg>
+                            </A>
+                        </div>
+                        <h1 class="text-2xl font-bold leading-tight text-base-content">"Compare Modules"</h1>
+                    </div>
+                </div>
+
+                <Suspense fallback=move || {
+                    view! {
+                        <div class="flex items-center justify-center py-12">
+                            <span class="loading loading-spinner loading-lg"></span>
+                        </div>
+                    }
+                }>
+                    {move || Suspend::new(async move {
+                        match modules.await {
+                            Ok(modules) if modules.len() < 2 => {
+                                view! {
+                                    <div class="alert">
+                                        <span>"Select at least two modules to compare, using the compare button on the module cards."</span>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                            Ok(modules) => {
+                                let missing = keys.get_untracked().len().saturating_sub(modules.len());
+                                view! {
+                                    {(missing > 0).then(|| view! {
+                                        <div class="alert alert-warning">
+                                            <span>{missing} " of the selected modules no longer exist and are left out."</span>
+                                        </div>
+                                    })}
+                                    <CompareTable modules=modules />
+                                }
+                                .into_any()
+                            }
+                            Err(e) => {
+                                view! {
+                                    <div class="alert alert-error">
+                                        <span>"Error loading modules: " {e.to_string()}</span>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+#[component]
+fn CompareTable(modules: Vec<ModuleDetail>) -> impl IntoView {
+    let keys: Vec<(i32, i32)> = modules.iter().map(|m| (m.id, m.version)).collect();
+    let rows = compare_rows(&modules);
+
+    view! {
+        <div class="card bg-base-100 shadow-sm">
+            <div class="card-body p-0 overflow-x-auto">
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th class="w-40"></th>
+                            {modules.into_iter().map(|module| {
+                                let href = format!("/module/{}/{}", module.id, module.version);
+                                let others: Vec<(i32, i32)> = keys
+                                    .iter()
+                                    .copied()
+                                    .filter(|k| *k != (module.id, module.version))
+                                    .collect();
+                                view! {
+                                    <th class="align-top min-w-56">
+                                        <div class="flex items-start justify-between gap-2">
+                                            <div class="flex flex-col">
+                                                <A href=href attr:class="link link-hover text-base font-semibold text-base-content normal-case">
+                                                    {module.title}
+                                                </A>
+                                                <span class="text-xs font-normal text-base-content/50">
+                                                    "Module " {module.id} " · v" {module.version}
+                                                </span>
+                                            </div>
+                                            <div class="tooltip tooltip-left" data-tip="Remove from comparison">
+                                                <A href=compare_url(&others) attr:class="btn btn-ghost btn-xs btn-circle">
+                                                    <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M6 18L18 6M6 6l12 12" />
+                                                    </svg>
+                                                </A>
+                                            </div>
+                                        </div>
+                                    </th>
+                                }
+                            }).collect_view()}
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {rows.into_iter().map(|row| {
+                            let differs = row.differs();
+                            view! {
+                                <tr class=if differs { "bg-warning/10" } else { "" }>
+                                    <th class="align-top text-xs font-semibold text-base-content/60">
+                                        <div class="flex items-center gap-1.5">
+                                            {row.label}
+                                            {differs.then(|| view! {
+                                                <span class="badge badge-soft badge-warning badge-xs">"differs"</span>
+                                            })}
+                                        </div>
+                                    </th>
+                                    {row.cells.into_iter().map(|lines| {
+                                        view! {
+                                            <td class="align-top text-sm">
+                                                {if lines.is_empty() {
+                                                    view! { <span class="text-base-content/40">"–"</span> }.into_any()
+                                                } else {
+                                                    view! {
+                                                        <ul class="flex flex-col gap-0.5">
+                                                            {lines.into_iter().map(|line| view! { <li>{line}</li> }).collect_view()}
+                                                        </ul>
+                                                    }.into_any()
+                                                }}
+                                            </td>
+                                        }
+                                    }).collect_view()}
+                                </tr>
+                            }
+                        }).collect_view()}
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
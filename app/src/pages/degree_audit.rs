@@ -0,0 +1,281 @@
+use crate::api::{get_degree_audit, get_stupo_options};
+use crate::components::layout::PageLayout;
+use crate::handbook::{handbook_url, HandbookFormat, HandbookSource};
+use crate::models::{AreaAudit, AuditModule, DegreeAudit};
+use crate::planner::use_study_plan;
+use crate::starred::use_starred_modules;
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+/// Which module set the audit runs on
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AuditSource {
+    Starred,
+    Plan,
+}
+
+#[component]
+pub fn DegreeAuditPage() -> impl IntoView {
+    let (starred, _set_starred) = use_starred_modules();
+    let (plan, _set_plan) = use_study_plan();
+
+    let stupo_options = Resource::new(|| (), |_| async { get_stupo_options().await });
+    let selected_stupo = RwSignal::new(None::<i32>);
+    let source = RwSignal::new(AuditSource::Plan);
+
+    let keys = Memo::new(move |_| {
+        let mut keys: Vec<(i32, i32)> = match source.get() {
+            AuditSource::Starred => starred.get().modules.into_iter().collect(),
+            AuditSource::Plan => plan
+                .get()
+                .semesters
+                .into_iter()
+                .flat_map(|s| s.modules)
+                .collect(),
+        };
+        keys.sort();
+        keys
+    });
+
+    // Module sets only exist in local storage, so audit on the client
+    let audit = LocalResource::new(move || {
+        let stupo = selected_stupo.get();
+        let keys = keys.get();
+        async move {
+            match stupo {
+                Some(stupo_id) => get_degree_audit(stupo_id, keys).await.map(Some),
+                None => Ok(None),
+            }
+        }
+    });
+
+    view! {
+        <PageLayout>
+            <div class="py-8 flex flex-col gap-6">
+                <div class="flex items-center justify-between gap-4 pb-3 border-b border-base-300/50">
+                    <div class="flex items-center gap-3 flex-1 min-w-0">
+                        <div class="tooltip tooltip-right" data-tip="Back to planner">
+                            <A href="/planner" attr:class="btn btn-sm btn-soft btn-circle shrink-0">
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7" />
+                                </svg>
+                            </A>
+                        </div>
+                        <h1 class="text-2xl font-bold leading-tight text-base-content">"Degree Requirements"</h1>
+                    </div>
+                </div>
+
+                <div class="card bg-base-100 shadow-sm">
+                    <div class="card-body p-5 flex-row flex-wrap items-end gap-4">
+                        <label class="form-control flex-1 min-w-64">
+                            <span class="label-text text-sm mb-1">"Study program and StuPO"</span>
+                            <Suspense fallback=move || view! { <span class="loading loading-spinner loading-sm"></span> }>
+                                {move || Suspend::new(async move {
+                                    let options = stupo_options.await.unwrap_or_default();
+                                    view! {
+                                        <select
+                                            class="select select-sm w-full"
+                                            on:change=move |ev| {
+                                                selected_stupo.set(event_target_value(&ev).parse::<i32>().ok());
+                                            }
+                                        >
+                                            <option value="" selected=move || selected_stupo.get().is_none()>"Select a StuPO…"</option>
+                                            {options.into_iter().map(|option| {
+                                                let id = option.id;
+                                                view! {
+                                                    <option value=id.to_string() selected=move || selected_stupo.get() == Some(id)>
+                                                        {option.program_name} " – " {option.name}
+                                                    </option>
+                                                }
+                                            }).collect_view()}
+                                        </select>
+                                    }
+                                })}
+                            </Suspense>
+                        </label>
+                        <div class="join">
+                            <button
+                                class=move || if source.get() == AuditSource::Plan { "btn btn-sm join-item btn-primary" } else { "btn btn-sm join-item" }
+                                on:click=move |_| source.set(AuditSource::Plan)
+                            >
+                                "Study plan"
+                            </button>
+                            <button
+                                class=move || if source.get() == AuditSource::Starred { "btn btn-sm join-item btn-primary" } else { "btn btn-sm join-item" }
+                                on:click=move |_| source.set(AuditSource::Starred)
+                            >
+                                "Starred"
+                            </button>
+                        </div>
+                    </div>
+                </div>
+
+                <Suspense fallback=move || {
+                    view! {
+                        <div class="flex items-center justify-center py-12">
+                            <span class="loading loading-spinner loading-lg"></span>
+                        </div>
+                    }
+                }>
+                    {move || Suspend::new(async move {
+                        match audit.await {
+                            Ok(Some(audit)) => view! { <AuditView audit=audit /> }.into_any(),
+                            Ok(None) => {
+                                view! {
+                                    <div class="alert">
+                                        <span>"Select your study program and StuPO to check your modules against it."</span>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                            Err(e) => {
+                                view! {
+                                    <div class="alert alert-error">
+                                        <span>"Error running audit: " {e.to_string()}</span>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+#[component]
+fn AuditView(audit: DegreeAudit) -> impl IntoView {
+    if audit.areas.is_empty() {
+        return view! {
+            <div class="alert">
+                <span>"No catalog areas are known for this StuPO yet."</span>
+            </div>
+        }
+        .into_any();
+    }
+
+    let covered = audit.areas.iter().filter(|a| !a.modules.is_empty()).count();
+    let area_count = audit.areas.len();
+
+    view! {
+        <div class="flex items-center gap-2 flex-wrap">
+            <div class="badge badge-soft badge-primary badge-lg font-semibold">
+                {audit.total_credits} " CP in selection"
+            </div>
+            <div class="badge badge-soft badge-lg">
+                {covered} " of " {area_count} " areas covered"
+            </div>
+        </div>
+
+        <div class="card bg-base-100 shadow-sm">
+            <div class="card-body p-5 gap-4">
+                <h2 class="card-title text-lg text-primary">Catalog Areas</h2>
+                {audit.areas.into_iter().map(|area| view! { <AreaRow area=area /> }).collect_view()}
+            </div>
+        </div>
+
+        <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+            <ModuleListCard
+                title="Counted in Multiple Areas"
+                empty="No module is listed in more than one area."
+                modules=audit.shared_modules
+                show_areas=true
+            />
+            <ModuleListCard
+                title="Not in this StuPO"
+                empty="All selected modules are listed in this StuPO."
+                modules=audit.outside_modules
+                show_areas=false
+            />
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn AreaRow(area: AreaAudit) -> impl IntoView {
+    // The handbook covers every module of the area, not just the selected ones
+    let handbook_href = handbook_url(HandbookFormat::Html, None, &HandbookSource::Area(area.id));
+    let progress = area
+        .required_credits
+        .filter(|required| *required > 0)
+        .map(|required| (area.earned_credits * 100 / required).min(100));
+    let status = match area.missing_credits {
+        Some(0) => view! { <div class="badge badge-soft badge-success badge-sm">"Complete"</div> }.into_any(),
+        Some(missing) => view! { <div class="badge badge-soft badge-warning badge-sm">{missing} " CP missing"</div> }.into_any(),
+        None => view! { <div class="badge badge-soft badge-sm">"Requirement unknown"</div> }.into_any(),
+    };
+
+    view! {
+        <div class="flex flex-col gap-2 pb-4 border-b border-base-300/50 last:border-0 last:pb-0">
+            <div class="flex items-center justify-between gap-3 flex-wrap">
+                <h3 class="font-semibold text-base">{area.name}</h3>
+                <div class="flex items-center gap-2">
+                    <span class="text-sm text-base-content/60">
+                        {area.earned_credits}
+                        {area.required_credits.map(|required| format!(" / {}", required))}
+                        " CP"
+                    </span>
+                    {status}
+                    <a href=handbook_href target="_blank" class="btn btn-xs btn-soft">"Handbook"</a>
+                </div>
+            </div>
+            {progress.map(|value| view! {
+                <progress class="progress progress-primary w-full" value=value max="100"></progress>
+            })}
+            {if area.modules.is_empty() {
+                view! { <p class="text-sm text-base-content/50">"No selected module counts toward this area."</p> }.into_any()
+            } else {
+                view! { <AuditModuleLinks modules=area.modules show_areas=false /> }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn ModuleListCard(
+    title: &'static str,
+    empty: &'static str,
+    modules: Vec<AuditModule>,
+    show_areas: bool,
+) -> impl IntoView {
+    view! {
+        <div class="card bg-base-100 shadow-sm">
+            <div class="card-body p-5">
+                <h2 class="card-title text-lg text-primary">{title}</h2>
+                {if modules.is_empty() {
+                    view! { <p class="text-sm text-base-content/60">{empty}</p> }.into_any()
+                } else {
+                    view! { <AuditModuleLinks modules=modules show_areas=show_areas /> }.into_any()
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn AuditModuleLinks(modules: Vec<AuditModule>, show_areas: bool) -> impl IntoView {
+    view! {
+        <ul class="flex flex-col gap-1.5">
+            {modules.into_iter().map(|module| {
+                let href = format!("/module/{}/{}", module.id, module.version);
+                view! {
+                    <li class="flex flex-col text-sm">
+                        <div class="flex items-center gap-2">
+                            <A href=href attr:class="link link-hover font-medium">
+                                {module.title}
+                            </A>
+                            <span class="text-xs text-base-content/40 whitespace-nowrap">
+                                "#" {module.id} " · " {module.credits} " CP"
+                            </span>
+                        </div>
+                        {show_areas.then(|| view! {
+                            <span class="text-xs text-base-content/50">{module.areas.join(", ")}</span>
+                        })}
+                    </li>
+                }
+            }).collect_view()}
+        </ul>
+    }
+}
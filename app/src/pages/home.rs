@@ -1,9 +1,13 @@
-use crate::api::{get_filter_options, get_module_count, search_modules_paginated};
-use crate::components::{FilterPanel, ModuleDetailModal, ModuleList, PageLayout};
-use crate::models::ModuleSummary;
-use crate::starred::{is_starred, use_search_filters, use_starred_modules};
+use crate::api::{get_filter_facets, get_filter_options, get_module_count, get_shared_list, search_modules_paginated};
+use crate::collections::{add_collection, use_module_collections};
+use crate::compare::{compare_url, use_compare_modules, CompareModules, MAX_COMPARE};
+use crate::components::{ExportMenu, FilterPanel, ModuleDetailModal, ModuleList, PageLayout, QueryChips, SortSelect};
+use crate::models::{FilterFacets, ModuleSummary, SearchFilters, SharedList};
+use crate::starred::{use_search_filters, use_starred_modules};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_query_map};
 
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::JsCast;
@@ -18,6 +22,66 @@ pub fn HomePage() -> impl IntoView {
     // Starred modules (stored in local storage)
     let (starred, set_starred) = use_starred_modules();
 
+    // Named module collections (stored in local storage)
+    let (collections, set_collections) = use_module_collections();
+
+    // Modules picked for comparison (stored in local storage)
+    let (compare, set_compare) = use_compare_modules();
+
+    // Shared list opened via `?shared=<token>`, shown instead of the starred or collection filter
+    let query = use_query_map();
+    let shared_token = Memo::new(move |_| query.read().get("shared"));
+    let shared_list = RwSignal::new(None::<Result<SharedList, String>>);
+    Effect::new(move || {
+        let Some(token) = shared_token.get() else {
+            shared_list.set(None);
+            return;
+        };
+        spawn_local(async move {
+            shared_list.set(Some(get_shared_list(token).await.map_err(|e| e.to_string())));
+        });
+    });
+
+    // Filters sent to the server, with starred, collection and shared lists resolved to module keys
+    let query_filters = Memo::new(move |_| {
+        // A shared list is shown as is, regardless of the saved filters except the order
+        if shared_token.get().is_some() {
+            let keys = match shared_list.get() {
+                Some(Ok(list)) => list.entries.iter().map(|e| (e.id, e.version)).collect(),
+                _ => Vec::new(),
+            };
+            return SearchFilters {
+                module_keys: Some(keys),
+                include_discontinued: true,
+                sort: filters.get().sort,
+                ..Default::default()
+            };
+        }
+
+        let mut current_filters = filters.get();
+        let mut keys: Option<Vec<(i32, i32)>> = None;
+
+        if current_filters.starred_only {
+            let mut starred_keys: Vec<(i32, i32)> = starred.get().modules.into_iter().collect();
+            starred_keys.sort();
+            keys = Some(starred_keys);
+        }
+
+        // A deleted collection no longer filters anything
+        if let Some(collection) = current_filters.collection_id.and_then(|id| collections.get().get(id).cloned()) {
+            keys = Some(match keys {
+                Some(keys) => keys.into_iter().filter(|&(id, version)| collection.contains(id, version)).collect(),
+                None => collection.keys(),
+            });
+        }
+
+        current_filters.module_keys = keys;
+        current_filters
+    });
+
+    // Feedback after importing the shared list
+    let shared_imported = RwSignal::new(None::<&'static str>);
+
     // Modal state for module details
     let selected_module = RwSignal::new(None::<(i32, i32)>);
 
@@ -25,31 +89,15 @@ pub fn HomePage() -> impl IntoView {
     let filter_options = Resource::new(|| (), |_| async { get_filter_options().await });
 
     // Accumulated modules (all fetched from server)
-    let all_modules = RwSignal::new(Vec::<ModuleSummary>::new());
-
-    // Filtered modules (client-side filtering for starred)
     let modules = RwSignal::new(Vec::<ModuleSummary>::new());
 
-    // Update filtered modules when all_modules or starred filter changes
-    Effect::new(move || {
-        let mods = all_modules.get();
-        let starred_only = filters.get().starred_only;
-
-        let filtered = if starred_only {
-            let starred_data = starred.get();
-            mods.into_iter()
-                .filter(|m| is_starred(&starred_data, m.id, m.version))
-                .collect()
-        } else {
-            mods
-        };
-
-        modules.set(filtered);
-    });
-
     let is_loading = RwSignal::new(false);
     let has_more = RwSignal::new(true);
     let total_count = RwSignal::new(0_i64);
+    // Cursor of the next page, `None` before the first page
+    let next_cursor = RwSignal::new(None::<String>);
+    // Matches per filter option under the current filters
+    let facets = RwSignal::new(None::<FilterFacets>);
 
     // Load next page
     let load_next_page = move || {
@@ -59,17 +107,35 @@ pub fn HomePage() -> impl IntoView {
             }
 
             is_loading.set(true);
-            let current_filters = filters.get_untracked();
-            let current_page = (all_modules.with_untracked(|m| m.len()) as i64) / PAGE_SIZE;
+            let current_filters = query_filters.get_untracked();
+            let cursor = next_cursor.get_untracked();
+
+            match search_modules_paginated(current_filters.clone(), cursor.clone(), PAGE_SIZE).await {
+                Ok(page) => {
+                    // The filters changed while loading, this page belongs to the old search
+                    if query_filters.get_untracked() != current_filters {
+                        return;
+                    }
+
+                    // Count against the same snapshot as the pages that follow
+                    if cursor.is_none() {
+                        let snapshot = page.snapshot;
+                        spawn_local(async move {
+                            match get_module_count(current_filters.clone(), snapshot).await {
+                                Ok(count) if query_filters.get_untracked() == current_filters => total_count.set(count),
+                                Ok(_) => {}
+                                Err(e) => leptos::logging::error!("Error fetching count: {:?}", e),
+                            }
+                        });
+                    }
 
-            match search_modules_paginated(current_filters, current_page, PAGE_SIZE).await {
-                Ok(new_modules) => {
-                    let count = new_modules.len();
+                    let count = page.modules.len();
 
-                    // Update all_modules
-                    all_modules.update(|m| m.extend(new_modules));
+                    // Update modules
+                    modules.update(|m| m.extend(page.modules));
+                    next_cursor.set(page.next_cursor.clone());
 
-                    if count < PAGE_SIZE as usize {
+                    if page.next_cursor.is_none() {
                         has_more.set(false);
                     }
 
@@ -88,21 +154,21 @@ pub fn HomePage() -> impl IntoView {
         });
     };
 
-    // Fetch count and reset when filters change (excluding starred_only which is client-side)
+    // Reset when filters, stars or collections change, the first page fetches the count
     Effect::new(move || {
-        let current_filters = filters.get();
+        let facet_filters = query_filters.get();
 
-        // Only reset and fetch if non-starred filters changed
-        // starred_only is handled client-side via the memo
-        all_modules.set(Vec::new());
+        modules.set(Vec::new());
+        next_cursor.set(None);
         has_more.set(true);
         is_loading.set(false);
 
-        // Fetch total count
+        // Fetch facet counts, keeping the previous ones visible until they arrive
         spawn_local(async move {
-            match get_module_count(current_filters.clone()).await {
-                Ok(count) => total_count.set(count),
-                Err(e) => leptos::logging::error!("Error fetching count: {:?}", e),
+            match get_filter_facets(facet_filters.clone()).await {
+                Ok(counts) if query_filters.get_untracked() == facet_filters => facets.set(Some(counts)),
+                Ok(_) => {}
+                Err(e) => leptos::logging::error!("Error fetching facet counts: {:?}", e),
             }
         });
 
@@ -166,6 +232,8 @@ pub fn HomePage() -> impl IntoView {
                                 <FilterPanel
                                     filters=filters
                                     filter_options=filter_options.get().into()
+                                    collections=collections
+                                    facets=facets.into()
                                 />
                             }}
                         </Suspense>
@@ -227,14 +295,125 @@ pub fn HomePage() -> impl IntoView {
                                     </div>
                                 }
                             }}
+                            <div class="flex items-center gap-2">
+                                <SortSelect filters=filters />
+                                <ExportMenu filters=query_filters />
+                                {move || {
+                                    let mut starred_keys: Vec<(i32, i32)> = starred.get().modules.into_iter().collect();
+                                    starred_keys.sort();
+                                    if filters.get().starred_only && starred_keys.len() >= 2 {
+                                        starred_keys.truncate(MAX_COMPARE);
+                                        view! {
+                                            <A href=compare_url(&starred_keys) attr:class="btn btn-sm btn-soft">
+                                                "Compare starred"
+                                            </A>
+                                        }.into_any()
+                                    } else {
+                                        view! { <></> }.into_any()
+                                    }
+                                }}
+                                {move || {
+                                    let keys = compare.get().modules;
+                                    if keys.is_empty() {
+                                        view! { <></> }.into_any()
+                                    } else {
+                                        view! {
+                                            <div class="join">
+                                                <A href=compare_url(&keys) attr:class="btn btn-sm btn-soft btn-primary join-item">
+                                                    "Compare (" {keys.len()} ")"
+                                                </A>
+                                                <button
+                                                    class="btn btn-sm btn-soft join-item"
+                                                    on:click=move |_| set_compare.set(CompareModules::default())
+                                                >
+                                                    "Clear"
+                                                </button>
+                                            </div>
+                                        }.into_any()
+                                    }
+                                }}
+                            </div>
                         </div>
                     </div>
 
+                    // Filters typed into the search box
+                    <QueryChips filters=filters />
+
+                    // Shared list banner
+                    {move || shared_token.get().map(|_| match shared_list.get() {
+                        None => view! {
+                            <div class="alert alert-soft mb-4">
+                                <span class="loading loading-spinner loading-xs"></span>
+                                <span>"Loading shared list..."</span>
+                            </div>
+                        }.into_any(),
+                        Some(Err(e)) => view! {
+                            <div class="alert alert-error alert-soft mb-4">
+                                <span>"This shared list could not be opened: " {e}</span>
+                                <A href="/" attr:class="btn btn-sm btn-ghost">"Close"</A>
+                            </div>
+                        }.into_any(),
+                        Some(Ok(list)) => {
+                            let count = list.entries.len();
+                            let keys: Vec<(i32, i32)> = list.entries.iter().map(|e| (e.id, e.version)).collect();
+                            let list = StoredValue::new(list);
+                            let navigate = use_navigate();
+                            view! {
+                                <div class="alert alert-info alert-soft flex-wrap mb-4">
+                                    <div class="flex flex-col flex-1 min-w-0">
+                                        <span class="font-semibold truncate">
+                                            "Shared list: " {list.with_value(|l| l.name.clone())}
+                                        </span>
+                                        <span class="text-xs">
+                                            {count} " modules · "
+                                            {move || shared_imported.get().unwrap_or("read-only, import it to make changes")}
+                                        </span>
+                                    </div>
+                                    <div class="flex items-center gap-2">
+                                        <button
+                                            class="btn btn-sm btn-soft"
+                                            on:click=move |_| {
+                                                let keys = keys.clone();
+                                                set_starred.update(|s| s.modules.extend(keys));
+                                                shared_imported.set(Some("added to your starred modules"));
+                                            }
+                                        >
+                                            "Star all"
+                                        </button>
+                                        <button
+                                            class="btn btn-sm btn-soft btn-primary"
+                                            on:click=move |_| {
+                                                let (name, entries) = list.with_value(|l| (l.name.clone(), l.entries.clone()));
+                                                set_collections.set(add_collection(&collections.get_untracked(), &name, entries));
+                                                shared_imported.set(Some("saved as a collection"));
+                                            }
+                                        >
+                                            "Save as collection"
+                                        </button>
+                                        <button
+                                            class="btn btn-sm btn-ghost"
+                                            on:click=move |_| {
+                                                shared_imported.set(None);
+                                                navigate("/", Default::default());
+                                            }
+                                        >
+                                            "Close"
+                                        </button>
+                                    </div>
+                                </div>
+                            }.into_any()
+                        }
+                    })}
+
                     // Module list
                     <ModuleList
                         modules=modules
                         starred=starred
                         set_starred=set_starred
+                        collections=collections
+                        set_collections=set_collections
+                        compare=compare
+                        set_compare=set_compare
                         on_module_click=move |id, version| selected_module.set(Some((id, version)))
                         is_loading=is_loading.into()
                     />
@@ -260,6 +439,8 @@ pub fn HomePage() -> impl IntoView {
                                 <FilterPanel
                                     filters=filters
                                     filter_options=filter_options.get().into()
+                                    collections=collections
+                                    facets=facets.into()
                                 />
                             }}
                         </Suspense>
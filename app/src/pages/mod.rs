@@ -1,7 +1,21 @@
+pub mod collections;
+pub mod compare;
+pub mod degree_audit;
 pub mod home;
 pub mod module_detail;
+pub mod module_graph;
+pub mod notifications;
+pub mod planner;
 pub mod scraper;
+pub mod sync;
 
+pub use collections::*;
+pub use compare::*;
+pub use degree_audit::*;
 pub use home::*;
 pub use module_detail::*;
+pub use module_graph::*;
+pub use notifications::*;
+pub use planner::*;
 pub use scraper::*;
+pub use sync::*;
@@ -0,0 +1,201 @@
+use crate::api::get_module_graph;
+use crate::components::layout::PageLayout;
+use crate::graph::render_graph_svg;
+use crate::models::{GraphNode, ModuleGraph};
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::use_params;
+use leptos_router::params::Params;
+
+#[derive(Params, PartialEq, Clone)]
+struct ModuleGraphParams {
+    id: i32,
+    version: i32,
+}
+
+#[component]
+pub fn ModuleGraphPage() -> impl IntoView {
+    let params = use_params::<ModuleGraphParams>();
+
+    let graph_data = Resource::new(
+        move || params.get(),
+        |params_result| async move {
+            match params_result {
+                Ok(p) => get_module_graph(p.id, p.version).await,
+                Err(e) => Err(ServerFnError::new(e.to_string())),
+            }
+        },
+    );
+
+    view! {
+        <PageLayout>
+            <div class="py-8 flex flex-col gap-6">
+                <Suspense fallback=move || {
+                    view! {
+                        <div class="flex items-center justify-center py-12">
+                            <span class="loading loading-spinner loading-lg"></span>
+                        </div>
+                    }
+                }>
+                    {move || Suspend::new(async move {
+                        match graph_data.await {
+                            Ok(graph) => view! { <ModuleGraphView graph=graph /> }.into_any(),
+                            Err(e) => {
+                                view! {
+                                    <div class="alert alert-error">
+                                        <span>"Error loading prerequisite graph: " {e.to_string()}</span>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+#[component]
+fn ModuleGraphView(graph: ModuleGraph) -> impl IntoView {
+    let module_href = format!("/module/{}/{}", graph.root_id, graph.root_version);
+    let export_base = format!("/api/modules/{}/{}/graph", graph.root_id, graph.root_version);
+    let title = graph
+        .nodes
+        .iter()
+        .find(|n| n.id == graph.root_id)
+        .map(|n| n.title.clone())
+        .unwrap_or_default();
+    let svg = render_graph_svg(&graph);
+
+    // Prerequisites grouped by layer give a suggested order to take them in
+    let mut steps: Vec<Vec<GraphNode>> = Vec::new();
+    let mut unlocks: Vec<GraphNode> = Vec::new();
+    for node in &graph.nodes {
+        if node.layer < 0 {
+            match steps.last_mut() {
+                Some(step) if step[0].layer == node.layer => step.push(node.clone()),
+                _ => steps.push(vec![node.clone()]),
+            }
+        } else if node.layer > 0 {
+            unlocks.push(node.clone());
+        }
+    }
+    let has_relations = graph.nodes.len() > 1;
+
+    view! {
+        <div class="flex items-center justify-between gap-4 pb-3 border-b border-base-300/50">
+            <div class="flex items-center gap-3 flex-1 min-w-0">
+                <div class="tooltip tooltip-right" data-tip="Back to module">
+                    <A href=module_href attr:class="btn btn-sm btn-soft btn-circle shrink-0">
+                        <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7" />
+                        </svg>
+                    </A>
+                </div>
+                <div class="flex items-baseline gap-3 flex-wrap flex-1 min-w-0">
+                    <h1 class="text-2xl font-bold leading-tight text-base-content">{title}</h1>
+                    <span class="text-sm text-base-content/50 whitespace-nowrap">
+                        "Prerequisite graph · Module " {graph.root_id} " · v" {graph.root_version}
+                    </span>
+                </div>
+            </div>
+            <div class="flex items-center gap-2 shrink-0">
+                <a href=format!("{}.svg", export_base) target="_blank" rel="noopener noreferrer" class="btn btn-sm btn-soft">
+                    "SVG"
+                </a>
+                <a href=format!("{}.json", export_base) target="_blank" rel="noopener noreferrer" class="btn btn-sm btn-soft">
+                    "JSON"
+                </a>
+            </div>
+        </div>
+
+        {if has_relations {
+            view! {
+                <div class="card bg-base-100 shadow-sm">
+                    <div class="card-body p-5 gap-3">
+                        <div class="flex items-center gap-4 text-xs text-base-content/60">
+                            <span class="flex items-center gap-1.5">
+                                <span class="inline-block w-6 border-t-2 border-warning"></span>
+                                "Mandatory"
+                            </span>
+                            <span class="flex items-center gap-1.5">
+                                <span class="inline-block w-6 border-t-2 border-dashed border-info"></span>
+                                "Recommended"
+                            </span>
+                        </div>
+                        <div class="overflow-auto" inner_html=svg></div>
+                    </div>
+                </div>
+
+                <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                    <div class="card bg-base-100 shadow-sm">
+                        <div class="card-body p-5">
+                            <h2 class="card-title text-lg text-primary">Suggested Order</h2>
+                            {if steps.is_empty() {
+                                view! {
+                                    <p class="text-sm text-base-content/60">"No known prerequisites."</p>
+                                }.into_any()
+                            } else {
+                                view! {
+                                    <ol class="flex flex-col gap-3">
+                                        {steps.into_iter().enumerate().map(|(index, step)| {
+                                            view! {
+                                                <li>
+                                                    <div class="text-xs font-semibold text-base-content/50 mb-1">
+                                                        "Step " {index + 1}
+                                                    </div>
+                                                    <GraphNodeLinks nodes=step />
+                                                </li>
+                                            }
+                                        }).collect_view()}
+                                    </ol>
+                                }.into_any()
+                            }}
+                        </div>
+                    </div>
+                    <div class="card bg-base-100 shadow-sm">
+                        <div class="card-body p-5">
+                            <h2 class="card-title text-lg text-primary">Unlocks</h2>
+                            {if unlocks.is_empty() {
+                                view! {
+                                    <p class="text-sm text-base-content/60">"No modules build on this one."</p>
+                                }.into_any()
+                            } else {
+                                view! { <GraphNodeLinks nodes=unlocks /> }.into_any()
+                            }}
+                        </div>
+                    </div>
+                </div>
+            }.into_any()
+        } else {
+            view! {
+                <div class="alert">
+                    <span>"No prerequisite relations are known for this module."</span>
+                </div>
+            }.into_any()
+        }}
+    }
+}
+
+/// Linked list of graph nodes
+#[component]
+fn GraphNodeLinks(nodes: Vec<GraphNode>) -> impl IntoView {
+    view! {
+        <ul class="flex flex-col gap-1.5">
+            {nodes.into_iter().map(|node| {
+                let href = format!("/module/{}/{}", node.id, node.version);
+                view! {
+                    <li class="flex items-center gap-2 text-sm">
+                        <A href=href attr:class="link link-hover font-medium">
+                            {node.title}
+                        </A>
+                        <span class="text-xs text-base-content/40 whitespace-nowrap">
+                            "#" {node.id} " · " {node.credits} " CP"
+                        </span>
+                    </li>
+                }
+            }).collect_view()}
+        </ul>
+    }
+}
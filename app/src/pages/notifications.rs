@@ -0,0 +1,159 @@
+use crate::api::subscribe_notifications;
+use crate::components::layout::PageLayout;
+use crate::planner::percent_encode;
+use crate::starred::use_starred_modules;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::components::A;
+use leptos_router::hooks::use_query_map;
+
+#[component]
+pub fn NotificationsPage() -> impl IntoView {
+    let (starred, _set_starred) = use_starred_modules();
+    let query = use_query_map();
+
+    let email = RwSignal::new(String::new());
+    let (submitting, set_submitting) = signal(false);
+    let (result, set_result) = signal(None::<Result<String, String>>);
+
+    let starred_count = move || starred.get().modules.len();
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let mut modules: Vec<(i32, i32)> = starred.get_untracked().modules.into_iter().collect();
+        modules.sort();
+        let address = email.get_untracked();
+
+        spawn_local(async move {
+            set_submitting.set(true);
+            set_result.set(None);
+
+            match subscribe_notifications(address.clone(), modules).await {
+                Ok(()) => set_result.set(Some(Ok(format!(
+                    "We sent a confirmation link to {}. Notifications start once you confirm.",
+                    address
+                )))),
+                Err(e) => set_result.set(Some(Err(e.to_string()))),
+            }
+
+            set_submitting.set(false);
+        });
+    };
+
+    view! {
+        <PageLayout>
+            <div class="py-8 flex flex-col gap-6 max-w-2xl">
+                <div class="flex items-center justify-between gap-4 pb-3 border-b border-base-300/50">
+                    <div class="flex items-center gap-3 flex-1 min-w-0">
+                        <div class="tooltip tooltip-right" data-tip="Back to search">
+                            <A href="/" attr:class="btn btn-sm btn-soft btn-circle shrink-0">
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7" />
+                                </svg>
+                            </A>
+                        </div>
+                        <h1 class="text-2xl font-bold leading-tight text-base-content">"Email Notifications"</h1>
+                    </div>
+                </div>
+
+                // Links from emails land here, only the buttons change anything
+                {move || {
+                    let query = query.read();
+                    if let Some(token) = query.get("confirm") {
+                        let action = format!("/notifications/confirm/{}", percent_encode(&token));
+                        return view! {
+                            <div class="card bg-base-100 shadow-sm">
+                                <form class="card-body p-5 gap-3" method="post" action=action>
+                                    <h2 class="card-title text-lg text-primary">"Confirm your subscription"</h2>
+                                    <p class="text-sm text-base-content/70">
+                                        "Confirm to get an email when one of the modules you chose changes. "
+                                        "An existing subscription of your address follows these modules from now on."
+                                    </p>
+                                    <div class="card-actions justify-end">
+                                        <button type="submit" class="btn btn-sm btn-primary">"Confirm"</button>
+                                    </div>
+                                </form>
+                            </div>
+                        }.into_any();
+                    }
+                    if let Some(token) = query.get("unsubscribe") {
+                        let action = format!("/notifications/unsubscribe/{}", percent_encode(&token));
+                        return view! {
+                            <div class="card bg-base-100 shadow-sm">
+                                <form class="card-body p-5 gap-3" method="post" action=action>
+                                    <h2 class="card-title text-lg text-primary">"Unsubscribe"</h2>
+                                    <p class="text-sm text-base-content/70">
+                                        "Stop all notification emails to your address."
+                                    </p>
+                                    <div class="card-actions justify-end">
+                                        <button type="submit" class="btn btn-sm btn-error">"Unsubscribe"</button>
+                                    </div>
+                                </form>
+                            </div>
+                        }.into_any();
+                    }
+                    match query.get("status").as_deref() {
+                        Some("confirmed") => view! {
+                            <div class="alert alert-success alert-soft">
+                                <span>"Your address is confirmed. You will get a digest after each scraping run that changes one of your modules."</span>
+                            </div>
+                        }.into_any(),
+                        Some("unsubscribed") => view! {
+                            <div class="alert alert-soft">
+                                <span>"You have been unsubscribed and will not get any more emails."</span>
+                            </div>
+                        }.into_any(),
+                        Some("invalid") => view! {
+                            <div class="alert alert-error alert-soft">
+                                <span>"This link is no longer valid."</span>
+                            </div>
+                        }.into_any(),
+                        _ => view! { <></> }.into_any(),
+                    }
+                }}
+
+                <div class="card bg-base-100 shadow-sm">
+                    <form class="card-body p-5 gap-4" on:submit=on_submit>
+                        <h2 class="card-title text-lg text-primary">"Follow your starred modules"</h2>
+                        <p class="text-sm text-base-content/70">
+                            "Get an email when the exam form, components, rotation or description of a starred module changes, "
+                            "when it disappears from MOSES, or when a new version is published. "
+                            "Subscribing again replaces the followed modules with your current stars once you confirm."
+                        </p>
+                        <div class="badge badge-soft badge-primary">
+                            {starred_count} " starred modules"
+                        </div>
+                        <label class="form-control w-full">
+                            <span class="label-text text-sm mb-1">"Email address"</span>
+                            <input
+                                type="email"
+                                required
+                                placeholder="you@campus.tu-berlin.de"
+                                class="input input-sm w-full"
+                                prop:value=move || email.get()
+                                on:input=move |ev| email.set(event_target_value(&ev))
+                            />
+                        </label>
+                        <div class="card-actions justify-end">
+                            <button
+                                type="submit"
+                                class="btn btn-sm btn-primary"
+                                disabled=move || submitting.get() || starred_count() == 0
+                            >
+                                {move || if submitting.get() { "Subscribing…" } else { "Subscribe" }}
+                            </button>
+                        </div>
+                        {move || result.get().map(|result| match result {
+                            Ok(message) => view! {
+                                <div class="alert alert-success alert-soft"><span>{message}</span></div>
+                            }.into_any(),
+                            Err(message) => view! {
+                                <div class="alert alert-error alert-soft"><span>{message}</span></div>
+                            }.into_any(),
+                        })}
+                    </form>
+                </div>
+            </div>
+        </PageLayout>
+    }
+}
@@ -0,0 +1,287 @@
+use crate::api::get_modules_by_keys;
+use crate::components::layout::PageLayout;
+use crate::models::ModuleSummary;
+use crate::planner::{
+    add_semester, assign_module, data_url, module_sws, plan_to_csv, planned_term, remove_semester,
+    unavailable_components, use_study_plan, PlannedSemester, StudyPlan, Term,
+};
+use crate::starred::use_starred_modules;
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+#[component]
+pub fn PlannerPage() -> impl IntoView {
+    let (starred, _set_starred) = use_starred_modules();
+    let (plan, set_plan) = use_study_plan();
+
+    // Only the starred set decides what to load, so editing the plan does not refetch
+    let starred_keys = Memo::new(move |_| {
+        let mut keys: Vec<(i32, i32)> = starred.get().modules.into_iter().collect();
+        keys.sort();
+        keys
+    });
+
+    // Plan and starred set only exist in local storage, so load on the client
+    let modules = LocalResource::new(move || {
+        let mut keys = starred_keys.get();
+        // Planned modules may have been unstarred, but the plan only gains starred ones
+        for semester in plan.get_untracked().semesters {
+            for key in semester.modules {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+        async move { get_modules_by_keys(keys).await }
+    });
+
+    view! {
+        <PageLayout>
+            <div class="py-8 flex flex-col gap-6">
+                <div class="flex items-center justify-between gap-4 pb-3 border-b border-base-300/50">
+                    <div class="flex items-center gap-3 flex-1 min-w-0">
+                        <div class="tooltip tooltip-right" data-tip="Back to search">
+                            <A href="/" attr:class="btn btn-sm btn-soft btn-circle shrink-0">
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7" />
+                                </svg>
+                            </A>
+                        </div>
+                        <h1 class="text-2xl font-bold leading-tight text-base-content">"Study Planner"</h1>
+                    </div>
+                </div>
+
+                <Suspense fallback=move || {
+                    view! {
+                        <div class="flex items-center justify-center py-12">
+                            <span class="loading loading-spinner loading-lg"></span>
+                        </div>
+                    }
+                }>
+                    {move || Suspend::new(async move {
+                        match modules.await {
+                            Ok(modules) => {
+                                view! { <PlannerBoard modules=modules plan=plan set_plan=set_plan /> }.into_any()
+                            }
+                            Err(e) => {
+                                view! {
+                                    <div class="alert alert-error">
+                                        <span>"Error loading modules: " {e.to_string()}</span>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+#[component]
+fn PlannerBoard(
+    modules: Vec<ModuleSummary>,
+    plan: Signal<StudyPlan>,
+    set_plan: WriteSignal<StudyPlan>,
+) -> impl IntoView {
+    let modules = StoredValue::new(modules);
+
+    let total_credits = move || {
+        let plan = plan.get();
+        modules.with_value(|modules| {
+            modules
+                .iter()
+                .filter(|m| planned_term(&plan, m.id, m.version).is_some())
+                .map(|m| m.credits)
+                .sum::<i32>()
+        })
+    };
+
+    let json_href = move || {
+        data_url(
+            "application/json",
+            &serde_json::to_string_pretty(&plan.get()).unwrap_or_default(),
+        )
+    };
+    let csv_href = move || modules.with_value(|modules| data_url("text/csv", &plan_to_csv(&plan.get(), modules)));
+
+    let unassigned = move || {
+        let plan = plan.get();
+        modules.with_value(|modules| {
+            modules
+                .iter()
+                .filter(|m| planned_term(&plan, m.id, m.version).is_none())
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! {
+        <div class="flex items-center justify-between gap-4 flex-wrap">
+            <div class="badge badge-soft badge-primary badge-lg font-semibold">
+                {total_credits} " CP planned"
+            </div>
+            <div class="flex items-center gap-2">
+                <button
+                    class="btn btn-sm btn-soft btn-primary"
+                    on:click=move |_| set_plan.set(add_semester(&plan.get_untracked()))
+                >
+                    "Add semester"
+                </button>
+                <A href="/audit" attr:class="btn btn-sm btn-soft">"Check requirements"</A>
+                <A href="/collections" attr:class="btn btn-sm btn-soft">"Collections"</A>
+                <A href="/notifications" attr:class="btn btn-sm btn-soft">"Email alerts"</A>
+                <A href="/sync" attr:class="btn btn-sm btn-soft">"Sync devices"</A>
+                <a href=json_href download="study-plan.json" class="btn btn-sm btn-soft">"Export JSON"</a>
+                <a href=csv_href download="study-plan.csv" class="btn btn-sm btn-soft">"Export CSV"</a>
+            </div>
+        </div>
+
+        <div class="grid grid-cols-1 lg:grid-cols-[380px_1fr] gap-6">
+            <div class="card bg-base-100 shadow-sm h-fit">
+                <div class="card-body p-5 gap-3">
+                    <h2 class="card-title text-lg text-primary">Unassigned</h2>
+                    {move || {
+                        let unassigned = unassigned();
+                        if unassigned.is_empty() {
+                            view! {
+                                <p class="text-sm text-base-content/60">
+                                    "Star modules in the search to plan them here."
+                                </p>
+                            }.into_any()
+                        } else {
+                            unassigned.into_iter().map(|module| {
+                                view! { <PlannedModuleRow module=module term=None plan=plan set_plan=set_plan /> }
+                            }).collect_view().into_any()
+                        }
+                    }}
+                </div>
+            </div>
+
+            <div class="flex flex-col gap-4">
+                {move || {
+                    let semesters = plan.get().semesters;
+                    if semesters.is_empty() {
+                        view! {
+                            <div class="alert">
+                                <span>"No semesters yet. Add one to start planning."</span>
+                            </div>
+                        }.into_any()
+                    } else {
+                        semesters.into_iter().map(|semester| {
+                            let planned: Vec<ModuleSummary> = modules.with_value(|modules| {
+                                semester.modules.iter()
+                                    .filter_map(|&(id, version)| modules.iter().find(|m| m.id == id && m.version == version).cloned())
+                                    .collect()
+                            });
+                            view! { <SemesterCard semester=semester modules=planned plan=plan set_plan=set_plan /> }
+                        }).collect_view().into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn SemesterCard(
+    semester: PlannedSemester,
+    modules: Vec<ModuleSummary>,
+    plan: Signal<StudyPlan>,
+    set_plan: WriteSignal<StudyPlan>,
+) -> impl IntoView {
+    let term = semester.term;
+    let credits: i32 = modules.iter().map(|m| m.credits).sum();
+    let sws: i32 = modules.iter().map(module_sws).sum();
+
+    view! {
+        <div class="card bg-base-100 shadow-sm">
+            <div class="card-body p-5 gap-3">
+                <div class="flex items-center justify-between gap-3">
+                    <h2 class="card-title text-lg text-primary">{term.label()}</h2>
+                    <div class="flex items-center gap-2">
+                        <div class="badge badge-soft badge-primary">{credits} " CP"</div>
+                        <div class="badge badge-soft badge-secondary">{sws} " SWS"</div>
+                        <div class="tooltip tooltip-left" data-tip="Remove semester">
+                            <button
+                                class="btn btn-ghost btn-xs btn-circle"
+                                on:click=move |_| set_plan.set(remove_semester(&plan.get_untracked(), term))
+                            >
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M6 18L18 6M6 6l12 12" />
+                                </svg>
+                            </button>
+                        </div>
+                    </div>
+                </div>
+                {if modules.is_empty() {
+                    view! {
+                        <p class="text-sm text-base-content/60">"No modules planned for this semester."</p>
+                    }.into_any()
+                } else {
+                    modules.into_iter().map(|module| {
+                        view! { <PlannedModuleRow module=module term=Some(term) plan=plan set_plan=set_plan /> }
+                    }).collect_view().into_any()
+                }}
+            </div>
+        </div>
+    }
+}
+
+/// A module with a semester picker and a warning if it is not offered in its semester
+#[component]
+fn PlannedModuleRow(
+    module: ModuleSummary,
+    term: Option<Term>,
+    plan: Signal<StudyPlan>,
+    set_plan: WriteSignal<StudyPlan>,
+) -> impl IntoView {
+    let (id, version) = (module.id, module.version);
+    let href = format!("/module/{}/{}", id, version);
+    let unavailable = term
+        .map(|t| unavailable_components(&module, t.season))
+        .unwrap_or_default();
+    let sws = module_sws(&module);
+    let current_label = term.map(|t| t.label()).unwrap_or_default();
+
+    view! {
+        <div class="flex items-center justify-between gap-3 py-1.5 border-b border-base-300/50 last:border-0">
+            <div class="flex flex-col min-w-0">
+                <A href=href attr:class="link link-hover font-medium text-sm truncate">
+                    {module.title}
+                </A>
+                <div class="flex items-center gap-2 text-xs text-base-content/50">
+                    <span>"#" {id} " v" {version}</span>
+                    <span>{module.credits} " CP · " {sws} " SWS"</span>
+                    {if unavailable.is_empty() {
+                        view! { <></> }.into_any()
+                    } else {
+                        let tip = format!("Not offered in this semester: {}", unavailable.join(", "));
+                        view! {
+                            <div class="tooltip tooltip-right" data-tip=tip>
+                                <div class="badge badge-soft badge-warning badge-xs">"Rotation"</div>
+                            </div>
+                        }.into_any()
+                    }}
+                </div>
+            </div>
+            <select
+                class="select select-xs w-36 shrink-0"
+                on:change=move |ev| {
+                    let value = event_target_value(&ev);
+                    let current = plan.get_untracked();
+                    let target = current.semesters.iter().map(|s| s.term).find(|t| t.label() == value);
+                    set_plan.set(assign_module(&current, id, version, target));
+                }
+            >
+                <option value="" selected=term.is_none()>"Unassigned"</option>
+                {move || plan.get().semesters.into_iter().map(|semester| {
+                    let label = semester.term.label();
+                    let selected = label == current_label;
+                    view! { <option value=label.clone() selected=selected>{label}</option> }
+                }).collect_view()}
+            </select>
+        </div>
+    }
+}
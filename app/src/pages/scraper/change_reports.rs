@@ -0,0 +1,175 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+use crate::api::{get_change_report, get_change_reports};
+use crate::components::module_detail::FieldChangeRow;
+use crate::models::{ChangeReport, ChangeReportEntry, ChangeReportSummary};
+
+/// Recent change reports, reloaded whenever the page returns from a running scrape
+#[component]
+pub fn ChangeReports() -> impl IntoView {
+    let reports = Resource::new(|| (), |_| get_change_reports());
+    let selected_run = RwSignal::new(None::<i32>);
+
+    view! {
+        <div class="card bg-base-100 shadow-xl">
+            <div class="card-body">
+                <h2 class="card-title">
+                    <svg xmlns="http://www.w3.org/2000/svg" class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 5H7a2 2 0 00-2 2v12a2 2 0 002 2h10a2 2 0 002-2V7a2 2 0 00-2-2h-2M9 5a2 2 0 002 2h2a2 2 0 002-2M9 5a2 2 0 012-2h2a2 2 0 012 2m-6 9l2 2 4-4" />
+                    </svg>
+                    "Change Reports"
+                </h2>
+                <p class="text-sm text-base-content/60">
+                    "Each completed run is compared to the previous completed run."
+                </p>
+
+                <Suspense fallback=move || view! { <span class="loading loading-spinner loading-md"></span> }>
+                    {move || Suspend::new(async move {
+                        match reports.await {
+                            Ok(reports) if reports.is_empty() => {
+                                view! {
+                                    <p class="text-sm text-base-content/60">"No change reports yet."</p>
+                                }
+                                .into_any()
+                            }
+                            Ok(reports) => {
+                                view! {
+                                    <div class="overflow-x-auto">
+                                        <table class="table table-sm">
+                                            <thead>
+                                                <tr>
+                                                    <th>"Run"</th>
+                                                    <th>"Completed"</th>
+                                                    <th>"New"</th>
+                                                    <th>"Removed"</th>
+                                                    <th>"Changed"</th>
+                                                    <th></th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {reports.into_iter().map(|report| {
+                                                    view! { <ReportRow report=report selected_run=selected_run /> }
+                                                }).collect_view()}
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                            Err(e) => {
+                                view! {
+                                    <div class="alert alert-error">
+                                        <span>"Error loading change reports: " {e.to_string()}</span>
+                                    </div>
+                                }
+                                .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+
+                {move || selected_run.get().map(|run_id| view! { <ReportDetails run_id=run_id /> })}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ReportRow(report: ChangeReportSummary, selected_run: RwSignal<Option<i32>>) -> impl IntoView {
+    let run_id = report.run_id;
+    let completed = report
+        .completed_at
+        .map(|at| at.format("%d.%m.%Y %H:%M").to_string())
+        .unwrap_or_default();
+
+    view! {
+        <tr class=move || if selected_run.get() == Some(run_id) { "bg-base-200" } else { "" }>
+            <td>"#" {run_id} <span class="text-base-content/50">" vs #" {report.previous_run_id}</span></td>
+            <td>{completed}</td>
+            <td class="text-success font-semibold">{report.added}</td>
+            <td class="text-error font-semibold">{report.removed}</td>
+            <td class="text-warning font-semibold">{report.changed}</td>
+            <td class="text-right whitespace-nowrap">
+                <button
+                    class="btn btn-xs btn-soft btn-primary"
+                    on:click=move |_| {
+                        selected_run.update(|s| *s = if *s == Some(run_id) { None } else { Some(run_id) });
+                    }
+                >
+                    {move || if selected_run.get() == Some(run_id) { "Hide" } else { "View" }}
+                </button>
+                <a href=format!("/api/runs/{}/changes.json", run_id) download class="btn btn-xs btn-soft ml-1">"JSON"</a>
+                <a href=format!("/api/runs/{}/changes.md", run_id) download class="btn btn-xs btn-soft ml-1">"Markdown"</a>
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+fn ReportDetails(run_id: i32) -> impl IntoView {
+    let report = Resource::new(move || run_id, get_change_report);
+
+    view! {
+        <Suspense fallback=move || view! { <span class="loading loading-spinner loading-md"></span> }>
+            {move || Suspend::new(async move {
+                match report.await {
+                    Ok(report) => view! { <ReportSections report=report /> }.into_any(),
+                    Err(e) => {
+                        view! {
+                            <div class="alert alert-error">
+                                <span>"Error loading change report: " {e.to_string()}</span>
+                            </div>
+                        }
+                        .into_any()
+                    }
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn ReportSections(report: ChangeReport) -> impl IntoView {
+    let section = |change_type: &str, title: &'static str| {
+        let entries: Vec<ChangeReportEntry> = report.entries_of(change_type).cloned().collect();
+        view! {
+            <div class="flex flex-col gap-2">
+                <h3 class="font-semibold text-lg">{title} " (" {entries.len()} ")"</h3>
+                {if entries.is_empty() {
+                    view! { <p class="text-sm text-base-content/60">"None."</p> }.into_any()
+                } else {
+                    entries.into_iter().map(|entry| view! { <ReportEntry entry=entry /> }).collect_view().into_any()
+                }}
+            </div>
+        }
+    };
+
+    view! {
+        <div class="divider"></div>
+        <div class="flex flex-col gap-6">
+            {section("added", "New module versions")}
+            {section("removed", "Removed modules")}
+            {section("changed", "Changed modules")}
+        </div>
+    }
+}
+
+#[component]
+fn ReportEntry(entry: ChangeReportEntry) -> impl IntoView {
+    let href = format!("/module/{}/{}", entry.module_id, entry.module_version);
+
+    view! {
+        <div class="flex flex-col gap-2 pb-2 border-b border-base-300/50 last:border-0">
+            <div class="flex items-center gap-2 text-sm">
+                <A href=href attr:class="link link-hover font-medium">{entry.title}</A>
+                <span class="text-xs text-base-content/40">"#" {entry.module_id} " v" {entry.module_version}</span>
+            </div>
+            {(!entry.changes.is_empty()).then(|| view! {
+                <div class="flex flex-col gap-2 pl-3">
+                    {entry.changes.into_iter().map(|change| view! { <FieldChangeRow change=change /> }).collect_view()}
+                </div>
+            })}
+        </div>
+    }
+}
@@ -2,5 +2,6 @@ mod scraper_page;
 mod csv_upload;
 mod scraper_progress;
 mod scraper_logs;
+mod change_reports;
 
 pub use scraper_page::ScraperPage;
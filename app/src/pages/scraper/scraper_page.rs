@@ -5,6 +5,7 @@ use std::collections::VecDeque;
 use crate::api::{get_scraper_status, start_scraper, CsvInfoDto};
 use crate::components::PageLayout;
 
+use super::change_reports::ChangeReports;
 use super::csv_upload::CsvUpload;
 use super::scraper_logs::{LogEntry, ScraperLogs};
 use super::scraper_progress::ScraperProgress;
@@ -22,6 +23,7 @@ pub fn ScraperPage() -> impl IntoView {
 
     // Configuration options
     let (workers, set_workers) = signal(1_u32);
+    let (full_run, set_full_run) = signal(false);
     let (url_pattern, set_url_pattern) = signal(String::from(
         "https://moseskonto.tu-berlin.de/moses/modultransfersystem/bolognamodule/beschreibung/anzeigen.html?nummer={number}&version={version}&sprache=1"
     ));
@@ -87,13 +89,14 @@ pub fn ScraperPage() -> impl IntoView {
     let on_start_scraping = move |_| {
         let key = auth_key.get_untracked();
         let num_workers = workers.get_untracked();
+        let is_full_run = full_run.get_untracked();
         let pattern = url_pattern.get_untracked();
         let pattern_opt = if pattern.is_empty() { None } else { Some(pattern) };
 
         spawn_local(async move {
             set_error.set(None);
 
-            match start_scraper(key.clone(), num_workers, pattern_opt).await {
+            match start_scraper(key.clone(), num_workers, pattern_opt, is_full_run).await {
                 Ok(response) => {
                     if response.success {
                         set_is_scraping.set(true);
@@ -255,6 +258,23 @@ pub fn ScraperPage() -> impl IntoView {
                                                 </div>
                                             </div>
 
+                                            <div class="form-control">
+                                                <label class="label cursor-pointer justify-start gap-3">
+                                                    <input
+                                                        type="checkbox"
+                                                        class="checkbox checkbox-primary"
+                                                        prop:checked=move || full_run.get()
+                                                        on:change=move |ev| set_full_run.set(event_target_checked(&ev))
+                                                    />
+                                                    <span class="label-text">Full run</span>
+                                                </label>
+                                                <label class="label pt-0">
+                                                    <span class="label-text-alt">
+                                                        "Only check this if the CSV is the complete MOSES export. Modules missing from it are marked as discontinued and reported as removed."
+                                                    </span>
+                                                </label>
+                                            </div>
+
                                             <div class="card-actions justify-end mt-4">
                                                 <button
                                                     class="btn btn-primary btn-soft"
@@ -279,6 +299,8 @@ pub fn ScraperPage() -> impl IntoView {
                                         <span>{err}</span>
                                     </div>
                                 })}
+
+                                <ChangeReports />
                             </div>
                         }.into_any()
                     }
@@ -0,0 +1,226 @@
+use crate::api::{create_sync_code, revoke_sync_code};
+use crate::components::layout::PageLayout;
+use crate::models::{SearchFilters, SyncState};
+use crate::starred::{use_search_filters, use_starred_modules, StarredModules};
+use crate::sync::{link_device, local_state, sync_now, use_sync_link, SyncLink};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::components::A;
+
+#[component]
+pub fn SyncPage() -> impl IntoView {
+    let (starred, set_starred) = use_starred_modules();
+    let filters = use_search_filters();
+    let (link, set_link) = use_sync_link();
+
+    let code_input = RwSignal::new(String::new());
+    let (busy, set_busy) = signal(false);
+    let (message, set_message) = signal(None::<Result<String, String>>);
+    let confirm_revoke = RwSignal::new(false);
+
+    let current_state = move || local_state(&starred.get_untracked(), &filters.get_untracked());
+
+    // Apply a merged state to this device and remember it as the new base
+    let apply = move |state: SyncState, new_link: SyncLink, success: String| {
+        set_starred.set(StarredModules {
+            modules: state.starred.into_iter().collect(),
+        });
+        // Collections are not synced, keep the one shown on this device
+        let collection_id = filters.with_untracked(|f| f.collection_id);
+        filters.set(SearchFilters {
+            collection_id,
+            ..state.filters
+        });
+        set_link.set(Some(new_link));
+        set_message.set(Some(Ok(success)));
+    };
+
+    let on_create = move |_| {
+        let state = current_state();
+        spawn_local(async move {
+            set_busy.set(true);
+            set_message.set(None);
+            match create_sync_code(state.clone()).await {
+                Ok(snapshot) => {
+                    let new_link = SyncLink {
+                        token: snapshot.token,
+                        revision: snapshot.revision,
+                        base: state.clone(),
+                    };
+                    apply(state, new_link, "Sync code created. Enter it on your other devices.".to_string());
+                }
+                Err(e) => set_message.set(Some(Err(e.to_string()))),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let on_link = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let token = code_input.get_untracked().trim().to_string();
+        let state = current_state();
+        spawn_local(async move {
+            set_busy.set(true);
+            set_message.set(None);
+            match link_device(token, state).await {
+                Ok((merged, new_link)) => {
+                    code_input.set(String::new());
+                    apply(merged, new_link, "This device is now linked.".to_string());
+                }
+                Err(e) => set_message.set(Some(Err(e.to_string()))),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let on_sync = move |_| {
+        let Some(current_link) = link.get_untracked() else {
+            return;
+        };
+        let state = current_state();
+        spawn_local(async move {
+            set_busy.set(true);
+            set_message.set(None);
+            match sync_now(current_link, state).await {
+                Ok((merged, new_link)) => apply(merged, new_link, "Synced.".to_string()),
+                Err(e) => set_message.set(Some(Err(e.to_string()))),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let on_unlink = move |_| {
+        set_link.set(None);
+        confirm_revoke.set(false);
+        set_message.set(Some(Ok("This device is no longer linked. Your stars stay on this device.".to_string())));
+    };
+
+    let on_revoke = move |_| {
+        if !confirm_revoke.get_untracked() {
+            confirm_revoke.set(true);
+            return;
+        }
+        let Some(current_link) = link.get_untracked() else {
+            return;
+        };
+        spawn_local(async move {
+            set_busy.set(true);
+            set_message.set(None);
+            match revoke_sync_code(current_link.token).await {
+                Ok(()) => {
+                    set_link.set(None);
+                    set_message.set(Some(Ok(
+                        "The sync code was revoked. Other devices can no longer use it.".to_string(),
+                    )));
+                }
+                Err(e) => set_message.set(Some(Err(e.to_string()))),
+            }
+            confirm_revoke.set(false);
+            set_busy.set(false);
+        });
+    };
+
+    view! {
+        <PageLayout>
+            <div class="py-8 flex flex-col gap-6 max-w-2xl">
+                <div class="flex items-center justify-between gap-4 pb-3 border-b border-base-300/50">
+                    <div class="flex items-center gap-3 flex-1 min-w-0">
+                        <div class="tooltip tooltip-right" data-tip="Back to planner">
+                            <A href="/planner" attr:class="btn btn-sm btn-soft btn-circle shrink-0">
+                                <svg xmlns="http://www.w3.org/2000/svg" class="h-5 w-5" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7" />
+                                </svg>
+                            </A>
+                        </div>
+                        <h1 class="text-2xl font-bold leading-tight text-base-content">"Sync Devices"</h1>
+                    </div>
+                </div>
+
+                <p class="text-sm text-base-content/70">
+                    "Your starred modules and search filters are stored in this browser. "
+                    "A sync code shares them with your other devices without an account. "
+                    "Anyone who knows the code can read and change the synced state."
+                </p>
+
+                {move || message.get().map(|message| match message {
+                    Ok(text) => view! {
+                        <div class="alert alert-success alert-soft"><span>{text}</span></div>
+                    }.into_any(),
+                    Err(text) => view! {
+                        <div class="alert alert-error alert-soft"><span>{text}</span></div>
+                    }.into_any(),
+                })}
+
+                {move || match link.get() {
+                    Some(current_link) => view! {
+                        <div class="card bg-base-100 shadow-sm">
+                            <div class="card-body p-5 gap-4">
+                                <h2 class="card-title text-lg text-primary">"Linked"</h2>
+                                <label class="form-control w-full">
+                                    <span class="label-text text-sm mb-1">"Sync code"</span>
+                                    <input
+                                        type="text"
+                                        readonly
+                                        class="input input-sm w-full font-mono"
+                                        prop:value=current_link.token.clone()
+                                    />
+                                </label>
+                                <p class="text-xs text-base-content/60">
+                                    "Revision " {current_link.revision} " · "
+                                    {current_link.base.starred.len()} " starred modules at the last sync"
+                                </p>
+                                <div class="card-actions justify-end">
+                                    <button class="btn btn-sm btn-soft" disabled=move || busy.get() on:click=on_unlink>
+                                        "Unlink this device"
+                                    </button>
+                                    <button class="btn btn-sm btn-soft btn-error" disabled=move || busy.get() on:click=on_revoke>
+                                        {move || if confirm_revoke.get() { "Really revoke?" } else { "Revoke code" }}
+                                    </button>
+                                    <button class="btn btn-sm btn-primary" disabled=move || busy.get() on:click=on_sync>
+                                        "Sync now"
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                    }.into_any(),
+                    None => view! {
+                        <div class="card bg-base-100 shadow-sm">
+                            <div class="card-body p-5 gap-3">
+                                <h2 class="card-title text-lg text-primary">"Create a sync code"</h2>
+                                <p class="text-sm text-base-content/70">
+                                    "Upload the stars and filters of this device and get a code for your other devices."
+                                </p>
+                                <div class="card-actions justify-end">
+                                    <button class="btn btn-sm btn-primary" disabled=move || busy.get() on:click=on_create>
+                                        "Create code"
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                        <div class="card bg-base-100 shadow-sm">
+                            <form class="card-body p-5 gap-3" on:submit=on_link>
+                                <h2 class="card-title text-lg text-primary">"Link this device"</h2>
+                                <p class="text-sm text-base-content/70">
+                                    "Stars from both devices are kept, the filters are taken from the code."
+                                </p>
+                                <input
+                                    type="text"
+                                    required
+                                    placeholder="Sync code"
+                                    class="input input-sm w-full font-mono"
+                                    prop:value=move || code_input.get()
+                                    on:input=move |ev| code_input.set(event_target_value(&ev))
+                                />
+                                <div class="card-actions justify-end">
+                                    <button type="submit" class="btn btn-sm btn-primary" disabled=move || busy.get()>
+                                        "Link"
+                                    </button>
+                                </div>
+                            </form>
+                        </div>
+                    }.into_any(),
+                }}
+            </div>
+        </PageLayout>
+    }
+}
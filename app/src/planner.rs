@@ -0,0 +1,195 @@
+use crate::models::ModuleSummary;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_arch = "wasm32")]
+use codee::string::JsonSerdeCodec;
+#[cfg(target_arch = "wasm32")]
+use leptos_use::storage::use_local_storage;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Season {
+    SoSe,
+    WiSe,
+}
+
+/// A concrete semester, identified by season and the calendar year it starts in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Term {
+    pub year: i32,
+    pub season: Season,
+}
+
+impl Term {
+    /// The semester that is currently running or about to start
+    ///
+    /// Summer semesters run from April to September, winter semesters from
+    /// October to March.
+    pub fn current() -> Self {
+        use chrono::Datelike;
+
+        let today = chrono::Local::now().date_naive();
+        match today.month() {
+            4..=9 => Term { year: today.year(), season: Season::SoSe },
+            10..=12 => Term { year: today.year(), season: Season::WiSe },
+            _ => Term { year: today.year() - 1, season: Season::WiSe },
+        }
+    }
+
+    /// Build a term from the `semester` enum text and year stored in the database
+    pub fn from_db(semester: &str, year: i32) -> Option<Self> {
+        let season = match semester {
+            "SoSe" => Season::SoSe,
+            "WiSe" => Season::WiSe,
+            _ => return None,
+        };
+        Some(Term { year, season })
+    }
+
+    pub fn next(self) -> Self {
+        match self.season {
+            Season::SoSe => Term { year: self.year, season: Season::WiSe },
+            Season::WiSe => Term { year: self.year + 1, season: Season::SoSe },
+        }
+    }
+
+    /// Display label, e.g. "WiSe 2026/27" or "SoSe 2027"
+    pub fn label(self) -> String {
+        match self.season {
+            Season::SoSe => format!("SoSe {}", self.year),
+            Season::WiSe => format!("WiSe {}/{:02}", self.year, (self.year + 1) % 100),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlannedSemester {
+    pub term: Term,
+    pub modules: Vec<(i32, i32)>, // (id, version)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct StudyPlan {
+    pub semesters: Vec<PlannedSemester>,
+}
+
+pub fn use_study_plan() -> (Signal<StudyPlan>, WriteSignal<StudyPlan>) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let (plan, set_plan, _remove) = use_local_storage::<StudyPlan, JsonSerdeCodec>("study_plan");
+        (plan, set_plan)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // SSR fallback - just return dummy signals
+        let plan = RwSignal::new(StudyPlan::default());
+        (plan.read_only().into(), plan.write_only())
+    }
+}
+
+/// Term a module is planned in, if any
+pub fn planned_term(plan: &StudyPlan, id: i32, version: i32) -> Option<Term> {
+    plan.semesters
+        .iter()
+        .find(|s| s.modules.contains(&(id, version)))
+        .map(|s| s.term)
+}
+
+/// Append the semester following the last planned one
+pub fn add_semester(plan: &StudyPlan) -> StudyPlan {
+    let mut new_plan = plan.clone();
+    let term = match new_plan.semesters.last() {
+        Some(last) => last.term.next(),
+        None => Term::current(),
+    };
+    new_plan.semesters.push(PlannedSemester { term, modules: Vec::new() });
+    new_plan
+}
+
+/// Remove a semester, its modules become unassigned
+pub fn remove_semester(plan: &StudyPlan, term: Term) -> StudyPlan {
+    let mut new_plan = plan.clone();
+    new_plan.semesters.retain(|s| s.term != term);
+    new_plan
+}
+
+/// Move a module into the given semester, or out of the plan with `None`
+pub fn assign_module(plan: &StudyPlan, id: i32, version: i32, term: Option<Term>) -> StudyPlan {
+    let mut new_plan = plan.clone();
+    let key = (id, version);
+
+    for semester in &mut new_plan.semesters {
+        semester.modules.retain(|k| *k != key);
+    }
+    if let Some(term) = term {
+        if let Some(semester) = new_plan.semesters.iter_mut().find(|s| s.term == term) {
+            semester.modules.push(key);
+        }
+    }
+
+    new_plan
+}
+
+/// Components of a module that are not offered in the given season
+pub fn unavailable_components(module: &ModuleSummary, season: Season) -> Vec<String> {
+    module
+        .components
+        .iter()
+        .filter(|c| !rotation_offers(&c.rotation, season))
+        .map(|c| c.name.clone().unwrap_or_else(|| c.number.clone()))
+        .collect()
+}
+
+fn rotation_offers(rotation: &str, season: Season) -> bool {
+    match season {
+        Season::SoSe => rotation.contains("SoSe"),
+        Season::WiSe => rotation.contains("WiSe"),
+    }
+}
+
+/// Total SWS of all components of a module
+pub fn module_sws(module: &ModuleSummary) -> i32 {
+    module.components.iter().map(|c| c.sws).sum()
+}
+
+/// Render the plan as CSV with one row per planned module
+pub fn plan_to_csv(plan: &StudyPlan, modules: &[ModuleSummary]) -> String {
+    let mut csv = String::from("semester,module_id,version,title,credits,sws\n");
+    for semester in &plan.semesters {
+        for &(id, version) in &semester.modules {
+            let Some(module) = modules.iter().find(|m| m.id == id && m.version == version) else {
+                continue;
+            };
+            csv.push_str(&format!(
+                "{},{},{},\"{}\",{},{}\n",
+                semester.term.label(),
+                id,
+                version,
+                module.title.replace('"', "\"\""),
+                module.credits,
+                module_sws(module)
+            ));
+        }
+    }
+    csv
+}
+
+/// Build a `data:` URL so exports can be downloaded without a server round trip
+pub fn data_url(mime: &str, content: &str) -> String {
+    format!("data:{};charset=utf-8,{}", mime, percent_encode(content))
+}
+
+/// Percent-encode everything except unreserved URL characters
+pub fn percent_encode(content: &str) -> String {
+    let mut encoded = String::with_capacity(content.len());
+    for byte in content.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
@@ -0,0 +1,604 @@
+//! Filters typed into the search box, e.g.
+//! `lp:>=6 lang:en exam:oral rot:SoSe prog:"Informatik" -"Projekt"`
+//!
+//! Bare words are matched against the module title like a plain search, runs
+//! of consecutive words as one phrase. Quoted phrases are matched as a whole,
+//! a leading `-` excludes a phrase or an exam form. Filters typed into the
+//! search box apply on top of the filter panel.
+
+use crate::models::SearchFilters;
+use std::fmt;
+use std::ops::Range;
+
+/// Filter keys with a short description, used for help texts and errors
+pub const QUERY_KEYS: [(&str, &str); 9] = [
+    ("lp", "credits, e.g. lp:6, lp:>=6 or lp:5-9"),
+    ("lang", "component language, e.g. lang:en or lang:de,en"),
+    ("exam", "exam category: oral, written or praktisch"),
+    ("rot", "semester: WiSe, SoSe or both"),
+    ("prog", "study program name, e.g. prog:\"Informatik\""),
+    ("type", "component type, e.g. type:VL"),
+    ("form", "exam form, e.g. form:portfolio or -form:oral"),
+    ("graded", "graded:yes or graded:no"),
+    ("hours", "total workload, e.g. hours:<=150"),
+];
+
+/// Text matched against the module title
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextTerm {
+    pub text: String,
+    pub negated: bool,
+}
+
+/// One part of the query as shown to the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryClause {
+    pub label: String,
+    /// Byte range in the query, removing it removes the clause
+    pub span: Range<usize>,
+    /// Plain title text without any syntax
+    pub plain: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedQuery {
+    /// Filters given in the query, the search query itself stays empty
+    pub filters: SearchFilters,
+    pub terms: Vec<TextTerm>,
+    pub clauses: Vec<QueryClause>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    /// Byte range of the offending part of the query
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl ParsedQuery {
+    /// The whole query as one title phrase, how searches matched before the syntax existed
+    pub fn plain(query: &str) -> Self {
+        let text = query.trim();
+        if text.is_empty() {
+            return ParsedQuery::default();
+        }
+        ParsedQuery {
+            terms: vec![TextTerm { text: text.to_string(), negated: false }],
+            ..Default::default()
+        }
+    }
+
+    /// Text to rank titles by, the first phrase that is not excluded
+    pub fn title_text(&self) -> Option<&str> {
+        self.terms.iter().find(|term| !term.negated).map(|term| term.text.as_str())
+    }
+}
+
+/// Parse a search box query, `Err` points at the first invalid part
+pub fn parse_query(input: &str) -> Result<ParsedQuery, QueryError> {
+    let mut parsed = ParsedQuery::default();
+    // Consecutive bare words, merged into one phrase
+    let mut words: Vec<(String, Range<usize>)> = Vec::new();
+
+    for token in tokenize(input)? {
+        if token.key.is_none() && !token.quoted && !token.negated {
+            words.push((token.value, token.span));
+            continue;
+        }
+        flush_words(&mut parsed, &mut words);
+        apply_token(&mut parsed, token)?;
+    }
+    flush_words(&mut parsed, &mut words);
+
+    if let (Some(min), Some(max)) = (parsed.filters.min_credits, parsed.filters.max_credits) {
+        if min > max {
+            return Err(credit_conflict(&parsed));
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Query without the given clause and the whitespace around it, `None` if nothing is left
+pub fn remove_clause(input: &str, span: &Range<usize>) -> Option<String> {
+    let before = input.get(..span.start).unwrap_or(input).trim_end();
+    let after = input.get(span.end..).unwrap_or("").trim_start();
+    let rest = match (before.is_empty(), after.is_empty()) {
+        (true, _) => after.to_string(),
+        (_, true) => before.to_string(),
+        _ => format!("{} {}", before, after),
+    };
+    if rest.is_empty() {
+        None
+    } else {
+        Some(rest)
+    }
+}
+
+struct Token {
+    negated: bool,
+    key: Option<(String, Range<usize>)>,
+    value: String,
+    quoted: bool,
+    span: Range<usize>,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut negated = false;
+        if c == '-' {
+            chars.next();
+            match chars.peek() {
+                Some(&(_, next)) if !next.is_whitespace() => negated = true,
+                // A lone dash is just text
+                _ => {
+                    tokens.push(Token { negated, key: None, value: "-".to_string(), quoted: false, span: start..start + 1 });
+                    continue;
+                }
+            }
+        }
+
+        let mut key = None;
+        let mut value = String::new();
+        let mut quoted = false;
+        let mut end = input.len();
+
+        if let Some(&(quote, '"')) = chars.peek() {
+            chars.next();
+            value = read_quoted(input, quote, &mut chars)?;
+            quoted = true;
+            end = chars.peek().map(|&(i, _)| i).unwrap_or(input.len());
+        } else {
+            let word_start = chars.peek().map(|&(i, _)| i).unwrap_or(input.len());
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    end = i;
+                    break;
+                }
+                chars.next();
+                if c == ':' && key.is_none() && !value.is_empty() && value.chars().all(char::is_alphabetic) {
+                    key = Some((std::mem::take(&mut value), word_start..i + 1));
+                    if let Some(&(quote, '"')) = chars.peek() {
+                        chars.next();
+                        value = read_quoted(input, quote, &mut chars)?;
+                        quoted = true;
+                        end = chars.peek().map(|&(i, _)| i).unwrap_or(input.len());
+                        break;
+                    }
+                    continue;
+                }
+                value.push(c);
+            }
+        }
+
+        tokens.push(Token { negated, key, value, quoted, span: start..end });
+    }
+
+    Ok(tokens)
+}
+
+/// Read up to the closing quote, the opening quote at `quote` was consumed already
+fn read_quoted(
+    input: &str,
+    quote: usize,
+    chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+) -> Result<String, QueryError> {
+    let mut text = String::new();
+    for (_, c) in chars.by_ref() {
+        if c == '"' {
+            return Ok(text);
+        }
+        text.push(c);
+    }
+    Err(QueryError {
+        message: "Missing closing quote".to_string(),
+        span: quote..input.len(),
+    })
+}
+
+fn flush_words(parsed: &mut ParsedQuery, words: &mut Vec<(String, Range<usize>)>) {
+    let (Some(first), Some(last)) = (words.first(), words.last()) else {
+        return;
+    };
+    let span = first.1.start..last.1.end;
+    let text = words.iter().map(|(word, _)| word.as_str()).collect::<Vec<_>>().join(" ");
+    parsed.clauses.push(QueryClause { label: text.clone(), span, plain: true });
+    parsed.terms.push(TextTerm { text, negated: false });
+    words.clear();
+}
+
+fn apply_token(parsed: &mut ParsedQuery, token: Token) -> Result<(), QueryError> {
+    let error = |message: String| QueryError { message, span: token.span.clone() };
+
+    let Some((key, key_span)) = token.key.clone() else {
+        if token.value.trim().is_empty() {
+            return Err(error("Empty phrase".to_string()));
+        }
+        let label = if token.quoted { format!("\"{}\"", token.value) } else { token.value.clone() };
+        parsed.clauses.push(QueryClause {
+            label: if token.negated { format!("not {}", label) } else { label },
+            span: token.span.clone(),
+            plain: false,
+        });
+        parsed.terms.push(TextTerm { text: token.value, negated: token.negated });
+        return Ok(());
+    };
+
+    let key = key.to_lowercase();
+    let value = token.value.trim();
+    if value.is_empty() {
+        return Err(error(format!("`{}:` needs a value", key)));
+    }
+    if token.negated && key != "form" {
+        return Err(error(format!("`{}:` cannot be negated, only phrases and `form:` can", key)));
+    }
+    let values: Vec<&str> = value.split(',').map(str::trim).filter(|v| !v.is_empty()).collect();
+    let filters = &mut parsed.filters;
+
+    let label = match key.as_str() {
+        "lp" | "cp" | "ects" => {
+            let (min, max) = parse_bounds(value).ok_or_else(|| error(format!("Invalid credits `{}`, use e.g. lp:6, lp:>=6 or lp:5-9", value)))?;
+            if let Some(min) = min {
+                filters.min_credits = Some(filters.min_credits.map_or(min, |current| current.max(min)));
+            }
+            if let Some(max) = max {
+                filters.max_credits = Some(filters.max_credits.map_or(max, |current| current.min(max)));
+            }
+            format!("CP {}", bounds_label(min, max))
+        }
+        "hours" => {
+            let (min, max) = parse_bounds(value).ok_or_else(|| error(format!("Invalid hours `{}`, use e.g. hours:<=150", value)))?;
+            if let Some(min) = min {
+                filters.min_workload_hours = Some(filters.min_workload_hours.map_or(min, |current| current.max(min)));
+            }
+            if let Some(max) = max {
+                filters.max_workload_hours = Some(filters.max_workload_hours.map_or(max, |current| current.min(max)));
+            }
+            format!("Workload {} h", bounds_label(min, max))
+        }
+        "lang" => {
+            let languages: Vec<String> = values.iter().map(|v| language_name(v)).collect();
+            let label = format!("Language: {}", languages.join(" or "));
+            extend(&mut filters.component_languages, languages);
+            label
+        }
+        "exam" => {
+            let categories = values
+                .iter()
+                .map(|v| exam_category(v).ok_or_else(|| error(format!("Unknown exam category `{}`, use oral, written or praktisch", v))))
+                .collect::<Result<Vec<_>, _>>()?;
+            let label = format!("Exam: {}", categories.join(" or "));
+            extend(&mut filters.exam_categories, categories);
+            label
+        }
+        "rot" => {
+            let mut rotations = Vec::new();
+            for v in &values {
+                let matching = semester_rotations(v).ok_or_else(|| error(format!("Unknown semester `{}`, use WiSe, SoSe or both", v)))?;
+                rotations.extend(matching.iter().map(|r| r.to_string()));
+            }
+            rotations.dedup();
+            let label = format!("Semester: {}", values.join(" or "));
+            extend(&mut filters.semester_rotations, rotations);
+            label
+        }
+        "prog" => {
+            let names: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+            let label = format!("Program: {}", names.join(" or "));
+            extend(&mut filters.study_program_names, names);
+            label
+        }
+        "type" => {
+            let types: Vec<String> = values.iter().map(|v| v.to_uppercase()).collect();
+            let label = format!("Type: {}", types.join(" or "));
+            extend(&mut filters.component_types, types);
+            label
+        }
+        "form" => {
+            let forms: Vec<String> = values.iter().map(|v| exam_form(v)).collect();
+            let label = format!("{}: {}", if token.negated { "Not exam form" } else { "Exam form" }, forms.join(" or "));
+            if token.negated {
+                extend(&mut filters.excluded_exam_types, forms);
+            } else {
+                extend(&mut filters.exam_types, forms);
+            }
+            label
+        }
+        "graded" => {
+            let graded = match value.to_lowercase().as_str() {
+                "yes" | "true" | "ja" => true,
+                "no" | "false" | "nein" => false,
+                _ => return Err(error(format!("Invalid value `{}`, use graded:yes or graded:no", value))),
+            };
+            filters.graded = Some(graded);
+            if graded { "Graded".to_string() } else { "Ungraded".to_string() }
+        }
+        _ => {
+            let known: Vec<&str> = QUERY_KEYS.iter().map(|(key, _)| *key).collect();
+            return Err(QueryError {
+                message: format!(
+                    "Unknown filter `{}:`, use one of {} or put text containing `:` in quotes",
+                    key,
+                    known.join(", ")
+                ),
+                span: key_span,
+            });
+        }
+    };
+
+    parsed.clauses.push(QueryClause { label, span: token.span, plain: false });
+    Ok(())
+}
+
+fn extend(list: &mut Option<Vec<String>>, values: Vec<String>) {
+    let list = list.get_or_insert_with(Vec::new);
+    for value in values {
+        if !list.contains(&value) {
+            list.push(value);
+        }
+    }
+}
+
+/// Parse `6`, `>=6`, `>5`, `<=9`, `<10`, `5-9` or `5..9` into inclusive bounds,
+/// `None` if the value is malformed or out of range
+fn parse_bounds(value: &str) -> Option<(Option<i32>, Option<i32>)> {
+    let number = |text: &str| text.trim().parse::<i32>().ok();
+    if let Some(rest) = value.strip_prefix(">=") {
+        return Some((Some(number(rest)?), None));
+    }
+    if let Some(rest) = value.strip_prefix("<=") {
+        return Some((None, Some(number(rest)?)));
+    }
+    if let Some(rest) = value.strip_prefix('>') {
+        return Some((Some(number(rest)?.checked_add(1)?), None));
+    }
+    if let Some(rest) = value.strip_prefix('<') {
+        return Some((None, Some(number(rest)?.checked_sub(1)?)));
+    }
+    if let Some(rest) = value.strip_prefix('=') {
+        let exact = number(rest)?;
+        return Some((Some(exact), Some(exact)));
+    }
+    if let Some((min, max)) = value.split_once("..").or_else(|| value.split_once('-')) {
+        return Some((Some(number(min)?), Some(number(max)?)));
+    }
+    let exact = number(value)?;
+    Some((Some(exact), Some(exact)))
+}
+
+fn bounds_label(min: Option<i32>, max: Option<i32>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) if min == max => format!("= {}", min),
+        (Some(min), Some(max)) => format!("{}–{}", min, max),
+        (Some(min), None) => format!("≥ {}", min),
+        (None, Some(max)) => format!("≤ {}", max),
+        (None, None) => String::new(),
+    }
+}
+
+fn credit_conflict(parsed: &ParsedQuery) -> QueryError {
+    let credit_clauses: Vec<&QueryClause> = parsed.clauses.iter().filter(|c| c.label.starts_with("CP ")).collect();
+    let span = match (credit_clauses.first(), credit_clauses.last()) {
+        (Some(first), Some(last)) => first.span.start..last.span.end,
+        _ => 0..0,
+    };
+    QueryError {
+        message: "The credit bounds exclude each other".to_string(),
+        span,
+    }
+}
+
+/// Language name as stored for components, unknown names are kept as typed
+fn language_name(value: &str) -> String {
+    match value.to_lowercase().as_str() {
+        "de" | "deu" | "german" | "deutsch" => "Deutsch".to_string(),
+        "en" | "eng" | "english" | "englisch" => "Englisch".to_string(),
+        _ => {
+            let mut chars = value.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+    }
+}
+
+fn exam_category(value: &str) -> Option<String> {
+    let category = match value.to_lowercase().as_str() {
+        "oral" | "mündlich" | "muendlich" => "oral",
+        "written" | "schriftlich" => "written",
+        "praktisch" | "practical" => "praktisch",
+        _ => return None,
+    };
+    Some(category.to_string())
+}
+
+/// Rotations of components offered in the given semester
+fn semester_rotations(value: &str) -> Option<&'static [&'static str]> {
+    match value.to_lowercase().as_str() {
+        "wise" | "winter" => Some(&["WiSe", "WiSe/SoSe"]),
+        "sose" | "summer" | "sommer" => Some(&["SoSe", "WiSe/SoSe"]),
+        "both" | "wise/sose" => Some(&["WiSe/SoSe"]),
+        _ => None,
+    }
+}
+
+/// Exam form as stored, unknown forms are kept as typed
+fn exam_form(value: &str) -> String {
+    match value.to_lowercase().as_str() {
+        "portfolio" | "portfolioprüfung" => "Portfolioprüfung".to_string(),
+        "oral" | "mündlich" | "muendlich" => "Mündliche Prüfung".to_string(),
+        "written" | "schriftlich" => "Schriftliche Prüfung".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(parsed: &ParsedQuery) -> Vec<&str> {
+        parsed.clauses.iter().map(|clause| clause.label.as_str()).collect()
+    }
+
+    fn term(text: &str, negated: bool) -> TextTerm {
+        TextTerm { text: text.to_string(), negated }
+    }
+
+    #[test]
+    fn test_parse_example_query() {
+        let parsed = parse_query(r#"lp:>=6 lang:en exam:oral rot:SoSe prog:"Informatik" -"Projekt""#).unwrap();
+
+        assert_eq!(parsed.filters.min_credits, Some(6));
+        assert_eq!(parsed.filters.max_credits, None);
+        assert_eq!(parsed.filters.component_languages, Some(vec!["Englisch".to_string()]));
+        assert_eq!(parsed.filters.exam_categories, Some(vec!["oral".to_string()]));
+        assert_eq!(
+            parsed.filters.semester_rotations,
+            Some(vec!["SoSe".to_string(), "WiSe/SoSe".to_string()])
+        );
+        assert_eq!(parsed.filters.study_program_names, Some(vec!["Informatik".to_string()]));
+        assert_eq!(parsed.filters.search_query, None);
+        assert_eq!(parsed.terms, vec![term("Projekt", true)]);
+        assert_eq!(parsed.title_text(), None);
+        assert_eq!(
+            labels(&parsed),
+            vec!["CP ≥ 6", "Language: Englisch", "Exam: oral", "Semester: SoSe", "Program: Informatik", "not \"Projekt\""]
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_phrases() {
+        let input = r#"Analysis "Lineare Algebra" für"#;
+        let parsed = parse_query(input).unwrap();
+
+        assert_eq!(
+            parsed.terms,
+            vec![term("Analysis", false), term("Lineare Algebra", false), term("für", false)]
+        );
+        assert_eq!(labels(&parsed), vec!["Analysis", "\"Lineare Algebra\"", "für"]);
+        assert_eq!(&input[parsed.clauses[1].span.clone()], "\"Lineare Algebra\"");
+        assert!(parsed.clauses[0].plain);
+        assert!(!parsed.clauses[1].plain);
+        assert_eq!(parsed.title_text(), Some("Analysis"));
+
+        // Quoted text may contain keys without being parsed as a filter
+        let parsed = parse_query(r#""lp:6""#).unwrap();
+        assert_eq!(parsed.terms, vec![term("lp:6", false)]);
+        assert_eq!(parsed.filters.min_credits, None);
+    }
+
+    #[test]
+    fn test_parse_unterminated_quote() {
+        let error = parse_query(r#"Analysis "Lineare"#).unwrap_err();
+        assert_eq!(error.message, "Missing closing quote");
+        assert_eq!(error.span, 9..17);
+
+        let error = parse_query(r#"prog:"Informatik"#).unwrap_err();
+        assert_eq!(error.message, "Missing closing quote");
+        assert_eq!(error.span, 5..16);
+    }
+
+    #[test]
+    fn test_parse_negation() {
+        let parsed = parse_query(r#"-"Projekt Labor""#).unwrap();
+        assert_eq!(parsed.terms, vec![term("Projekt Labor", true)]);
+        assert_eq!(parsed.filters.excluded_exam_types, None);
+        assert_eq!(labels(&parsed), vec!["not \"Projekt Labor\""]);
+
+        let parsed = parse_query("-form:oral,portfolio").unwrap();
+        assert!(parsed.terms.is_empty());
+        assert_eq!(
+            parsed.filters.excluded_exam_types,
+            Some(vec!["Mündliche Prüfung".to_string(), "Portfolioprüfung".to_string()])
+        );
+        assert_eq!(parsed.filters.exam_types, None);
+        assert_eq!(labels(&parsed), vec!["Not exam form: Mündliche Prüfung or Portfolioprüfung"]);
+
+        let error = parse_query("-lp:6").unwrap_err();
+        assert!(error.message.contains("cannot be negated"));
+        assert_eq!(error.span, 0..5);
+
+        // A lone dash is text
+        let parsed = parse_query("Analysis - Lineare").unwrap();
+        assert_eq!(parsed.terms, vec![term("Analysis - Lineare", false)]);
+    }
+
+    #[test]
+    fn test_parse_bounds() {
+        assert_eq!(parse_bounds("6"), Some((Some(6), Some(6))));
+        assert_eq!(parse_bounds("=6"), Some((Some(6), Some(6))));
+        assert_eq!(parse_bounds(">=6"), Some((Some(6), None)));
+        assert_eq!(parse_bounds(">5"), Some((Some(6), None)));
+        assert_eq!(parse_bounds("<=9"), Some((None, Some(9))));
+        assert_eq!(parse_bounds("<10"), Some((None, Some(9))));
+        assert_eq!(parse_bounds("5-9"), Some((Some(5), Some(9))));
+        assert_eq!(parse_bounds("5..9"), Some((Some(5), Some(9))));
+        assert_eq!(parse_bounds("six"), None);
+        assert_eq!(parse_bounds("5-"), None);
+    }
+
+    #[test]
+    fn test_parse_bounds_overflow() {
+        assert_eq!(parse_bounds(">2147483647"), None);
+        assert_eq!(parse_bounds("<-2147483648"), None);
+        assert_eq!(parse_bounds("99999999999"), None);
+
+        let error = parse_query("Analysis lp:>2147483647").unwrap_err();
+        assert!(error.message.starts_with("Invalid credits"));
+        assert_eq!(error.span, 9..23);
+
+        let error = parse_query("hours:<-2147483648").unwrap_err();
+        assert!(error.message.starts_with("Invalid hours"));
+    }
+
+    #[test]
+    fn test_parse_conflicting_credits() {
+        let parsed = parse_query("lp:>=6 lp:<=9").unwrap();
+        assert_eq!((parsed.filters.min_credits, parsed.filters.max_credits), (Some(6), Some(9)));
+
+        let error = parse_query("lp:>=9 Analysis lp:<=6").unwrap_err();
+        assert_eq!(error.message, "The credit bounds exclude each other");
+        assert_eq!(error.span, 0..22);
+    }
+
+    #[test]
+    fn test_parse_unknown_key() {
+        let error = parse_query("Analysis foo:bar").unwrap_err();
+        assert!(error.message.starts_with("Unknown filter `foo:`"));
+        assert_eq!(error.span, 9..13);
+
+        let error = parse_query("exam:essay").unwrap_err();
+        assert!(error.message.starts_with("Unknown exam category `essay`"));
+
+        let error = parse_query("lang:").unwrap_err();
+        assert_eq!(error.message, "`lang:` needs a value");
+    }
+
+    #[test]
+    fn test_remove_clause() {
+        let input = r#"lp:>=6  Analysis I -"Projekt""#;
+        let parsed = parse_query(input).unwrap();
+        let spans: Vec<Range<usize>> = parsed.clauses.iter().map(|clause| clause.span.clone()).collect();
+        assert_eq!(spans, vec![0..6, 8..18, 19..29]);
+
+        assert_eq!(remove_clause(input, &spans[0]), Some(r#"Analysis I -"Projekt""#.to_string()));
+        assert_eq!(remove_clause(input, &spans[1]), Some(r#"lp:>=6 -"Projekt""#.to_string()));
+        assert_eq!(remove_clause(input, &spans[2]), Some("lp:>=6  Analysis I".to_string()));
+        assert_eq!(remove_clause("lp:6", &(0..4)), None);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::api::{pull_sync_state, push_sync_state};
+use crate::models::{PushOutcome, SearchFilters, SyncState};
+use crate::starred::StarredModules;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[cfg(target_arch = "wasm32")]
+use codee::string::JsonSerdeCodec;
+#[cfg(target_arch = "wasm32")]
+use leptos_use::storage::use_local_storage;
+
+/// Number of pull, merge and push rounds before giving up on a busy sync code
+const MAX_SYNC_ATTEMPTS: usize = 3;
+
+/// The sync code this device is linked to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SyncLink {
+    pub token: String,
+    /// Server revision of the last successful sync
+    pub revision: i32,
+    /// State as of the last successful sync, the common base for merging
+    pub base: SyncState,
+}
+
+pub fn use_sync_link() -> (Signal<Option<SyncLink>>, WriteSignal<Option<SyncLink>>) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let (link, set_link, _remove) = use_local_storage::<Option<SyncLink>, JsonSerdeCodec>("sync_link");
+        (link, set_link)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // SSR fallback - just return dummy signals
+        let link = RwSignal::new(None::<SyncLink>);
+        (link.read_only().into(), link.write_only())
+    }
+}
+
+/// Build the shared state from the local starred modules and filters
+///
+/// The collection filter is left out, collection ids only mean something on
+/// the device that created them.
+pub fn local_state(starred: &StarredModules, filters: &SearchFilters) -> SyncState {
+    let mut starred: Vec<(i32, i32)> = starred.modules.iter().copied().collect();
+    starred.sort();
+    SyncState {
+        starred,
+        filters: SearchFilters {
+            collection_id: None,
+            ..filters.clone()
+        },
+    }
+}
+
+/// Three-way merge of the local and remote state against the last synced base
+///
+/// Stars added or removed on this device since the base are applied on top of
+/// the remote stars, so edits from both sides survive. Filters are a single
+/// unit: local filters win if they changed since the base, otherwise the
+/// remote ones are taken.
+pub fn merge_states(base: &SyncState, local: &SyncState, remote: &SyncState) -> SyncState {
+    let base_stars: BTreeSet<(i32, i32)> = base.starred.iter().copied().collect();
+    let local_stars: BTreeSet<(i32, i32)> = local.starred.iter().copied().collect();
+    let mut stars: BTreeSet<(i32, i32)> = remote.starred.iter().copied().collect();
+
+    stars.extend(local_stars.difference(&base_stars));
+    for removed in base_stars.difference(&local_stars) {
+        stars.remove(removed);
+    }
+
+    let filters = if local.filters != base.filters {
+        local.filters.clone()
+    } else {
+        remote.filters.clone()
+    };
+
+    SyncState {
+        starred: stars.into_iter().collect(),
+        filters,
+    }
+}
+
+/// Pull the remote state, merge it with the local one and push the result
+///
+/// Returns the merged state to apply locally and the updated link. A push that
+/// loses against a concurrent one is retried on the newer server state.
+pub async fn sync_now(link: SyncLink, local: SyncState) -> Result<(SyncState, SyncLink), ServerFnError> {
+    for _ in 0..MAX_SYNC_ATTEMPTS {
+        let remote = pull_sync_state(link.token.clone()).await?;
+        let merged = merge_states(&link.base, &local, &remote.state);
+
+        let revision = if merged == remote.state {
+            remote.revision
+        } else {
+            match push_sync_state(link.token.clone(), remote.revision, merged.clone()).await? {
+                PushOutcome::Saved { revision } => revision,
+                PushOutcome::Conflict(_) => continue,
+            }
+        };
+
+        let link = SyncLink {
+            token: link.token,
+            revision,
+            base: merged.clone(),
+        };
+        return Ok((merged, link));
+    }
+
+    Err(ServerFnError::new("The sync code is being changed from another device, try again"))
+}
+
+/// Link this device to an existing sync code
+///
+/// Stars of both devices are kept, the filters are taken from the sync code.
+pub async fn link_device(token: String, local: SyncState) -> Result<(SyncState, SyncLink), ServerFnError> {
+    let remote = pull_sync_state(token).await?;
+
+    // An empty star base keeps the stars of both devices, unchanged local
+    // filters make the merge take the remote ones
+    let base = SyncState {
+        starred: Vec::new(),
+        filters: local.filters.clone(),
+    };
+    let link = SyncLink {
+        token: remote.token,
+        revision: remote.revision,
+        base,
+    };
+    sync_now(link, local).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: (i32, i32) = (40001, 1);
+    const B: (i32, i32) = (40002, 1);
+    const C: (i32, i32) = (40003, 2);
+
+    fn state(starred: &[(i32, i32)]) -> SyncState {
+        SyncState {
+            starred: starred.to_vec(),
+            filters: SearchFilters::default(),
+        }
+    }
+
+    #[test]
+    fn test_merge_states_keeps_concurrent_adds() {
+        let merged = merge_states(&state(&[A]), &state(&[A, B]), &state(&[A, C]));
+        assert_eq!(merged.starred, vec![A, B, C]);
+    }
+
+    #[test]
+    fn test_merge_states_keeps_concurrent_removes() {
+        let merged = merge_states(&state(&[A, B, C]), &state(&[B, C]), &state(&[A, B]));
+        assert_eq!(merged.starred, vec![B]);
+    }
+
+    #[test]
+    fn test_merge_states_add_versus_remove() {
+        // Local adds B while the remote removes A
+        let merged = merge_states(&state(&[A]), &state(&[A, B]), &state(&[]));
+        assert_eq!(merged.starred, vec![B]);
+
+        // Local removes A while the remote keeps it
+        let merged = merge_states(&state(&[A]), &state(&[]), &state(&[A]));
+        assert!(merged.starred.is_empty());
+
+        // The remote removed A, the unchanged local copy does not bring it back
+        let merged = merge_states(&state(&[A, B]), &state(&[A, B]), &state(&[B]));
+        assert_eq!(merged.starred, vec![B]);
+
+        // Local removes A while the remote re-added it after removing it
+        let merged = merge_states(&state(&[A]), &state(&[]), &state(&[A, C]));
+        assert_eq!(merged.starred, vec![C]);
+    }
+
+    #[test]
+    fn test_merge_states_filters() {
+        let base = state(&[]);
+        let mut local = state(&[]);
+        let mut remote = state(&[]);
+        remote.filters.min_credits = Some(6);
+
+        // Unchanged local filters take the remote ones
+        assert_eq!(merge_states(&base, &local, &remote).filters.min_credits, Some(6));
+
+        // Changed local filters win
+        local.filters.max_credits = Some(9);
+        let merged = merge_states(&base, &local, &remote);
+        assert_eq!(merged.filters.min_credits, None);
+        assert_eq!(merged.filters.max_credits, Some(9));
+    }
+
+    #[test]
+    fn test_local_state_leaves_out_collection() {
+        let filters = SearchFilters {
+            collection_id: Some(3),
+            min_credits: Some(6),
+            ..Default::default()
+        };
+        let state = local_state(&StarredModules::default(), &filters);
+        assert_eq!(state.filters.collection_id, None);
+        assert_eq!(state.filters.min_credits, Some(6));
+    }
+}
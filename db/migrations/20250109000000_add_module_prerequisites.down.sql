@@ -0,0 +1,6 @@
+-- Rollback module prerequisite relations
+
+DROP INDEX IF EXISTS idx_module_prerequisite_required;
+DROP INDEX IF EXISTS idx_module_prerequisite_module;
+DROP TABLE IF EXISTS module_prerequisite;
+DROP TYPE IF EXISTS prerequisite_kind;
@@ -0,0 +1,24 @@
+-- Store prerequisite relations between modules
+-- Mentions of other modules in the requirements text are resolved by the scraper
+-- and stored per module snapshot, typed as mandatory or recommended
+
+CREATE TYPE prerequisite_kind AS ENUM ('mandatory', 'recommended');
+
+CREATE TABLE module_prerequisite (
+    id SERIAL PRIMARY KEY,
+    module_id INTEGER NOT NULL,
+    module_version INTEGER NOT NULL,
+    module_scraping_run_id INTEGER NOT NULL,
+    required_module_id INTEGER NOT NULL,
+    required_module_version INTEGER NOT NULL,
+    kind prerequisite_kind NOT NULL,
+    matched_text TEXT NOT NULL,
+    CONSTRAINT fk_module_prerequisite_module
+        FOREIGN KEY (module_id, module_version, module_scraping_run_id)
+        REFERENCES module(id, version, scraping_run_id) ON DELETE CASCADE,
+    CONSTRAINT unique_module_prerequisite
+        UNIQUE (module_id, module_version, module_scraping_run_id, required_module_id)
+);
+
+CREATE INDEX idx_module_prerequisite_module ON module_prerequisite(module_id, module_version, module_scraping_run_id);
+CREATE INDEX idx_module_prerequisite_required ON module_prerequisite(required_module_id);
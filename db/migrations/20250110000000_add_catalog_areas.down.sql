@@ -0,0 +1,8 @@
+-- Rollback catalog areas
+
+DROP INDEX IF EXISTS idx_module_catalog_area_area;
+DROP INDEX IF EXISTS idx_module_catalog_area_module;
+DROP INDEX IF EXISTS idx_catalog_area_stupo;
+
+DROP TABLE IF EXISTS module_catalog_area;
+DROP TABLE IF EXISTS catalog_area;
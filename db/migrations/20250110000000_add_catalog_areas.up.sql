@@ -0,0 +1,31 @@
+-- Store the catalog areas (Modullisten) of a StuPO and which module snapshots count toward them
+-- Areas are reference data like stupo; the required credits are filled in when MOSES lists them
+
+CREATE TABLE catalog_area (
+    id SERIAL PRIMARY KEY,
+    stupo_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    required_credits INTEGER,
+    CONSTRAINT fk_catalog_area_stupo FOREIGN KEY (stupo_id)
+        REFERENCES stupo(id) ON DELETE CASCADE,
+    CONSTRAINT unique_catalog_area_per_stupo UNIQUE (stupo_id, name)
+);
+
+CREATE TABLE module_catalog_area (
+    id SERIAL PRIMARY KEY,
+    module_id INTEGER NOT NULL,
+    module_version INTEGER NOT NULL,
+    module_scraping_run_id INTEGER NOT NULL,
+    catalog_area_id INTEGER NOT NULL,
+    CONSTRAINT fk_module_catalog_area_module
+        FOREIGN KEY (module_id, module_version, module_scraping_run_id)
+        REFERENCES module(id, version, scraping_run_id) ON DELETE CASCADE,
+    CONSTRAINT fk_module_catalog_area_area FOREIGN KEY (catalog_area_id)
+        REFERENCES catalog_area(id) ON DELETE CASCADE,
+    CONSTRAINT unique_module_catalog_area
+        UNIQUE (module_id, module_version, module_scraping_run_id, catalog_area_id)
+);
+
+CREATE INDEX idx_catalog_area_stupo ON catalog_area(stupo_id);
+CREATE INDEX idx_module_catalog_area_module ON module_catalog_area(module_id, module_version, module_scraping_run_id);
+CREATE INDEX idx_module_catalog_area_area ON module_catalog_area(catalog_area_id);
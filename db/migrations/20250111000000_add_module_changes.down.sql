@@ -0,0 +1,10 @@
+-- Rollback run change reports
+
+DROP INDEX IF EXISTS idx_module_change_field_change;
+DROP INDEX IF EXISTS idx_module_change_module;
+DROP INDEX IF EXISTS idx_module_change_run;
+DROP TABLE IF EXISTS module_change_field;
+DROP TABLE IF EXISTS module_change;
+ALTER TABLE scraping_run DROP CONSTRAINT IF EXISTS fk_scraping_run_previous_run;
+ALTER TABLE scraping_run DROP COLUMN IF EXISTS previous_run_id;
+DROP TYPE IF EXISTS module_change_type;
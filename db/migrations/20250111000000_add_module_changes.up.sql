@@ -0,0 +1,38 @@
+-- Store the change report of a completed run against the previous completed run
+-- Each entry is a module version that is new, disappeared or changed in one of
+-- the compared fields, with the old and new value of every changed field
+
+CREATE TYPE module_change_type AS ENUM ('added', 'removed', 'changed');
+
+ALTER TABLE scraping_run ADD COLUMN previous_run_id INTEGER;
+ALTER TABLE scraping_run
+ADD CONSTRAINT fk_scraping_run_previous_run
+    FOREIGN KEY (previous_run_id)
+    REFERENCES scraping_run(id) ON DELETE SET NULL;
+
+CREATE TABLE module_change (
+    id SERIAL PRIMARY KEY,
+    scraping_run_id INTEGER NOT NULL,
+    module_id INTEGER NOT NULL,
+    module_version INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    change_type module_change_type NOT NULL,
+    CONSTRAINT fk_module_change_run FOREIGN KEY (scraping_run_id)
+        REFERENCES scraping_run(id) ON DELETE CASCADE,
+    CONSTRAINT unique_module_change
+        UNIQUE (scraping_run_id, module_id, module_version)
+);
+
+CREATE TABLE module_change_field (
+    id SERIAL PRIMARY KEY,
+    module_change_id INTEGER NOT NULL,
+    field TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    CONSTRAINT fk_module_change_field_change FOREIGN KEY (module_change_id)
+        REFERENCES module_change(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_module_change_run ON module_change(scraping_run_id);
+CREATE INDEX idx_module_change_module ON module_change(module_id, module_version);
+CREATE INDEX idx_module_change_field_change ON module_change_field(module_change_id);
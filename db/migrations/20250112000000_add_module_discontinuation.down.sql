@@ -0,0 +1,5 @@
+-- Rollback discontinued module marks
+
+DROP INDEX IF EXISTS idx_module_discontinued;
+ALTER TABLE module DROP CONSTRAINT IF EXISTS fk_module_discontinued_run;
+ALTER TABLE module DROP COLUMN IF EXISTS discontinued_in_run_id;
@@ -0,0 +1,12 @@
+-- Mark modules that are missing from a completed full run as discontinued
+-- The mark is set on the latest snapshot of the module version and points to
+-- the first run that no longer listed it. A module that shows up again gets a
+-- new snapshot without the mark.
+
+ALTER TABLE module ADD COLUMN discontinued_in_run_id INTEGER;
+ALTER TABLE module
+ADD CONSTRAINT fk_module_discontinued_run
+    FOREIGN KEY (discontinued_in_run_id)
+    REFERENCES scraping_run(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_module_discontinued ON module(discontinued_in_run_id);
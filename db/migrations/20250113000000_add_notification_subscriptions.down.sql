@@ -0,0 +1,5 @@
+-- Rollback email notification subscriptions
+
+DROP INDEX IF EXISTS idx_notification_subscription_module_subscription;
+DROP TABLE IF EXISTS notification_subscription_module;
+DROP TABLE IF EXISTS notification_subscription;
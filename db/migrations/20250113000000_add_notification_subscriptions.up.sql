@@ -0,0 +1,31 @@
+-- Email notifications about changes to followed module versions
+-- A subscription only receives digests after its address was confirmed. The
+-- token is used in the confirmation and unsubscribe links, the last notified
+-- run keeps digests from covering a run twice.
+
+CREATE TABLE notification_subscription (
+    id SERIAL PRIMARY KEY,
+    email TEXT NOT NULL,
+    token TEXT NOT NULL DEFAULT replace(gen_random_uuid()::text, '-', ''),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    confirmed_at TIMESTAMPTZ,
+    last_notified_run_id INTEGER,
+    CONSTRAINT fk_notification_subscription_run FOREIGN KEY (last_notified_run_id)
+        REFERENCES scraping_run(id) ON DELETE SET NULL,
+    CONSTRAINT unique_notification_subscription_email UNIQUE (email),
+    CONSTRAINT unique_notification_subscription_token UNIQUE (token)
+);
+
+CREATE TABLE notification_subscription_module (
+    id SERIAL PRIMARY KEY,
+    subscription_id INTEGER NOT NULL,
+    module_id INTEGER NOT NULL,
+    module_version INTEGER NOT NULL,
+    CONSTRAINT fk_notification_subscription_module_subscription FOREIGN KEY (subscription_id)
+        REFERENCES notification_subscription(id) ON DELETE CASCADE,
+    CONSTRAINT unique_notification_subscription_module
+        UNIQUE (subscription_id, module_id, module_version)
+);
+
+CREATE INDEX idx_notification_subscription_module_subscription
+    ON notification_subscription_module(subscription_id);
@@ -0,0 +1,3 @@
+-- Rollback anonymous sync states
+
+DROP TABLE IF EXISTS sync_state;
@@ -0,0 +1,13 @@
+-- Anonymous sync of starred modules and search filters between devices
+-- The state is stored as JSON under a random token. The revision is bumped on
+-- every write so clients can detect concurrent edits and merge them.
+
+CREATE TABLE sync_state (
+    id SERIAL PRIMARY KEY,
+    token TEXT NOT NULL DEFAULT replace(gen_random_uuid()::text, '-', ''),
+    state JSONB NOT NULL,
+    revision INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_sync_state_token UNIQUE (token)
+);
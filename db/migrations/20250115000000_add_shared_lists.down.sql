@@ -0,0 +1,3 @@
+-- Rollback shared module lists
+
+DROP TABLE IF EXISTS shared_list;
@@ -0,0 +1,11 @@
+-- Read-only module lists shared by link
+-- A list is written once and never changed, the link only references the token.
+
+CREATE TABLE shared_list (
+    id SERIAL PRIMARY KEY,
+    token TEXT NOT NULL DEFAULT replace(gen_random_uuid()::text, '-', ''),
+    name TEXT NOT NULL,
+    entries JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_shared_list_token UNIQUE (token)
+);
@@ -0,0 +1,3 @@
+-- Rollback full run marks
+
+ALTER TABLE scraping_run DROP COLUMN IF EXISTS full_run;
@@ -0,0 +1,7 @@
+-- Remember which runs covered the full module list
+-- Only full runs can tell that a module disappeared, so change reports compare
+-- against the previous full run and only full runs report removed modules.
+-- Earlier runs can not be told apart and stay usable as a base.
+
+ALTER TABLE scraping_run ADD COLUMN full_run BOOLEAN NOT NULL DEFAULT FALSE;
+UPDATE scraping_run SET full_run = TRUE WHERE status = 'completed';
@@ -0,0 +1,3 @@
+-- Rollback subscription requests, open requests are dropped
+
+DROP TABLE IF EXISTS notification_subscription_request;
@@ -0,0 +1,31 @@
+-- Subscription requests waiting for their address to be confirmed
+-- A request only creates or replaces the subscription of its address once the
+-- link in the confirmation email is opened, so knowing an address is not
+-- enough to change what it follows. There is at most one open request per
+-- address. Unconfirmed subscriptions move over and keep their tokens, so links
+-- in confirmation emails sent before stay valid.
+
+CREATE TABLE notification_subscription_request (
+    id SERIAL PRIMARY KEY,
+    email TEXT NOT NULL,
+    token TEXT NOT NULL DEFAULT replace(gen_random_uuid()::text, '-', ''),
+    module_ids INTEGER[] NOT NULL,
+    module_versions INTEGER[] NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_notification_subscription_request_email UNIQUE (email),
+    CONSTRAINT unique_notification_subscription_request_token UNIQUE (token)
+);
+
+INSERT INTO notification_subscription_request (email, token, module_ids, module_versions, created_at)
+SELECT
+    s.email,
+    s.token,
+    COALESCE(array_agg(sm.module_id ORDER BY sm.id) FILTER (WHERE sm.id IS NOT NULL), '{}'),
+    COALESCE(array_agg(sm.module_version ORDER BY sm.id) FILTER (WHERE sm.id IS NOT NULL), '{}'),
+    s.created_at
+FROM notification_subscription s
+LEFT JOIN notification_subscription_module sm ON sm.subscription_id = s.id
+WHERE s.confirmed_at IS NULL
+GROUP BY s.id;
+
+DELETE FROM notification_subscription WHERE confirmed_at IS NULL;
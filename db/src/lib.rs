@@ -1,6 +1,7 @@
 // Core modules - always available
 pub mod error;
 pub mod models;
+pub mod snapshot;
 
 // Database connectivity - only available with "database" feature
 #[cfg(feature = "database")]
@@ -9,9 +10,13 @@ pub mod connection;
 // Re-exports for convenience
 pub use error::{DbError, DbResult};
 pub use models::*;
+pub use snapshot::{diff_snapshots, FieldChange, ModuleSnapshot};
 
 #[cfg(feature = "database")]
 pub use connection::{create_pool, create_pool_from_env, run_migrations, DbConfig};
 
+#[cfg(feature = "database")]
+pub use snapshot::{load_module_snapshots, load_run_snapshots, load_version_snapshots};
+
 #[cfg(feature = "database")]
 pub use sqlx::PgPool;
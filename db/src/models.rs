@@ -38,6 +38,23 @@ pub enum ComponentRotation {
     WiSeSoSe,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(sqlx::Type))]
+#[cfg_attr(feature = "database", sqlx(type_name = "prerequisite_kind", rename_all = "lowercase"))]
+pub enum PrerequisiteKind {
+    Mandatory,
+    Recommended,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(sqlx::Type))]
+#[cfg_attr(feature = "database", sqlx(type_name = "module_change_type", rename_all = "lowercase"))]
+pub enum ModuleChangeType {
+    Added,
+    Removed,
+    Changed,
+}
+
 // ============================================================================
 // Table Structs
 // ============================================================================
@@ -72,6 +89,7 @@ pub struct Module {
     pub requirements: Option<String>,
     pub additional_info: Option<String>,
     pub moses_link: String,
+    pub discontinued_in_run_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -198,3 +216,54 @@ pub struct ModuleCatalogUsage {
     pub first_usage: String,
     pub last_usage: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(sqlx::FromRow))]
+pub struct ModulePrerequisite {
+    pub id: i32,
+    pub module_id: i32,
+    pub module_version: i32,
+    pub required_module_id: i32,
+    pub required_module_version: i32,
+    pub kind: PrerequisiteKind,
+    pub matched_text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(sqlx::FromRow))]
+pub struct CatalogArea {
+    pub id: i32,
+    pub stupo_id: i32,
+    pub name: String,
+    pub required_credits: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(sqlx::FromRow))]
+pub struct ModuleCatalogArea {
+    pub id: i32,
+    pub module_id: i32,
+    pub module_version: i32,
+    pub catalog_area_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(sqlx::FromRow))]
+pub struct ModuleChange {
+    pub id: i32,
+    pub scraping_run_id: i32,
+    pub module_id: i32,
+    pub module_version: i32,
+    pub title: String,
+    pub change_type: ModuleChangeType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(sqlx::FromRow))]
+pub struct ModuleChangeField {
+    pub id: i32,
+    pub module_change_id: i32,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
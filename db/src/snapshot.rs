@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "database")]
+use crate::error::DbResult;
+#[cfg(feature = "database")]
+use sqlx::PgPool;
+
+/// The fields of a module that are compared between snapshots
+///
+/// Components and exam components are flattened into one display line each, so
+/// that a change shows up as a removed and an added line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleSnapshot {
+    pub id: i32,
+    pub version: i32,
+    pub scraping_run_id: i32,
+    pub title: String,
+    pub credits: i32,
+    pub responsible_person: Option<String>,
+    pub learning_result: Option<String>,
+    pub content: Option<String>,
+    pub components: Vec<String>,
+    pub exam_form: Option<String>,
+    pub exam_components: Vec<String>,
+}
+
+/// A single field that differs between two snapshots
+///
+/// List fields are joined with newlines.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// Compare two snapshots of a module, in field order of [`ModuleSnapshot`]
+pub fn diff_snapshots(old: &ModuleSnapshot, new: &ModuleSnapshot) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    let mut compare = |field: &str, old_value: Option<String>, new_value: Option<String>| {
+        if old_value != new_value {
+            changes.push(FieldChange {
+                field: field.to_string(),
+                old_value,
+                new_value,
+            });
+        }
+    };
+
+    compare("title", Some(old.title.clone()), Some(new.title.clone()));
+    compare("credits", Some(old.credits.to_string()), Some(new.credits.to_string()));
+    compare("responsible_person", old.responsible_person.clone(), new.responsible_person.clone());
+    compare("learning_result", old.learning_result.clone(), new.learning_result.clone());
+    compare("content", old.content.clone(), new.content.clone());
+    compare("components", join_lines(&old.components), join_lines(&new.components));
+    compare("exam_form", old.exam_form.clone(), new.exam_form.clone());
+    compare("exam_components", join_lines(&old.exam_components), join_lines(&new.exam_components));
+
+    changes
+}
+
+fn join_lines(lines: &[String]) -> Option<String> {
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Load every snapshot of a module version, oldest run first
+#[cfg(feature = "database")]
+pub async fn load_module_snapshots(pool: &PgPool, id: i32, version: i32) -> DbResult<Vec<ModuleSnapshot>> {
+    load_snapshots(pool, Some(id), Some(version), None).await
+}
+
+/// Load the latest snapshot of every version of a module number, oldest version first
+#[cfg(feature = "database")]
+pub async fn load_version_snapshots(pool: &PgPool, id: i32) -> DbResult<Vec<ModuleSnapshot>> {
+    let mut latest: Vec<ModuleSnapshot> = Vec::new();
+    // Snapshots come ordered by run, so a later one replaces the earlier one of its version
+    for snapshot in load_snapshots(pool, Some(id), None, None).await? {
+        match latest.iter_mut().find(|s| s.version == snapshot.version) {
+            Some(existing) => *existing = snapshot,
+            None => latest.push(snapshot),
+        }
+    }
+    latest.sort_by_key(|s| s.version);
+    Ok(latest)
+}
+
+/// Load the snapshots of all modules scraped in a run
+#[cfg(feature = "database")]
+pub async fn load_run_snapshots(pool: &PgPool, scraping_run_id: i32) -> DbResult<Vec<ModuleSnapshot>> {
+    load_snapshots(pool, None, None, Some(scraping_run_id)).await
+}
+
+#[cfg(feature = "database")]
+async fn load_snapshots(
+    pool: &PgPool,
+    id: Option<i32>,
+    version: Option<i32>,
+    scraping_run_id: Option<i32>,
+) -> DbResult<Vec<ModuleSnapshot>> {
+    use std::collections::HashMap;
+
+    let mut snapshots: Vec<ModuleSnapshot> = sqlx::query!(
+        r#"
+        SELECT
+            m.id, m.version, m.scraping_run_id, m.title, m.credits,
+            m.learning_result, m.content,
+            rp.name as "responsible_person?"
+        FROM module m
+        LEFT JOIN responsible_person rp ON m.responsible_id = rp.id
+        WHERE ($1::int IS NULL OR m.id = $1)
+          AND ($2::int IS NULL OR m.version = $2)
+          AND ($3::int IS NULL OR m.scraping_run_id = $3)
+        ORDER BY m.scraping_run_id, m.id, m.version
+        "#,
+        id,
+        version,
+        scraping_run_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| ModuleSnapshot {
+        id: row.id,
+        version: row.version,
+        scraping_run_id: row.scraping_run_id,
+        title: row.title,
+        credits: row.credits,
+        responsible_person: row.responsible_person,
+        learning_result: row.learning_result,
+        content: row.content,
+        components: Vec::new(),
+        exam_form: None,
+        exam_components: Vec::new(),
+    })
+    .collect();
+
+    let index: HashMap<(i32, i32, i32), usize> = snapshots
+        .iter()
+        .enumerate()
+        .map(|(i, s)| ((s.id, s.version, s.scraping_run_id), i))
+        .collect();
+
+    let components = sqlx::query!(
+        r#"
+        SELECT
+            module_id, module_version, module_scraping_run_id,
+            component_type, module_name, number, rotation::text as "rotation!", sws, language
+        FROM module_component
+        WHERE ($1::int IS NULL OR module_id = $1)
+          AND ($2::int IS NULL OR module_version = $2)
+          AND ($3::int IS NULL OR module_scraping_run_id = $3)
+        ORDER BY component_type, number
+        "#,
+        id,
+        version,
+        scraping_run_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for c in components {
+        if let Some(&i) = index.get(&(c.module_id, c.module_version, c.module_scraping_run_id)) {
+            let name = c.module_name.unwrap_or(c.number);
+            snapshots[i].components.push(format!(
+                "{} {} ({}, {} SWS, {})",
+                c.component_type, name, c.rotation, c.sws, c.language
+            ));
+        }
+    }
+
+    let exams = sqlx::query!(
+        r#"
+        SELECT
+            e.module_id, e.module_version, e.module_scraping_run_id,
+            e.exam_type, e.graded,
+            ec.name as "component_name?", ec.points as "points?", ec.category::text as "category?"
+        FROM exam e
+        LEFT JOIN exam_component ec ON ec.exam_id = e.id
+        WHERE ($1::int IS NULL OR e.module_id = $1)
+          AND ($2::int IS NULL OR e.module_version = $2)
+          AND ($3::int IS NULL OR e.module_scraping_run_id = $3)
+        ORDER BY e.id, ec.id
+        "#,
+        id,
+        version,
+        scraping_run_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for e in exams {
+        if let Some(&i) = index.get(&(e.module_id, e.module_version, e.module_scraping_run_id)) {
+            let snapshot = &mut snapshots[i];
+            snapshot.exam_form = Some(format!(
+                "{}{}",
+                e.exam_type,
+                if e.graded { ", graded" } else { ", ungraded" }
+            ));
+            if let (Some(name), Some(points), Some(category)) = (e.component_name, e.points, e.category) {
+                snapshot
+                    .exam_components
+                    .push(format!("{} ({}, {} points)", name, category, points));
+            }
+        }
+    }
+
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> ModuleSnapshot {
+        ModuleSnapshot {
+            id: 40033,
+            version: 3,
+            scraping_run_id: 1,
+            title: "Analysis I".to_string(),
+            credits: 6,
+            responsible_person: Some("Jane Doe".to_string()),
+            learning_result: None,
+            content: Some("Folgen und Reihen".to_string()),
+            components: vec!["VL Analysis I (WiSe, 4 SWS, Deutsch)".to_string()],
+            exam_form: Some("Schriftliche Prüfung, graded".to_string()),
+            exam_components: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let old = snapshot();
+        let mut new = snapshot();
+        new.scraping_run_id = 2;
+        new.credits = 9;
+        new.components.push("UE Analysis I (WiSe, 2 SWS, Deutsch)".to_string());
+
+        assert!(diff_snapshots(&old, &old).is_empty());
+
+        let changes = diff_snapshots(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, "credits");
+        assert_eq!(changes[0].old_value.as_deref(), Some("6"));
+        assert_eq!(changes[0].new_value.as_deref(), Some("9"));
+        assert_eq!(changes[1].field, "components");
+    }
+}
@@ -0,0 +1,246 @@
+use anyhow::Result;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+
+/// A module version that is new, disappeared or changed between two runs
+#[derive(Debug, Clone)]
+pub struct ModuleChangeEntry {
+    pub module_id: i32,
+    pub module_version: i32,
+    pub title: String,
+    pub change_type: db::ModuleChangeType,
+    pub fields: Vec<db::FieldChange>,
+}
+
+/// Counts of a stored change report
+#[derive(Debug, Clone, Default)]
+pub struct ChangeSummary {
+    pub previous_run_id: Option<i32>,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    /// Module versions newly marked as discontinued, only set by full runs
+    pub discontinued: u64,
+}
+
+/// Compare the snapshots of two runs, ordered by module number and version
+///
+/// `listed` holds the (number, version) keys of the full module list for runs
+/// that covered all modules. Only those runs report removed modules, and
+/// listed modules whose scrape failed or was skipped do not count as removed.
+/// Partial runs pass `None`.
+pub fn compare_runs(
+    previous: &[db::ModuleSnapshot],
+    current: &[db::ModuleSnapshot],
+    listed: Option<&[(i32, i32)]>,
+) -> Vec<ModuleChangeEntry> {
+    let previous_by_key: HashMap<(i32, i32), &db::ModuleSnapshot> =
+        previous.iter().map(|s| ((s.id, s.version), s)).collect();
+    let mut present: HashSet<(i32, i32)> = current.iter().map(|s| (s.id, s.version)).collect();
+
+    let mut changes = Vec::new();
+
+    for snapshot in current {
+        let change = match previous_by_key.get(&(snapshot.id, snapshot.version)) {
+            None => Some((db::ModuleChangeType::Added, Vec::new())),
+            Some(old) => {
+                let fields = db::diff_snapshots(old, snapshot);
+                (!fields.is_empty()).then_some((db::ModuleChangeType::Changed, fields))
+            }
+        };
+        if let Some((change_type, fields)) = change {
+            changes.push(ModuleChangeEntry {
+                module_id: snapshot.id,
+                module_version: snapshot.version,
+                title: snapshot.title.clone(),
+                change_type,
+                fields,
+            });
+        }
+    }
+
+    let Some(listed) = listed else {
+        changes.sort_by_key(|c| (c.module_id, c.module_version));
+        return changes;
+    };
+    present.extend(listed.iter().copied());
+
+    for snapshot in previous {
+        if !present.contains(&(snapshot.id, snapshot.version)) {
+            changes.push(ModuleChangeEntry {
+                module_id: snapshot.id,
+                module_version: snapshot.version,
+                title: snapshot.title.clone(),
+                change_type: db::ModuleChangeType::Removed,
+                fields: Vec::new(),
+            });
+        }
+    }
+
+    changes.sort_by_key(|c| (c.module_id, c.module_version));
+    changes
+}
+
+/// Generate and store the change report of a run against the previous full run
+///
+/// Partial runs never serve as the base, otherwise every module they did not
+/// cover would show up as added in the next full run. `listed` is passed on to
+/// [`compare_runs`]. Regenerating replaces the stored report. Without a
+/// previous full run there is nothing to compare against and no report is stored.
+pub async fn generate_change_report(
+    pool: &PgPool,
+    scraping_run_id: i32,
+    listed: Option<&[(i32, i32)]>,
+) -> Result<ChangeSummary> {
+    let previous_run_id = sqlx::query!(
+        r#"
+        SELECT id
+        FROM scraping_run
+        WHERE status = 'completed' AND full_run AND id < $1
+        ORDER BY id DESC
+        LIMIT 1
+        "#,
+        scraping_run_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|r| r.id);
+
+    let Some(previous_run_id) = previous_run_id else {
+        return Ok(ChangeSummary::default());
+    };
+
+    let previous = db::load_run_snapshots(pool, previous_run_id).await?;
+    let current = db::load_run_snapshots(pool, scraping_run_id).await?;
+    let changes = compare_runs(&previous, &current, listed);
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "UPDATE scraping_run SET previous_run_id = $1 WHERE id = $2",
+        previous_run_id,
+        scraping_run_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM module_change WHERE scraping_run_id = $1", scraping_run_id)
+        .execute(&mut *tx)
+        .await?;
+
+    for change in &changes {
+        let change_id = sqlx::query!(
+            r#"
+            INSERT INTO module_change (scraping_run_id, module_id, module_version, title, change_type)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            scraping_run_id,
+            change.module_id,
+            change.module_version,
+            change.title,
+            change.change_type as db::ModuleChangeType
+        )
+        .fetch_one(&mut *tx)
+        .await?
+        .id;
+
+        for field in &change.fields {
+            sqlx::query!(
+                r#"
+                INSERT INTO module_change_field (module_change_id, field, old_value, new_value)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                change_id,
+                field.field,
+                field.old_value,
+                field.new_value
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    let count = |change_type: db::ModuleChangeType| changes.iter().filter(|c| c.change_type == change_type).count();
+
+    Ok(ChangeSummary {
+        previous_run_id: Some(previous_run_id),
+        added: count(db::ModuleChangeType::Added),
+        removed: count(db::ModuleChangeType::Removed),
+        changed: count(db::ModuleChangeType::Changed),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(id: i32, version: i32, credits: i32) -> db::ModuleSnapshot {
+        db::ModuleSnapshot {
+            id,
+            version,
+            scraping_run_id: 1,
+            title: format!("Module {}", id),
+            credits,
+            responsible_person: None,
+            learning_result: None,
+            content: None,
+            components: Vec::new(),
+            exam_form: None,
+            exam_components: Vec::new(),
+        }
+    }
+
+    fn summarize(changes: &[ModuleChangeEntry]) -> Vec<(i32, i32, db::ModuleChangeType)> {
+        changes
+            .iter()
+            .map(|c| (c.module_id, c.module_version, c.change_type))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_runs() {
+        let previous = vec![snapshot(1, 1, 6), snapshot(2, 1, 6), snapshot(3, 1, 6)];
+        let current = vec![snapshot(1, 1, 6), snapshot(2, 1, 9), snapshot(3, 2, 6)];
+
+        let listed = [(1, 1), (2, 1), (3, 2)];
+        let changes = compare_runs(&previous, &current, Some(&listed));
+        let summary = summarize(&changes);
+
+        assert_eq!(
+            summary,
+            vec![
+                (2, 1, db::ModuleChangeType::Changed),
+                (3, 1, db::ModuleChangeType::Removed),
+                (3, 2, db::ModuleChangeType::Added),
+            ]
+        );
+        assert_eq!(changes[0].fields.len(), 1);
+        assert_eq!(changes[0].fields[0].field, "credits");
+    }
+
+    #[test]
+    fn test_compare_runs_keeps_unscraped_modules() {
+        let previous = vec![snapshot(1, 1, 6), snapshot(2, 1, 6), snapshot(3, 1, 6)];
+        let current = vec![snapshot(1, 1, 9)];
+
+        // Module 2 failed to scrape in a full run, module 3 is gone
+        let listed = [(1, 1), (2, 1)];
+        assert_eq!(
+            summarize(&compare_runs(&previous, &current, Some(&listed))),
+            vec![
+                (1, 1, db::ModuleChangeType::Changed),
+                (3, 1, db::ModuleChangeType::Removed),
+            ]
+        );
+
+        // A partial run never reports removals
+        assert_eq!(
+            summarize(&compare_runs(&previous, &current, None)),
+            vec![(1, 1, db::ModuleChangeType::Changed)]
+        );
+    }
+}
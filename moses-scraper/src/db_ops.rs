@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use sqlx::PgPool;
 
 use crate::mapper::MappedModuleData;
+use crate::prerequisites::PrerequisiteMatch;
 
 pub async fn insert_module_data(pool: &PgPool, data: MappedModuleData) -> Result<()> {
     // Use a transaction to ensure atomicity
@@ -136,6 +137,23 @@ pub async fn insert_module_data(pool: &PgPool, data: MappedModuleData) -> Result
         .execute(&mut *tx)
         .await
         .context("Failed to insert module catalog usage")?;
+
+        for catalog_area_id in usage.catalog_area_ids {
+            sqlx::query!(
+                r#"
+                INSERT INTO module_catalog_area (module_id, module_version, module_scraping_run_id, catalog_area_id)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT DO NOTHING
+                "#,
+                usage.module_id,
+                usage.module_version,
+                usage.module_scraping_run_id,
+                catalog_area_id
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert module catalog area")?;
+        }
     }
 
     // Insert exam if present
@@ -183,3 +201,40 @@ pub async fn insert_module_data(pool: &PgPool, data: MappedModuleData) -> Result
 
     Ok(())
 }
+
+pub async fn insert_prerequisites(
+    pool: &PgPool,
+    module_id: i32,
+    module_version: i32,
+    scraping_run_id: i32,
+    prerequisites: &[PrerequisiteMatch],
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for prerequisite in prerequisites {
+        sqlx::query!(
+            r#"
+            INSERT INTO module_prerequisite (
+                module_id, module_version, module_scraping_run_id,
+                required_module_id, required_module_version, kind, matched_text
+            )
+            VALUES ($1, $2, $3, $4, $5, $6::prerequisite_kind, $7)
+            ON CONFLICT (module_id, module_version, module_scraping_run_id, required_module_id) DO NOTHING
+            "#,
+            module_id,
+            module_version,
+            scraping_run_id,
+            prerequisite.required_module_id,
+            prerequisite.required_module_version,
+            prerequisite.kind as db::PrerequisiteKind,
+            prerequisite.matched_text
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert module prerequisite")?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
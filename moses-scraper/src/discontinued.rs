@@ -0,0 +1,40 @@
+use anyhow::Result;
+use sqlx::PgPool;
+
+/// Mark module versions that a full run no longer listed as discontinued
+///
+/// `listed` holds the (number, version) keys of every module in the run's
+/// export, including the ones that failed or were skipped, so that scraping
+/// errors do not discontinue a module. The mark is set on the latest snapshot
+/// and keeps the first run that missed the module. Returns the number of
+/// newly marked module versions.
+pub async fn mark_discontinued(pool: &PgPool, scraping_run_id: i32, listed: &[(i32, i32)]) -> Result<u64> {
+    let (ids, versions): (Vec<i32>, Vec<i32>) = listed.iter().copied().unzip();
+
+    let result = sqlx::query!(
+        r#"
+        WITH latest_modules AS (
+            SELECT DISTINCT ON (id, version) id, version, scraping_run_id
+            FROM module
+            ORDER BY id, version, scraping_run_id DESC
+        ),
+        listed AS (
+            SELECT * FROM UNNEST($2::int[], $3::int[]) AS l(id, version)
+        )
+        UPDATE module m
+        SET discontinued_in_run_id = $1
+        FROM latest_modules lm
+        WHERE m.id = lm.id AND m.version = lm.version AND m.scraping_run_id = lm.scraping_run_id
+          AND m.scraping_run_id < $1
+          AND m.discontinued_in_run_id IS NULL
+          AND NOT EXISTS (SELECT 1 FROM listed l WHERE l.id = m.id AND l.version = m.version)
+        "#,
+        scraping_run_id,
+        &ids,
+        &versions
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
@@ -3,9 +3,14 @@ pub mod search;
 pub mod module;
 pub mod mapper;
 pub mod db_ops;
+pub mod prerequisites;
 pub mod runner;
+pub mod changes;
+pub mod discontinued;
 
 // Re-export commonly used types
 pub use models::*;
-pub use runner::{ScraperConfig, ScraperProgress, ScraperEvent, run_scraper};
+pub use runner::{ScraperConfig, ScraperProgress, ScraperEvent, run_scraper, complete_run, fail_run};
+pub use changes::{ChangeSummary, ModuleChangeEntry, compare_runs, generate_change_report};
+pub use discontinued::mark_discontinued;
 pub use search::{ModuleRef, CsvValidationResult, validate_csv_content, parse_csv_content};
@@ -4,7 +4,7 @@ use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::sync::Arc;
 
-use moses_scraper::{search, runner::{ScraperConfig, ScraperEvent, run_scraper}};
+use moses_scraper::{search, runner::{ScraperConfig, ScraperEvent, run_scraper, complete_run}};
 
 #[derive(Parser, Debug)]
 #[command(name = "moses-scraper")]
@@ -94,6 +94,12 @@ async fn main() -> Result<()> {
 
     let modules = search::fetch_all_modules("", args.limit).await?;
 
+    // Only a run over the whole export can tell that a module was discontinued
+    let listed: Option<Vec<(i32, i32)>> = args
+        .limit
+        .is_none()
+        .then(|| modules.iter().map(|m| (m.number, m.version)).collect());
+
     spinner.finish_and_clear();
     println!(
         "{} Found {} modules",
@@ -194,24 +200,8 @@ async fn main() -> Result<()> {
     let failed = result.failed;
     let skipped = result.skipped;
 
-    // Update scraping run with final statistics
-    sqlx::query!(
-        r#"
-        UPDATE scraping_run
-        SET completed_at = NOW(),
-            status = 'completed',
-            successful_modules = $1,
-            failed_modules = $2,
-            skipped_modules = $3
-        WHERE id = $4
-        "#,
-        successful as i32,
-        failed as i32,
-        skipped as i32,
-        scraping_run_id
-    )
-    .execute(&*pool)
-    .await?;
+    // Mark the run as completed and compare it to the previous one
+    let changes = complete_run(&pool, scraping_run_id, &result, listed.as_deref()).await?;
 
     // Print summary
     println!();
@@ -222,6 +212,19 @@ async fn main() -> Result<()> {
     println!("  {} modules skipped (auth required)", skipped.to_string().yellow().bold());
     println!("  {} modules failed", failed.to_string().red().bold());
     println!();
+    match changes.previous_run_id {
+        Some(previous_run_id) => {
+            println!("{}", format!("Changes since run {}", previous_run_id).bright_cyan().bold());
+            println!("  {} new module versions", changes.added.to_string().green().bold());
+            println!("  {} modules removed", changes.removed.to_string().red().bold());
+            println!("  {} modules changed", changes.changed.to_string().yellow().bold());
+        }
+        None => println!("{}", "No previous completed run to compare against".bright_black()),
+    }
+    if listed.is_some() {
+        println!("  {} modules newly discontinued", changes.discontinued.to_string().red().bold());
+    }
+    println!();
 
     Ok(())
 }
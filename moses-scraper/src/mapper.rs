@@ -83,6 +83,7 @@ pub struct StudyProgramUsageData {
     pub stupo_id: i32,
     pub first_usage: String,
     pub last_usage: String,
+    pub catalog_area_ids: Vec<i32>,
 }
 
 pub struct ExamData {
@@ -301,6 +302,12 @@ async fn map_study_program_usage(pool: &PgPool, module_id: i32, module_version:
         &usage.stupo_link.unwrap_or_else(|| "https://www.tu-berlin.de".to_string())
     ).await?;
 
+    // Get or create the catalog areas the module is listed in
+    let mut catalog_area_ids = Vec::new();
+    for area in &usage.catalog_areas {
+        catalog_area_ids.push(get_or_create_catalog_area(pool, stupo_id, &area.name, area.required_credits).await?);
+    }
+
     Ok(StudyProgramUsageData {
         module_id,
         module_version,
@@ -308,6 +315,7 @@ async fn map_study_program_usage(pool: &PgPool, module_id: i32, module_version:
         stupo_id,
         first_usage: usage.first_usage,
         last_usage: usage.last_usage,
+        catalog_area_ids,
     })
 }
 
@@ -394,6 +402,19 @@ async fn get_or_create_stupo(pool: &PgPool, study_program_id: i32, name: &str, l
     Ok(result.id)
 }
 
+async fn get_or_create_catalog_area(pool: &PgPool, stupo_id: i32, name: &str, required_credits: Option<i32>) -> Result<i32> {
+    let result = sqlx::query!(
+        "INSERT INTO catalog_area (stupo_id, name, required_credits) VALUES ($1, $2, $3) ON CONFLICT (stupo_id, name) DO UPDATE SET required_credits = COALESCE($3, catalog_area.required_credits) RETURNING id",
+        stupo_id,
+        name,
+        required_credits
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result.id)
+}
+
 fn map_exam(module_id: i32, module_version: i32, scraping_run_id: i32, scraped_exam: ScrapedExam) -> Result<(ExamData, Vec<ExamComponentData>)> {
     let exam_data = ExamData {
         module_id,
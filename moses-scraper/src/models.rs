@@ -102,4 +102,11 @@ pub struct ScrapedStudyProgramUsage {
     pub stupo_link: Option<String>,
     pub first_usage: String,
     pub last_usage: String,
+    pub catalog_areas: Vec<ScrapedCatalogArea>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrapedCatalogArea {
+    pub name: String,
+    pub required_credits: Option<i32>,
 }
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use scraper::{Html, Selector};
 use tokio::time::{sleep, Duration};
 
-use crate::models::{ScrapedModule, ScrapedComponent, ScrapedWorkload, ScrapedStudyProgramUsage, ScrapedExam, ScrapedExamComponent};
+use crate::models::{ScrapedModule, ScrapedComponent, ScrapedWorkload, ScrapedStudyProgramUsage, ScrapedCatalogArea, ScrapedExam, ScrapedExamComponent};
 
 pub async fn fetch_module_details(url: &str, retries: u32) -> Result<Option<ScrapedModule>> {
     let mut attempts = 0;
@@ -504,9 +504,20 @@ fn parse_verwendung(document: &Html, module: &mut ScrapedModule) {
             continue;
         }
 
+        // Index of the usage the following expand rows belong to
+        let mut current_usage: Option<usize> = None;
+
         for row in rows.iter().skip(1) {
             let cells: Vec<_> = row.select(&cell_selector).collect();
             if cells.len() < 5 {
+                // Expanded rows list the catalog areas (Modullisten) of the usage above
+                if let Some(usage) = current_usage.and_then(|i| module.study_programs.get_mut(i)) {
+                    for area in parse_catalog_areas(row) {
+                        if !usage.catalog_areas.iter().any(|a| a.name == area.name) {
+                            usage.catalog_areas.push(area);
+                        }
+                    }
+                }
                 continue;
             }
 
@@ -530,12 +541,47 @@ fn parse_verwendung(document: &Html, module: &mut ScrapedModule) {
                     stupo_link: None,
                     first_usage,
                     last_usage,
+                    catalog_areas: Vec::new(),
                 });
+                current_usage = Some(module.study_programs.len() - 1);
+            } else {
+                current_usage = None;
             }
         }
     }
 }
 
+/// Catalog areas listed in an expanded usage row
+///
+/// Areas are the list items of the row, rows without a list (labels, scripts)
+/// carry no areas.
+fn parse_catalog_areas(row: &scraper::ElementRef) -> Vec<ScrapedCatalogArea> {
+    let item_selector = Selector::parse("li").unwrap();
+
+    // Only leaf items, nested lists would otherwise repeat their children's text
+    row.select(&item_selector)
+        .filter(|li| li.select(&item_selector).next().is_none())
+        .filter_map(|li| parse_catalog_area(&li.text().collect::<String>()))
+        .collect()
+}
+
+fn parse_catalog_area(label: &str) -> Option<ScrapedCatalogArea> {
+    let name = label.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return None;
+    }
+
+    let credit_regex = regex::Regex::new(r"(\d+)\s*(?:LP|ECTS)").unwrap();
+    let required_credits = credit_regex
+        .captures(&name)
+        .and_then(|caps| caps[1].parse::<i32>().ok());
+
+    Some(ScrapedCatalogArea {
+        name,
+        required_credits,
+    })
+}
+
 fn parse_additional_info(document: &Html, module: &mut ScrapedModule) {
     let body_selector = Selector::parse("body").unwrap();
     if let Some(body) = document.select(&body_selector).next() {
@@ -962,3 +1008,51 @@ fn parse_exam(document: &Html, module: &mut ScrapedModule) {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn areas_of(html: &str) -> Vec<ScrapedCatalogArea> {
+        let document = Html::parse_document(html);
+        let row_selector = Selector::parse("tr").unwrap();
+        let row = document.select(&row_selector).next().unwrap();
+        parse_catalog_areas(&row)
+    }
+
+    #[test]
+    fn test_parse_catalog_areas() {
+        let areas = areas_of(
+            r#"<table><tr><td colspan="6">
+                <span>Modullisten:</span>
+                <ul>
+                    <li>Wahlpflichtbereich
+                        <ul>
+                            <li>Informatik   Vertiefung (18 LP)</li>
+                            <li>Freie Wahl</li>
+                        </ul>
+                    </li>
+                </ul>
+            </td></tr></table>"#,
+        );
+
+        assert_eq!(areas.len(), 2);
+        assert_eq!(areas[0].name, "Informatik Vertiefung (18 LP)");
+        assert_eq!(areas[0].required_credits, Some(18));
+        assert_eq!(areas[1].name, "Freie Wahl");
+        assert_eq!(areas[1].required_credits, None);
+    }
+
+    #[test]
+    fn test_parse_catalog_areas_without_list() {
+        let areas = areas_of(
+            r#"<table><tr><td colspan="6">
+                <span>Modullisten:</span>
+                   keine
+                <script>$(function() { toggle(); });</script>
+            </td></tr></table>"#,
+        );
+
+        assert!(areas.is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::db_ops;
+
+/// A module that prerequisite mentions can be resolved against
+#[derive(Debug, Clone)]
+pub struct KnownModule {
+    pub id: i32,
+    pub version: i32,
+    pub title: String,
+}
+
+/// A mention of another module found in a requirements text
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrerequisiteMatch {
+    pub required_module_id: i32,
+    pub required_module_version: i32,
+    pub kind: db::PrerequisiteKind,
+    pub matched_text: String,
+}
+
+/// Titles shorter than this are too generic to be matched reliably
+const MIN_TITLE_LENGTH: usize = 10;
+
+/// Module number references, e.g. "#40033" or "Modul 40033"
+static NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"#?\b(\d{5,6})\b").unwrap());
+
+/// Recognizes mentions of a fixed set of known modules, built once per run
+pub struct PrerequisiteMatcher<'a> {
+    /// Newest version per module number
+    by_id: HashMap<i32, &'a KnownModule>,
+    /// Lowercased title to the module it names
+    by_title: HashMap<String, &'a KnownModule>,
+    /// Case-insensitive alternation of all titles, longest first
+    title_regex: Option<Regex>,
+}
+
+impl<'a> PrerequisiteMatcher<'a> {
+    pub fn new(known: &'a [KnownModule]) -> Self {
+        // Prefer the newest version for number references
+        let mut by_id: HashMap<i32, &KnownModule> = HashMap::new();
+        for m in known {
+            let entry = by_id.entry(m.id).or_insert(m);
+            if m.version > entry.version {
+                *entry = m;
+            }
+        }
+
+        // Longest titles first, so "Analysis II" wins over "Analysis I"
+        let mut modules: Vec<&KnownModule> = by_id
+            .values()
+            .copied()
+            .filter(|m| m.title.chars().count() >= MIN_TITLE_LENGTH)
+            .collect();
+        modules.sort_by(|a, b| b.title.len().cmp(&a.title.len()).then(a.id.cmp(&b.id)));
+
+        let mut by_title: HashMap<String, &KnownModule> = HashMap::new();
+        for m in &modules {
+            by_title.entry(m.title.to_lowercase()).or_insert(m);
+        }
+
+        let title_regex = if modules.is_empty() {
+            None
+        } else {
+            let alternatives: Vec<String> = modules.iter().map(|m| regex::escape(&m.title)).collect();
+            RegexBuilder::new(&alternatives.join("|"))
+                .case_insensitive(true)
+                .size_limit(1 << 28)
+                .build()
+                .ok()
+        };
+
+        PrerequisiteMatcher { by_id, by_title, title_regex }
+    }
+
+    /// Extract mentions of known modules from a requirements text
+    ///
+    /// Modules are recognized by their number (e.g. "#40033" or "Modul 40033") or
+    /// by their full title. Whether a mention is mandatory or recommended is taken
+    /// from the closest preceding "Wünschenswerte/Verpflichtende Voraussetzungen"
+    /// style heading; mentions without such a heading count as recommended.
+    pub fn extract(&self, requirements: &str, module_id: i32) -> Vec<PrerequisiteMatch> {
+        let mut matches: Vec<PrerequisiteMatch> = Vec::new();
+        let mut kind = db::PrerequisiteKind::Recommended;
+
+        for line in requirements.lines() {
+            if let Some(heading_kind) = classify_line(line) {
+                kind = heading_kind;
+            }
+
+            // Number references
+            for caps in NUMBER_REGEX.captures_iter(line) {
+                let Ok(number) = caps[1].parse::<i32>() else {
+                    continue;
+                };
+                if number == module_id {
+                    continue;
+                }
+                if let Some(m) = self.by_id.get(&number) {
+                    push_match(&mut matches, m, kind, caps[0].to_string());
+                }
+            }
+
+            // Title references, offsets come from the line itself so slicing it is safe
+            let Some(title_regex) = &self.title_regex else {
+                continue;
+            };
+            let mut search_from = 0;
+            while let Some(found) = title_regex.find_at(line, search_from) {
+                if !is_word_boundary(line, found.start(), found.end()) {
+                    // Retry from the next character, a shorter title may still fit
+                    search_from = found.start() + line[found.start()..].chars().next().map_or(1, char::len_utf8);
+                    continue;
+                }
+                search_from = found.end();
+
+                let Some(m) = self.by_title.get(&found.as_str().to_lowercase()) else {
+                    continue;
+                };
+                if m.id != module_id {
+                    push_match(&mut matches, m, kind, found.as_str().to_string());
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+/// Resolve prerequisite mentions for all modules scraped in the given run
+///
+/// Mentions are matched against the latest snapshot of every known module, so
+/// this runs once after all modules of the run have been inserted.
+pub async fn resolve_prerequisites(pool: &PgPool, scraping_run_id: i32) -> Result<usize> {
+    let known: Vec<KnownModule> = sqlx::query!(
+        r#"
+        SELECT DISTINCT ON (id, version) id, version, title
+        FROM module
+        ORDER BY id, version, scraping_run_id DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load known modules")?
+    .into_iter()
+    .map(|row| KnownModule {
+        id: row.id,
+        version: row.version,
+        title: row.title,
+    })
+    .collect();
+
+    let modules = sqlx::query!(
+        r#"
+        SELECT id, version, requirements as "requirements!"
+        FROM module
+        WHERE scraping_run_id = $1 AND requirements IS NOT NULL
+        "#,
+        scraping_run_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to load module requirements")?;
+
+    let matcher = PrerequisiteMatcher::new(&known);
+    let mut resolved = 0;
+    for module in modules {
+        let prerequisites = matcher.extract(&module.requirements, module.id);
+        if prerequisites.is_empty() {
+            continue;
+        }
+
+        resolved += prerequisites.len();
+        db_ops::insert_prerequisites(pool, module.id, module.version, scraping_run_id, &prerequisites).await?;
+    }
+
+    Ok(resolved)
+}
+
+fn classify_line(line: &str) -> Option<db::PrerequisiteKind> {
+    let lower = line.to_lowercase();
+
+    if ["verpflichtend", "obligatorisch", "zwingend", "mandatory", "compulsory"]
+        .iter()
+        .any(|k| lower.contains(k))
+    {
+        Some(db::PrerequisiteKind::Mandatory)
+    } else if ["wünschenswert", "empfohlen", "empfehlenswert", "recommended", "desirable"]
+        .iter()
+        .any(|k| lower.contains(k))
+    {
+        Some(db::PrerequisiteKind::Recommended)
+    } else {
+        None
+    }
+}
+
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(|c| c.is_alphanumeric()) && !after.is_some_and(|c| c.is_alphanumeric())
+}
+
+fn push_match(
+    matches: &mut Vec<PrerequisiteMatch>,
+    module: &KnownModule,
+    kind: db::PrerequisiteKind,
+    matched_text: String,
+) {
+    if let Some(existing) = matches.iter_mut().find(|m| m.required_module_id == module.id) {
+        // A mandatory mention outweighs a recommended one
+        if kind == db::PrerequisiteKind::Mandatory {
+            existing.kind = kind;
+        }
+        return;
+    }
+
+    matches.push(PrerequisiteMatch {
+        required_module_id: module.id,
+        required_module_version: module.version,
+        kind,
+        matched_text,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known() -> Vec<KnownModule> {
+        vec![
+            KnownModule { id: 40033, version: 1, title: "Analysis I für Ingenieurwissenschaften".to_string() },
+            KnownModule { id: 40033, version: 3, title: "Analysis I für Ingenieurwissenschaften".to_string() },
+            KnownModule { id: 40034, version: 2, title: "Analysis II für Ingenieurwissenschaften".to_string() },
+            KnownModule { id: 50830, version: 2, title: "Algorithmen und Datenstrukturen".to_string() },
+        ]
+    }
+
+    #[test]
+    fn test_extract_prerequisites() {
+        let text = "Wünschenswerte Voraussetzungen für die Teilnahme an den Lehrveranstaltungen:\n\
+                    Kenntnisse aus Algorithmen und Datenstrukturen\n\
+                    Verpflichtende Voraussetzungen für die Modulprüfungsanmeldung:\n\
+                    Erfolgreicher Abschluss von #40034 sowie Analysis I für Ingenieurwissenschaften";
+
+        let known = known();
+        let matches = PrerequisiteMatcher::new(&known).extract(text, 12345);
+
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].required_module_id, 50830);
+        assert_eq!(matches[0].kind, db::PrerequisiteKind::Recommended);
+        assert_eq!(matches[1].required_module_id, 40034);
+        assert_eq!(matches[1].kind, db::PrerequisiteKind::Mandatory);
+        assert_eq!(matches[2].required_module_id, 40033);
+        assert_eq!(matches[2].required_module_version, 3);
+        assert_eq!(matches[2].kind, db::PrerequisiteKind::Mandatory);
+    }
+
+    #[test]
+    fn test_extract_prerequisites_ignores_self_and_partial_titles() {
+        let text = "Analysis II für Ingenieurwissenschaften, siehe auch Modul 50830";
+        let known = known();
+        let matches = PrerequisiteMatcher::new(&known).extract(text, 50830);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].required_module_id, 40034);
+    }
+
+    #[test]
+    fn test_extract_prerequisites_ignores_case_and_keeps_original_text() {
+        // "İ" lowercases to two chars, so offsets into a lowercased copy would not line up
+        let text = "İİ Kenntnisse in ALGORITHMEN UND DATENSTRUKTUREN sowie analysis ii für ingenieurwissenschaften";
+        let known = known();
+        let matches = PrerequisiteMatcher::new(&known).extract(text, 12345);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].required_module_id, 50830);
+        assert_eq!(matches[0].matched_text, "ALGORITHMEN UND DATENSTRUKTUREN");
+        assert_eq!(matches[1].required_module_id, 40034);
+        assert_eq!(matches[1].matched_text, "analysis ii für ingenieurwissenschaften");
+    }
+
+    #[test]
+    fn test_extract_prerequisites_requires_word_boundaries() {
+        let text = "Algorithmen und Datenstrukturenlehre, Analysis I für Ingenieurwissenschaften";
+        let known = known();
+        let matches = PrerequisiteMatcher::new(&known).extract(text, 12345);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].required_module_id, 40033);
+    }
+}
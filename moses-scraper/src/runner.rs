@@ -3,7 +3,7 @@ use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use crate::{db_ops, mapper, module, search::ModuleRef};
+use crate::{changes::{self, ChangeSummary}, db_ops, discontinued, mapper, module, prerequisites, search::ModuleRef};
 
 /// Configuration for a scraping run
 #[derive(Debug, Clone)]
@@ -149,6 +149,12 @@ where
         }
     }
 
+    // Resolve prerequisite mentions now that all modules of this run are known
+    match prerequisites::resolve_prerequisites(&pool, scraping_run_id).await {
+        Ok(count) => tracing::info!("Resolved {} prerequisite relations", count),
+        Err(e) => tracing::warn!("Failed to resolve prerequisites: {}", e),
+    }
+
     // Get final progress
     let final_progress = {
         let p = progress.read().await;
@@ -164,6 +170,64 @@ where
     Ok(final_progress)
 }
 
+/// Mark a run as completed and generate its change report
+///
+/// Used by both the CLI and the web scraper so every finished run gets a report.
+/// `listed` holds the (number, version) keys of the full export for runs that
+/// covered all modules; modules missing from it are marked as discontinued and
+/// reported as removed. Partial runs pass `None`.
+pub async fn complete_run(
+    pool: &PgPool,
+    scraping_run_id: i32,
+    progress: &ScraperProgress,
+    listed: Option<&[(i32, i32)]>,
+) -> Result<ChangeSummary> {
+    sqlx::query!(
+        r#"
+        UPDATE scraping_run
+        SET completed_at = NOW(),
+            status = 'completed',
+            successful_modules = $1,
+            failed_modules = $2,
+            skipped_modules = $3,
+            full_run = $4
+        WHERE id = $5
+        "#,
+        progress.successful as i32,
+        progress.failed as i32,
+        progress.skipped as i32,
+        listed.is_some(),
+        scraping_run_id
+    )
+    .execute(pool)
+    .await?;
+
+    let mut summary = changes::generate_change_report(pool, scraping_run_id, listed).await?;
+
+    if let Some(listed) = listed {
+        summary.discontinued = discontinued::mark_discontinued(pool, scraping_run_id, listed).await?;
+    }
+
+    Ok(summary)
+}
+
+/// Mark a run as failed
+pub async fn fail_run(pool: &PgPool, scraping_run_id: i32) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE scraping_run
+        SET completed_at = NOW(),
+            status = 'failed'
+        WHERE id = $1
+        "#,
+        scraping_run_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 async fn process_module(
     pool: &PgPool,
     module_ref: &ModuleRef,